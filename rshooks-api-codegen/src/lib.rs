@@ -0,0 +1,376 @@
+//! Generates the host code enums of `rshooks-api` from the C headers
+//!
+//! [FieldId], [TxnType] and [Error] are generated from `sfcodes.h`, `tts.h` and `error.h`
+//! into `src/api/codes.rs`. Run `cargo run -p rshooks-api-codegen` after updating
+//! `c_bindings/h`, the tests of this crate fail while the generated file is out of date.
+//!
+//! [FieldId]: https://docs.rs/rshooks-api/latest/rshooks_api/enum.FieldId.html
+//! [TxnType]: https://docs.rs/rshooks-api/latest/rshooks_api/enum.TxnType.html
+//! [Error]: https://docs.rs/rshooks-api/latest/rshooks_api/enum.Error.html
+
+#![deny(warnings, clippy::all, missing_docs, unreachable_pub)]
+
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Header defining the serialized field codes
+pub const SFCODES_H: &str = "c_bindings/h/sfcodes.h";
+/// Header defining the transaction type codes
+pub const TTS_H: &str = "c_bindings/h/tts.h";
+/// Header defining the error codes
+pub const ERROR_H: &str = "c_bindings/h/error.h";
+/// Header defining the keylet types, including all others
+pub const HOOKAPI_H: &str = "c_bindings/h/hookapi.h";
+/// Bindings generated by `bindgen` from [HOOKAPI_H]
+pub const BINDINGS_RS: &str = "c_bindings/bindings.rs";
+/// Generated enums
+pub const CODES_RS: &str = "src/api/codes.rs";
+
+/// Words of the header names not spelled as plain title case in Rust
+const WORD_OVERRIDES: &[(&str, &str)] = &[("NFTOKEN", "NFToken"), ("URITOKEN", "URIToken")];
+
+/// Header names whose Rust names predate the generator
+const NAME_OVERRIDES: &[(&str, &str)] = &[("RESERVE_INSUFFICIENT", "ReserveInssuficient")];
+
+/// Documentation of the error codes, which `error.h` doesn't carry
+const ERROR_DOCS: &[(&str, &str)] = &[
+    ("OUT_OF_BOUNDS", "A pointer or buffer length provided as a parameter described memory outside of the Hook's allowed memory region."),
+    ("INTERNAL_ERROR", "Reserved for internal invariant trips, generally unrelated to inputs.\nThese should be reported with an issue."),
+    ("TOO_BIG", "Attempted to set a parameter or value larger than the allowed space ."),
+    ("TOO_SMALL", "The API was unable to produce output to the write_ptr because the specified write_len was too small"),
+    ("DOESNT_EXIST", "The requested object or item wasn't found"),
+    ("NO_FREE_SLOTS", "The Hook attempted to allocate an item into a slot, but there were no slots free.\nTo avoid ensure re-use of existing slots. The maximum number of slots is 255."),
+    ("INVALID_ARGUMENT", "One or more of the parameters to the API were invalid according to the individual API's specification."),
+    ("ALREADY_SET", "Some APIs allow for a once-per-execution parameter to be set.\nA second attempt to set a once-per-execution parameter results in this error."),
+    ("PREREQUISITE_NOT_MET", "An API required the Hook to do something before the API is allowed to be called.\nCheck the API's documentation."),
+    ("FEE_TOO_LARGE", "During fee calculation if an absurdly large fee is calculated this error is returned."),
+    ("EMISSION_FAILURE", "An attempt to emit() a TXN was unsccessful for any of a number of reasons.\nCheck the trace log of the rippled to which you are submitting the originating TXN."),
+    ("TOO_MANY_NONCES", "A Hook may only use up to 256 calls to nonce() per execution.\nFurther calls result in this error code."),
+    ("TOO_MANY_EMITTED_TXN", "A Hook must declare ahead of time how many TXN it intends to emit().\nIf it emits fewer than this many, this is allowed.\nIf it emits more than this many this error is returned."),
+    ("NOT_IMPLEMENTED", "While Hooks is/was in development an API may return this if some or all of that API is planned but not yet implemented."),
+    ("INVALID_ACCOUNT", "An API which accepts a 20 byte Account ID may return this if, in its opinion, the Account ID was not valid for any reason."),
+    ("GUARD_VIOLATION", "All loops inside a Hook must declare at the top of the loop, as the first non trivial instruction,\nbefore any branch instruction, the promised maximum number of iterations of the loop.\nIf this promise is violated the hook terminates immediately with this error code."),
+    ("INVALID_FIELD", "The requested serialized field could not be found in the specified object."),
+    ("PARSE_ERROR", "While parsing serialized content an error was encountered (typically indicating an invalidly serialized object)."),
+    ("RC_ROLLBACK", "Used internally to communicate a rollback event."),
+    ("RC_ACCEPT", "Used internally to communicate an accept event."),
+    ("NO_SUCH_KEYLET", "Specified keylet could not be found, or keylet is invalid"),
+    ("NOT_AN_ARRAY", "API was asked to assume object under analysis is an STArray but it was not."),
+    ("NOT_AN_OBJECT", "API was asked to assume object under analysis is an STObject but it was not."),
+    ("INVALID_FLOAT", "A floating point operation resulted in Not-A-Number or API call attempted to specify an XFL floating point number outside of the expressible range of XFL."),
+    ("DIVISION_BY_ZERO", "API call would result in a division by zero, so API ended early."),
+    ("MANTISSA_OVERSIZED", "When attempting to create an XFL the mantissa must be 16 decimal digits."),
+    ("MANTISSA_UNDERSIZED", "When attempting to create an XFL the mantissa must be 16 decimal digits."),
+    ("EXPONENT_OVERSIZED", "When attempting to create an XFL the exponent must not exceed 80."),
+    ("EXPONENT_UNDERSIZED", "When attempting to create an XFL the exponent must not be less than -96."),
+    ("XFL_OVERFLOW", "A floating point operation done on an XFL resulted in a value larger than XFL format is able to represent."),
+    ("NOT_IOU_AMOUNT", "An API assumed an STAmount was an IOU when in fact it was XRP."),
+    ("NOT_AN_AMOUNT", "An API assumed an STObject was an STAmount when in fact it was not."),
+    ("CANT_RETURN_NEGATIVE", "An API would have returned a negative integer except that negative integers are reserved for error codes (i.e. what you are reading.)"),
+    ("NOT_AUTHORIZED", "Hook attempted to set foreign state but was not authorized to do so (grant was missing or invalid.)"),
+    ("PREVIOUS_FAILURE_PREVENTS_RETRY", "Hook previously received a NOT_AUTHORIZED return code and is not allowed to retry."),
+    ("TOO_MANY_PARAMS", "Attempted to set a hook parameter for a later hook in the chain, but there are now too many parameters."),
+    ("INVALID_TXN", "Serialized transaction was not a valid transaction (usually because of a missing required field or data corruption / truncation.)"),
+    ("RESERVE_INSUFFICIENT", "Setting an additional state object on this account would cause the reserve requirements to exceed the account's balance."),
+    ("COMPLEX_NOT_SUPPORTED", "Hook API would be forced to return a complex number, which it cannot do."),
+    ("DOES_NOT_MATCH", "Two arguments were required to be of the same type but are not."),
+    ("INVALID_KEY", "The provided public key was not valid."),
+    ("NOT_A_STRING", "The buffer did not contain a nul terminated string."),
+    ("MEM_OVERLAP", "The writing pointer points to a buffer that overlaps with the reading pointer."),
+    ("TOO_MANY_STATE_MODIFICATIONS", "More than 5000 modified state entries in the combined hook chains"),
+    ("TOO_MANY_NAMESPACES", "More than 256 namespaces on this account"),];
+
+/// A `#define` of a header with an integer value
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Code {
+    /// Name of the define
+    pub name: String,
+    /// Evaluated value
+    pub value: i64,
+}
+
+/// Codes defined by the C headers
+#[derive(Clone, Debug, Default)]
+pub struct Headers {
+    /// `sf*` defines of `sfcodes.h`
+    pub fields: Vec<Code>,
+    /// `tt*` defines of `tts.h`
+    pub txn_types: Vec<Code>,
+    /// Negative defines of `error.h`
+    pub errors: Vec<Code>,
+    /// `KEYLET_*` defines of `hookapi.h`
+    pub keylets: Vec<Code>,
+}
+
+impl Headers {
+    /// Reads the headers of the repository at `root`
+    pub fn read(root: &Path) -> Result<Self, String> {
+        let read = |path: &str| {
+            fs::read_to_string(root.join(path)).map_err(|err| format!("{path}: {err}"))
+        };
+
+        Ok(Self {
+            fields: codes(&read(SFCODES_H)?, |code| code.name.starts_with("sf"))?,
+            txn_types: codes(&read(TTS_H)?, |code| code.name.starts_with("tt"))?,
+            errors: codes(&read(ERROR_H)?, |code| code.value < 0)?,
+            keylets: codes(&read(HOOKAPI_H)?, |code| code.name.starts_with("KEYLET_"))?,
+        })
+    }
+}
+
+/// Root of the repository
+pub fn repository_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("the codegen crate is a member of the repository workspace")
+        .to_path_buf()
+}
+
+/// Parses the `#define NAME VALUE` lines of a header, skipping commented out lines
+/// and defines without a value
+fn codes(header: &str, keep: impl Fn(&Code) -> bool) -> Result<Vec<Code>, String> {
+    let mut codes = Vec::new();
+    for line in header.lines() {
+        let Some(define) = line.trim().strip_prefix("#define ") else {
+            continue;
+        };
+        let (name, value) = define
+            .trim()
+            .split_once(char::is_whitespace)
+            .unwrap_or((define, ""));
+        let value = value.split("//").next().unwrap_or_default().trim();
+        if value.is_empty() || name.contains('(') {
+            continue;
+        }
+        let Some(value) = evaluate(value) else {
+            continue;
+        };
+        let code = Code {
+            name: name.to_string(),
+            value,
+        };
+        if keep(&code) {
+            codes.push(code);
+        }
+    }
+
+    if codes.is_empty() {
+        Err("no codes found".to_string())
+    } else {
+        Ok(codes)
+    }
+}
+
+/// Evaluates `N`, `-N` and `((T << 16) + F)` values, with optional `U` suffixes
+fn evaluate(value: &str) -> Option<i64> {
+    let value: String = value
+        .chars()
+        .filter(|c| !matches!(c, '(' | ')' | 'U' | ' '))
+        .collect();
+
+    match value.split_once('+') {
+        Some((shifted, field)) => {
+            let (type_code, shift) = shifted.split_once("<<")?;
+            Some(
+                (type_code.parse::<i64>().ok()? << shift.parse::<i64>().ok()?)
+                    + field.parse::<i64>().ok()?,
+            )
+        }
+        None => value.parse().ok(),
+    }
+}
+
+/// Rust name of a header name, `ttNFTOKEN_MINT` becomes `NFTokenMint`
+pub fn variant_name(name: &str, prefix: &str) -> String {
+    let name = name.strip_prefix(prefix).unwrap_or(name);
+    if let Some((_, rust)) = NAME_OVERRIDES.iter().find(|(c, _)| *c == name) {
+        return rust.to_string();
+    }
+
+    let mut rust = String::new();
+    for word in name.split('_') {
+        match WORD_OVERRIDES.iter().find(|(c, _)| *c == word) {
+            Some((_, word)) => rust.push_str(word),
+            None => {
+                let mut chars = word.chars();
+                if let Some(first) = chars.next() {
+                    rust.push(first.to_ascii_uppercase());
+                    rust.extend(chars.map(|c| c.to_ascii_lowercase()));
+                }
+            }
+        }
+    }
+    rust
+}
+
+/// Generates the content of [CODES_RS]
+pub fn generate(headers: &Headers) -> Result<String, String> {
+    let mut out = String::new();
+    out.push_str(concat!(
+        "//! Host codes generated from `c_bindings/h` by `rshooks-api-codegen`\n",
+        "//!\n",
+        "//! Do not edit, run `cargo run -p rshooks-api-codegen` instead.\n",
+        "\n",
+        "use super::Unknown;\n",
+        "use crate::_c;\n",
+    ));
+
+    out.push_str(concat!(
+        "\n",
+        "code_enum! {\n",
+        "    /// Transaction type\n",
+        "    #[allow(missing_docs)]\n",
+        "    #[derive(Clone, Copy)]\n",
+        "    pub enum TxnType: u32 {\n",
+    ));
+    for code in &headers.txn_types {
+        variant(&mut out, &variant_name(&code.name, "tt"), &code.name);
+    }
+    out.push_str("    }\n}\n");
+
+    out.push_str(concat!(
+        "\n",
+        "code_enum! {\n",
+        "    /// Field type\n",
+        "    #[allow(missing_docs)]\n",
+        "    #[derive(Clone, Copy)]\n",
+        "    pub enum FieldId: u32 {\n",
+    ));
+    for code in &headers.fields {
+        variant(
+            &mut out,
+            code.name.strip_prefix("sf").unwrap_or(&code.name),
+            &code.name,
+        );
+    }
+    out.push_str("    }\n}\n");
+
+    out.push_str(concat!(
+        "\n",
+        "code_enum! {\n",
+        "    /// Possible errors returned by Hook APIs.\n",
+        "    ///\n",
+        "    /// Errors are global across all Hook APIs. Non-negative return codes refer always to success\n",
+        "    /// and usually indicate the number of bytes written or events performed, depending on the specific API.\n",
+        "    #[derive(Clone, Copy)]\n",
+        "    pub enum Error: i32 {\n",
+    ));
+    for code in &headers.errors {
+        let doc = ERROR_DOCS
+            .iter()
+            .find(|(name, _)| *name == code.name)
+            .map(|(_, doc)| *doc)
+            .ok_or_else(|| format!("{}: add its documentation to ERROR_DOCS", code.name))?;
+        for line in doc.lines() {
+            writeln!(out, "        /// {line}").unwrap();
+        }
+        variant(&mut out, &variant_name(&code.name, ""), &code.name);
+    }
+    out.push_str(concat!(
+        "    }\n",
+        "\n",
+        "    /// Error code not known to this crate, such as one added by a later amendment\n",
+        "    Unknown => \"UNKNOWN\"\n",
+        "}\n",
+    ));
+
+    Ok(out)
+}
+
+fn variant(out: &mut String, rust: &str, name: &str) {
+    writeln!(out, "        {rust} = _c::{name} => \"{name}\",").unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(path: &str) -> String {
+        fs::read_to_string(repository_root().join(path)).unwrap()
+    }
+
+    #[test]
+    fn codes_test() {
+        let headers = Headers::read(&repository_root()).unwrap();
+        assert!(
+            generate(&headers).unwrap() == read(CODES_RS),
+            "{CODES_RS} disagrees with the headers, run `cargo run -p rshooks-api-codegen`"
+        );
+    }
+
+    #[test]
+    fn bindings_test() {
+        let headers = Headers::read(&repository_root()).unwrap();
+        let bindings = read(BINDINGS_RS);
+        for code in headers
+            .fields
+            .iter()
+            .chain(&headers.txn_types)
+            .chain(&headers.errors)
+            .chain(&headers.keylets)
+        {
+            let prefix = format!("pub const {}: ", code.name);
+            let value = bindings
+                .lines()
+                .find_map(|line| line.strip_prefix(&prefix))
+                .and_then(|line| line.split_once(" = "))
+                .and_then(|(_, value)| value.trim_end_matches(';').parse::<i64>().ok());
+            assert_eq!(
+                value,
+                Some(code.value),
+                "{BINDINGS_RS} disagrees with the headers on {}, run `cargo run -p rshooks-api-codegen`",
+                code.name
+            );
+        }
+    }
+
+    #[test]
+    fn keylets_test() {
+        let headers = Headers::read(&repository_root()).unwrap();
+        let util = read("src/api/util.rs");
+        for code in &headers.keylets {
+            assert!(
+                util.contains(&format!("_c::{},", code.name))
+                    || util.contains(&format!("_c::{})", code.name)),
+                "util_keylet doesn't handle {}",
+                code.name
+            );
+        }
+    }
+
+    #[test]
+    fn parse_test() {
+        let header = concat!(
+            "#ifndef CODES\n",
+            "#define CODES\n",
+            "#define sfAccount ((8U << 16U) + 1U)\n",
+            "// #define ttNICKNAME_SET 6 // deprecated\n",
+            "#define ttINVOKE 99\n",
+            "#define TOO_SMALL -4\n",
+            "#define SBUF(str) (uint32_t)(str), sizeof(str)\n",
+            "#endif\n",
+        );
+        let codes = codes(header, |_| true).unwrap();
+        let names: Vec<_> = codes.iter().map(|c| (c.name.as_str(), c.value)).collect();
+        assert_eq!(
+            names,
+            [("sfAccount", 0x80001), ("ttINVOKE", 99), ("TOO_SMALL", -4)]
+        );
+    }
+
+    #[test]
+    fn variant_name_test() {
+        assert_eq!(
+            variant_name("ttNFTOKEN_CREATE_OFFER", "tt"),
+            "NFTokenCreateOffer"
+        );
+        assert_eq!(variant_name("ttURITOKEN_BUY", "tt"), "URITokenBuy");
+        assert_eq!(variant_name("ttUNL_MODIFY", "tt"), "UnlModify");
+        assert_eq!(variant_name("XFL_OVERFLOW", ""), "XflOverflow");
+        assert_eq!(
+            variant_name("RESERVE_INSUFFICIENT", ""),
+            "ReserveInssuficient"
+        );
+    }
+}
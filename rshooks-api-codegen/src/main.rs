@@ -0,0 +1,61 @@
+//! Regenerates `src/api/codes.rs` and `c_bindings/bindings.rs` from `c_bindings/h`
+//!
+//! ``` txt
+//! cargo run -p rshooks-api-codegen [-- --skip-bindgen]
+//! ```
+//!
+//! The bindings are generated by the `bindgen` command line tool, which must be installed
+//! unless `--skip-bindgen` is passed.
+
+use std::fs;
+use std::process::{Command, ExitCode};
+
+use rshooks_api_codegen::{generate, repository_root, Headers, BINDINGS_RS, CODES_RS, HOOKAPI_H};
+
+fn main() -> ExitCode {
+    let skip_bindgen = std::env::args().any(|arg| arg == "--skip-bindgen");
+    let root = repository_root();
+
+    let codes = match Headers::read(&root).and_then(|headers| generate(&headers)) {
+        Ok(codes) => codes,
+        Err(err) => {
+            eprintln!("error: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Err(err) = fs::write(root.join(CODES_RS), codes) {
+        eprintln!("error: {CODES_RS}: {err}");
+        return ExitCode::FAILURE;
+    }
+    println!("OK: {CODES_RS}");
+
+    if skip_bindgen {
+        return ExitCode::SUCCESS;
+    }
+
+    let status = Command::new("bindgen")
+        .current_dir(&root)
+        .args([
+            "--blocklist-item=.*stdint.*",
+            "--use-core",
+            "--generate=functions,vars",
+            HOOKAPI_H,
+            "-o",
+            BINDINGS_RS,
+        ])
+        .status();
+    match status {
+        Ok(status) if status.success() => {
+            println!("OK: {BINDINGS_RS}");
+            ExitCode::SUCCESS
+        }
+        Ok(status) => {
+            eprintln!("error: bindgen exited with {status}");
+            ExitCode::FAILURE
+        }
+        Err(err) => {
+            eprintln!("error: bindgen: {err}, install it with `cargo install bindgen-cli`");
+            ExitCode::FAILURE
+        }
+    }
+}
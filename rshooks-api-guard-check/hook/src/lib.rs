@@ -0,0 +1,36 @@
+//! Hook built by CI for the guard checker
+//!
+//! Uses the host-independent code that holds loops or replaces them, so that loops
+//! the compiler adds or brings in are found.
+
+#![no_std]
+
+use rshooks_api::*;
+
+#[hook]
+fn main() {
+    let mut decimal = [0u8; XFL_DECIMAL_MAX_LEN];
+    let decimal = otxn_param(&mut decimal, b"AMT").expect(b"no amount");
+    let amount = XFL::parse_decimal(decimal).expect(b"invalid amount");
+
+    let fee =
+        xfl::float_mulratio(amount, true, 25, decimal.len() as u32 * 1_000).expect(b"invalid fee");
+    let net = xfl::float_sum(amount, fee).expect(b"invalid net");
+    let ratio = xfl::float_divide(net, amount).expect(b"invalid ratio");
+    let scale = xfl::float_set(-2, decimal.len() as i64).expect(b"invalid scale");
+    let scaled = xfl::float_multiply(ratio, scale).expect(b"invalid scaled");
+    if xfl::float_int(scaled, 2, true).expect(b"invalid int") > 100 {
+        rollback(b"too long", 1);
+    }
+
+    let mut buf = [0u8; 64];
+    let len = ratio.write_decimal(&mut buf).expect(b"write error");
+    let _ = trace(b"amount", &buf[..len], DataRepr::AsUTF8);
+
+    let mut object = [0u8; 256];
+    let object = otxn_param(&mut object, b"OBJ").expect(b"no object");
+    for field in sto::Reader::new(object) {
+        let field = field.expect(b"invalid object");
+        let _ = trace_num(b"field", field.type_code() as i64);
+    }
+}
@@ -0,0 +1,395 @@
+//! Checks that every loop of a compiled hook starts with a guard
+//!
+//! The guard checker of the ledger rejects a hook unless each `loop` instruction is
+//! directly followed by `i32.const`, `i32.const` and `call $_g`. Loops the compiler
+//! adds around guarded loops, or loops of host-independent code such as the `xfl`
+//! module, are only found in the compiled hook, so CI builds one and checks it.
+//!
+//! The empty `loop {}` of the panic handler, whose body is a single `br 0`, is allowed.
+
+#![deny(warnings, clippy::all, missing_docs, unreachable_pub)]
+
+use std::collections::HashMap;
+use std::fmt;
+
+/// Loop of a hook that doesn't start with a guard
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnguardedLoop {
+    /// Index of the function holding the loop
+    pub function: u32,
+    /// Name of the function, if the module has a name section
+    pub name: Option<String>,
+    /// Offset of the `loop` instruction in the module
+    pub offset: usize,
+}
+
+impl fmt::Display for UnguardedLoop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unguarded loop at {:#x} in function {}",
+            self.offset, self.function
+        )?;
+        if let Some(name) = &self.name {
+            write!(f, " ({name})")?;
+        }
+        Ok(())
+    }
+}
+
+/// Loops of a hook
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Loops {
+    /// Number of loops starting with a guard
+    pub guarded: usize,
+    /// Loops not starting with a guard
+    pub unguarded: Vec<UnguardedLoop>,
+}
+
+/// Finds the loops of a wasm module that don't start with a guard
+///
+/// Fails if the module is malformed or uses instructions hooks can't use.
+pub fn check(wasm: &[u8]) -> Result<Loops, String> {
+    let mut module = Bytes { wasm, pos: 0 };
+    if module.take(8)? != b"\0asm\x01\0\0\0" {
+        return Err("not a wasm module".into());
+    }
+
+    let mut guard = None;
+    let mut imported = 0;
+    let mut bodies = Vec::new();
+    let mut names = HashMap::new();
+    while module.pos < wasm.len() {
+        let id = module.byte()?;
+        let size = module.u32()? as usize;
+        let mut section = Bytes {
+            wasm,
+            pos: module.pos,
+        };
+        module.take(size)?;
+        match id {
+            0 if section.name()? == "name" => {
+                names = function_names(&mut section, module.pos)?;
+            }
+            2 => {
+                for _ in 0..section.u32()? {
+                    let (import, field) = (section.name()?, section.name()?);
+                    match section.byte()? {
+                        0 => {
+                            section.u32()?;
+                            if (import, field) == ("env", "_g") {
+                                guard = Some(imported);
+                            }
+                            imported += 1;
+                        }
+                        1 => {
+                            section.byte()?;
+                            section.limits()?;
+                        }
+                        2 => section.limits()?,
+                        3 => {
+                            section.take(2)?;
+                        }
+                        kind => return Err(format!("unknown import kind {kind}")),
+                    }
+                }
+            }
+            10 => {
+                for _ in 0..section.u32()? {
+                    let size = section.u32()? as usize;
+                    bodies.push(section.pos..section.pos + size);
+                    section.take(size)?;
+                }
+            }
+            _ => (),
+        }
+    }
+
+    let mut loops = Loops::default();
+    for (i, body) in bodies.into_iter().enumerate() {
+        let function = imported + i as u32;
+        let code = instructions(wasm, body)?;
+        for (at, instruction) in code.iter().enumerate() {
+            if instruction.opcode != LOOP {
+                continue;
+            }
+            let next = |n: usize| code.get(at + n);
+            let guarded = matches!(
+                (next(1), next(2), next(3)),
+                (Some(a), Some(b), Some(call))
+                    if a.opcode == I32_CONST && b.opcode == I32_CONST
+                        && call.opcode == CALL && Some(call.index) == guard
+            );
+            let panic_handler = matches!(
+                (next(1), next(2)),
+                (Some(br), Some(end)) if br.opcode == BR && br.index == 0 && end.opcode == END
+            );
+            if guarded {
+                loops.guarded += 1;
+            } else if !panic_handler {
+                loops.unguarded.push(UnguardedLoop {
+                    function,
+                    name: names.get(&function).cloned(),
+                    offset: instruction.offset,
+                });
+            }
+        }
+    }
+    Ok(loops)
+}
+
+const LOOP: u8 = 0x03;
+const END: u8 = 0x0B;
+const BR: u8 = 0x0C;
+const CALL: u8 = 0x10;
+const I32_CONST: u8 = 0x41;
+
+/// Instruction with its first immediate, enough to recognize guards
+struct Instruction {
+    offset: usize,
+    opcode: u8,
+    index: u32,
+}
+
+/// Decodes the instructions of a function body
+fn instructions(wasm: &[u8], body: std::ops::Range<usize>) -> Result<Vec<Instruction>, String> {
+    let mut code = Bytes {
+        wasm: &wasm[..body.end],
+        pos: body.start,
+    };
+    for _ in 0..code.u32()? {
+        code.u32()?;
+        code.byte()?;
+    }
+
+    let mut instructions = Vec::new();
+    while code.pos < body.end {
+        let offset = code.pos;
+        let opcode = code.byte()?;
+        let mut index = 0;
+        match opcode {
+            // block, loop, if
+            0x02..=0x04 => code.block_type()?,
+            // br, br_if, call, local and global accessors, table.get, table.set, ref.func
+            0x0C | 0x0D | 0x10 | 0x20..=0x26 | 0xD2 => index = code.u32()?,
+            // br_table
+            0x0E => {
+                for _ in 0..=code.u32()? {
+                    code.u32()?;
+                }
+            }
+            // call_indirect
+            0x11 => {
+                code.u32()?;
+                code.u32()?;
+            }
+            // typed select
+            0x1C => {
+                let count = code.u32()? as usize;
+                code.take(count)?;
+            }
+            // loads and stores
+            0x28..=0x3E => {
+                code.u32()?;
+                code.u32()?;
+            }
+            // memory.size, memory.grow, ref.null
+            0x3F | 0x40 | 0xD0 => {
+                code.byte()?;
+            }
+            // i32.const, i64.const
+            0x41 | 0x42 => code.i64()?,
+            0x43 => {
+                code.take(4)?;
+            }
+            0x44 => {
+                code.take(8)?;
+            }
+            0xFC => match code.u32()? {
+                0..=7 => (),
+                8 | 12 | 14 => {
+                    code.u32()?;
+                    code.u32()?;
+                }
+                9 | 11 | 13 | 15..=17 => {
+                    code.u32()?;
+                }
+                10 => {
+                    code.take(2)?;
+                }
+                op => return Err(format!("unknown instruction 0xfc {op} at {offset:#x}")),
+            },
+            0x00 | 0x01 | 0x05 | 0x0B | 0x0F | 0x1A | 0x1B | 0x45..=0xC4 | 0xD1 => (),
+            _ => return Err(format!("unknown instruction {opcode:#04x} at {offset:#x}")),
+        }
+        instructions.push(Instruction {
+            offset,
+            opcode,
+            index,
+        });
+    }
+    Ok(instructions)
+}
+
+/// Names of the functions in the name section
+fn function_names(section: &mut Bytes, end: usize) -> Result<HashMap<u32, String>, String> {
+    let mut names = HashMap::new();
+    while section.pos < end {
+        let id = section.byte()?;
+        let size = section.u32()? as usize;
+        if id != 1 {
+            section.take(size)?;
+            continue;
+        }
+        for _ in 0..section.u32()? {
+            let index = section.u32()?;
+            names.insert(index, section.name()?.to_string());
+        }
+    }
+    Ok(names)
+}
+
+/// Reader of the binary encoding
+struct Bytes<'a> {
+    wasm: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Bytes<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        match self.wasm.get(self.pos..self.pos + len) {
+            Some(bytes) => {
+                self.pos += len;
+                Ok(bytes)
+            }
+            None => Err(format!("unexpected end at {:#x}", self.pos)),
+        }
+    }
+
+    fn byte(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        let mut value = 0u64;
+        for shift in (0..35).step_by(7) {
+            let byte = self.byte()?;
+            value |= u64::from(byte & 0x7F) << shift;
+            if byte & 0x80 == 0 {
+                return u32::try_from(value).map_err(|_| format!("invalid u32 at {:#x}", self.pos));
+            }
+        }
+        Err(format!("invalid u32 at {:#x}", self.pos))
+    }
+
+    /// Signed integer of up to 64 bits, only skipped
+    fn i64(&mut self) -> Result<(), String> {
+        for _ in 0..10 {
+            if self.byte()? & 0x80 == 0 {
+                return Ok(());
+            }
+        }
+        Err(format!("invalid integer at {:#x}", self.pos))
+    }
+
+    fn name(&mut self) -> Result<&'a str, String> {
+        let len = self.u32()? as usize;
+        let pos = self.pos;
+        std::str::from_utf8(self.take(len)?).map_err(|_| format!("invalid name at {pos:#x}"))
+    }
+
+    fn limits(&mut self) -> Result<(), String> {
+        let max = self.byte()? & 1 == 1;
+        self.u32()?;
+        if max {
+            self.u32()?;
+        }
+        Ok(())
+    }
+
+    fn block_type(&mut self) -> Result<(), String> {
+        match self.wasm.get(self.pos) {
+            // empty or a value type
+            Some(0x40 | 0x6F..=0x7F) => self.byte().map(|_| ()),
+            // type index
+            Some(_) => self.i64(),
+            None => Err(format!("unexpected end at {:#x}", self.pos)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Module importing `_g` and holding a function with `body`
+    fn module(body: &[u8]) -> Vec<u8> {
+        let mut wasm = b"\0asm\x01\0\0\0".to_vec();
+        let mut section = |id: u8, content: &[u8]| {
+            wasm.push(id);
+            wasm.push(content.len() as u8);
+            wasm.extend_from_slice(content);
+        };
+        // (func (param i32 i32) (result i32)), (func)
+        section(1, &[2, 0x60, 2, 0x7F, 0x7F, 1, 0x7F, 0x60, 0, 0]);
+        section(2, &[1, 3, b'e', b'n', b'v', 2, b'_', b'g', 0, 0]);
+        section(3, &[1, 1]);
+        let mut code = vec![1, body.len() as u8 + 2, 0];
+        code.extend_from_slice(body);
+        code.push(END);
+        section(10, &code);
+        wasm
+    }
+
+    #[test]
+    fn guarded_loop_test() {
+        // loop, i32.const 1, i32.const 10, call $_g, drop, br_if 0 (i32.const 0), end
+        let loops = check(&module(&[
+            0x03, 0x40, 0x41, 1, 0x41, 10, 0x10, 0, 0x1A, 0x41, 0, 0x0D, 0, 0x0B,
+        ]));
+        assert_eq!(
+            loops,
+            Ok(Loops {
+                guarded: 1,
+                unguarded: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn unguarded_loop_test() {
+        // loop, i32.const 0, br_if 0, end
+        let loops = check(&module(&[0x03, 0x40, 0x41, 0, 0x0D, 0, 0x0B]));
+        assert_eq!(
+            loops,
+            Ok(Loops {
+                guarded: 0,
+                unguarded: vec![UnguardedLoop {
+                    function: 1,
+                    name: None,
+                    offset: 0x29,
+                }],
+            })
+        );
+
+        // the constants are there, but the call isn't to `_g`
+        let loops = check(&module(&[
+            0x03, 0x40, 0x41, 1, 0x41, 10, 0x10, 1, 0x1A, 0x0B,
+        ]));
+        assert!(matches!(loops, Ok(loops) if loops.unguarded.len() == 1));
+    }
+
+    #[test]
+    fn panic_handler_test() {
+        // loop, br 0, end
+        let loops = check(&module(&[0x03, 0x40, 0x0C, 0, 0x0B]));
+        assert_eq!(loops, Ok(Loops::default()));
+    }
+
+    #[test]
+    fn malformed_test() {
+        assert!(check(b"\0asm").is_err());
+        // unknown opcode
+        assert!(check(&module(&[0xFF])).is_err());
+    }
+}
@@ -0,0 +1,40 @@
+//! Checks that every loop of compiled hooks starts with a guard
+//!
+//! ``` txt
+//! cargo run -p rshooks-api-guard-check -- hook.wasm...
+//! ```
+
+use std::fs;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let paths: Vec<String> = std::env::args().skip(1).collect();
+    if paths.is_empty() {
+        eprintln!("usage: rshooks-api-guard-check hook.wasm...");
+        return ExitCode::FAILURE;
+    }
+
+    let mut status = ExitCode::SUCCESS;
+    for path in paths {
+        let loops = match fs::read(&path).map_err(|err| err.to_string()) {
+            Ok(wasm) => rshooks_api_guard_check::check(&wasm),
+            Err(err) => Err(err),
+        };
+        match loops {
+            Ok(loops) if loops.unguarded.is_empty() => {
+                println!("OK: {path}, {} guarded loops", loops.guarded);
+            }
+            Ok(loops) => {
+                for unguarded in &loops.unguarded {
+                    eprintln!("error: {path}: {unguarded}");
+                }
+                status = ExitCode::FAILURE;
+            }
+            Err(err) => {
+                eprintln!("error: {path}: {err}");
+                status = ExitCode::FAILURE;
+            }
+        }
+    }
+    status
+}
@@ -0,0 +1,290 @@
+//! Procedural macros for [rshooks-api](https://docs.rs/rshooks-api)
+//!
+//! Use them through the `rshooks_api` re-exports.
+
+#![deny(
+    warnings,
+    clippy::all,
+    missing_docs,
+    rustdoc::missing_crate_level_docs,
+    non_ascii_idents,
+    unreachable_pub
+)]
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{parse_macro_input, Error, ItemFn, ReturnType, Type};
+
+/// Hook entry point
+///
+/// Exports the annotated function as the `hook` symbol, calls the initial `_g`
+/// guard and accepts the originating transaction if the function returns.
+///
+/// The function may take an `ExecutionContext` argument.
+///
+/// # Example
+///
+/// ``` txt
+/// #[hook]
+/// fn main(ctx: ExecutionContext) {
+///     if ctx.is_weak() {
+///         accept(b"weak", 0);
+///     }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn hook(attr: TokenStream, item: TokenStream) -> TokenStream {
+    entry_point(
+        attr,
+        parse_macro_input!(item as ItemFn),
+        quote!(hook),
+        quote!(::rshooks_api::ExecutionContext),
+    )
+}
+
+/// Callback entry point
+///
+/// Exports the annotated function as the `cbak` symbol, calls the initial `_g`
+/// guard and accepts if the function returns.
+///
+/// The function may take a `CallbackContext` argument.
+///
+/// # Example
+///
+/// ``` txt
+/// #[cbak]
+/// fn callback(ctx: CallbackContext) {
+///     if ctx == CallbackContext::EmitFailure {
+///         rollback(b"emit failed", 0);
+///     }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn cbak(attr: TokenStream, item: TokenStream) -> TokenStream {
+    entry_point(
+        attr,
+        parse_macro_input!(item as ItemFn),
+        quote!(cbak),
+        quote!(::rshooks_api::CallbackContext),
+    )
+}
+
+fn entry_point(
+    attr: TokenStream,
+    item: ItemFn,
+    symbol: TokenStream2,
+    context: TokenStream2,
+) -> TokenStream {
+    match expand(attr.into(), item, symbol, context) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn expand(
+    attr: TokenStream2,
+    item: ItemFn,
+    symbol: TokenStream2,
+    context: TokenStream2,
+) -> syn::Result<TokenStream2> {
+    if !attr.is_empty() {
+        return Err(Error::new(
+            Span::call_site(),
+            "entry point attribute takes no arguments",
+        ));
+    }
+
+    let sig = &item.sig;
+
+    if sig.asyncness.is_some() || sig.constness.is_some() || sig.unsafety.is_some() {
+        return Err(Error::new_spanned(
+            sig.fn_token,
+            "entry point must be a plain `fn`",
+        ));
+    }
+
+    if !sig.generics.params.is_empty() || sig.generics.where_clause.is_some() {
+        return Err(Error::new_spanned(
+            &sig.generics,
+            "entry point cannot be generic",
+        ));
+    }
+
+    if let ReturnType::Type(_, ty) = &sig.output {
+        let valid = match &**ty {
+            Type::Never(_) => true,
+            Type::Tuple(tuple) => tuple.elems.is_empty(),
+            _ => false,
+        };
+
+        if !valid {
+            return Err(Error::new_spanned(
+                ty,
+                "entry point must return `()` or `!`",
+            ));
+        }
+    }
+
+    let name = &sig.ident;
+    let call = match sig.inputs.len() {
+        0 => quote!(#name()),
+        1 => quote! {
+            #name(match #context::from_reserved(reserved) {
+                ::rshooks_api::Ok(ctx) => ctx,
+                ::rshooks_api::Err(e) => {
+                    ::rshooks_api::rollback(b"invalid entry point argument", e.code() as _)
+                }
+            })
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                &sig.inputs,
+                "entry point takes at most one context argument",
+            ))
+        }
+    };
+
+    // `C-unwind` lets the native testing host unwind out of `accept` and `rollback`,
+    // in wasm it is the same as `C`
+    Ok(quote! {
+        #[no_mangle]
+        #[allow(unreachable_code, clippy::diverging_sub_expression)]
+        pub extern "C-unwind" fn #symbol(reserved: u32) -> i64 {
+            #item
+
+            ::rshooks_api::_g(1, 1);
+
+            let _ = reserved;
+            #call;
+
+            ::rshooks_api::accept(b"", 0)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    fn expand_hook(attr: TokenStream2, item: ItemFn) -> syn::Result<TokenStream2> {
+        expand(
+            attr,
+            item,
+            quote!(hook),
+            quote!(::rshooks_api::ExecutionContext),
+        )
+    }
+
+    fn expand_error(item: ItemFn) -> String {
+        match expand_hook(TokenStream2::new(), item) {
+            Ok(tokens) => panic!("expanded to {tokens}"),
+            Err(e) => e.to_string(),
+        }
+    }
+
+    #[test]
+    fn hook_test() {
+        let item: ItemFn = parse_quote! {
+            fn main() {}
+        };
+        let expanded = match expand_hook(TokenStream2::new(), item) {
+            Ok(tokens) => tokens,
+            Err(e) => panic!("{e}"),
+        };
+
+        let expected = quote! {
+            #[no_mangle]
+            #[allow(unreachable_code, clippy::diverging_sub_expression)]
+            pub extern "C-unwind" fn hook(reserved: u32) -> i64 {
+                fn main() {}
+
+                ::rshooks_api::_g(1, 1);
+
+                let _ = reserved;
+                main();
+
+                ::rshooks_api::accept(b"", 0)
+            }
+        };
+        assert_eq!(expanded.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn cbak_context_test() {
+        let item: ItemFn = parse_quote! {
+            fn callback(ctx: CallbackContext) -> ! {
+                rollback(b"", 0)
+            }
+        };
+        let expanded = match expand(
+            TokenStream2::new(),
+            item,
+            quote!(cbak),
+            quote!(::rshooks_api::CallbackContext),
+        ) {
+            Ok(tokens) => tokens.to_string(),
+            Err(e) => panic!("{e}"),
+        };
+
+        let signature = quote!(pub extern "C-unwind" fn cbak(reserved: u32) -> i64);
+        let context = quote!(callback(
+            match ::rshooks_api::CallbackContext::from_reserved(reserved) {
+                ::rshooks_api::Ok(ctx) => ctx,
+                ::rshooks_api::Err(e) => {
+                    ::rshooks_api::rollback(b"invalid entry point argument", e.code() as _)
+                }
+            }
+        ));
+        assert!(expanded.contains(&signature.to_string()), "{expanded}");
+        assert!(expanded.contains(&context.to_string()), "{expanded}");
+        assert!(expanded.contains(&quote!(::rshooks_api::_g(1, 1);).to_string()));
+        let accept = quote!(::rshooks_api::accept(b"", 0));
+        assert!(expanded.ends_with(&format!("{accept} }}")), "{expanded}");
+    }
+
+    #[test]
+    fn rejected_signature_test() {
+        assert_eq!(
+            expand_error(parse_quote!(
+                async fn main() {}
+            )),
+            "entry point must be a plain `fn`"
+        );
+        assert_eq!(
+            expand_error(parse_quote!(
+                unsafe fn main() {}
+            )),
+            "entry point must be a plain `fn`"
+        );
+        assert_eq!(
+            expand_error(parse_quote!(
+                fn main<T>() {}
+            )),
+            "entry point cannot be generic"
+        );
+        assert_eq!(
+            expand_error(parse_quote!(
+                fn main() -> i64 {
+                    0
+                }
+            )),
+            "entry point must return `()` or `!`"
+        );
+        assert_eq!(
+            expand_error(parse_quote!(
+                fn main(a: ExecutionContext, b: u32) {}
+            )),
+            "entry point takes at most one context argument"
+        );
+
+        let item: ItemFn = parse_quote!(
+            fn main() {}
+        );
+        assert!(matches!(
+            expand_hook(quote!(weak), item),
+            Err(e) if e.to_string() == "entry point attribute takes no arguments"
+        ));
+    }
+}
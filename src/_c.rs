@@ -1,4 +1,40 @@
 #![allow(non_upper_case_globals)]
 #![allow(missing_docs)]
 
+#[cfg(not(any(test, feature = "testing")))]
 include!("../c_bindings/bindings.rs");
+
+/// Pointer into the hook memory as passed to the host functions
+#[cfg(not(any(test, feature = "testing")))]
+pub type Ptr = u32;
+
+#[cfg(any(test, feature = "testing"))]
+mod bindings {
+    #![allow(dead_code, non_upper_case_globals, unreachable_pub)]
+
+    include!("../c_bindings/bindings.rs");
+}
+
+#[cfg(any(test, feature = "testing"))]
+pub use self::bindings::*;
+
+/// Pointer into the hook memory as passed to the host functions
+///
+/// Native pointers do not fit in 32 bits, so the mock host takes them whole.
+#[cfg(any(test, feature = "testing"))]
+pub type Ptr = usize;
+
+#[cfg(any(test, feature = "testing"))]
+pub use crate::testing::host::{
+    _g, accept, emit, etxn_burden, etxn_details, etxn_fee_base, etxn_generation, etxn_nonce,
+    etxn_reserve, fee_base, float_compare, float_divide, float_int, float_invert, float_log,
+    float_mantissa, float_mulratio, float_multiply, float_negate, float_one, float_root, float_set,
+    float_sign, float_sto, float_sto_set, float_sum, hook_account, hook_again, hook_hash,
+    hook_param, hook_param_set, hook_pos, hook_skip, ledger_keylet, ledger_last_hash,
+    ledger_last_time, ledger_nonce, ledger_seq, meta_slot, otxn_burden, otxn_field,
+    otxn_generation, otxn_id, otxn_param, otxn_slot, otxn_type, rollback, slot, slot_clear,
+    slot_count, slot_float, slot_set, slot_size, slot_subarray, slot_subfield, slot_type, state,
+    state_foreign, state_foreign_set, state_set, sto_emplace, sto_erase, sto_subarray,
+    sto_subfield, sto_validate, trace, trace_float, trace_num, util_accid, util_keylet, util_raddr,
+    util_sha512h, util_verify, xpop_slot,
+};
@@ -0,0 +1,522 @@
+use crate::api::{AccountId, Err, Error, Ok, Result};
+use crate::sha256;
+
+/// XRPL base58 alphabet
+pub const ALPHABET: &[u8; 58] = b"rpshnaf39wBUDNEGHJKLM4PQRST7VWXYZ2bcdeCg65jkm8oFqi1tuvAxyz";
+
+/// Shortest r-address
+pub const RADDR_MIN_LEN: usize = 25;
+
+/// Longest r-address
+pub const RADDR_MAX_LEN: usize = 35;
+
+/// Length of an X-address
+pub const XADDR_LEN: usize = 47;
+
+/// Most conversions all encoders, decoders and validations perform during a hook execution
+///
+/// Every loop of this module is guarded for this many conversions of the longest kind.
+pub const MAX_CONVERSIONS: usize = 16;
+
+/// Version byte of account r-addresses
+const ACCOUNT_VERSION: u8 = 0;
+
+/// Prefix of main network X-addresses, which start with `X`
+const XADDR_MAIN_PREFIX: [u8; 2] = [0x05, 0x44];
+
+/// Prefix of test network X-addresses, which start with `T`
+const XADDR_TEST_PREFIX: [u8; 2] = [0x04, 0x93];
+
+/// Length of an r-address payload: version byte and account ID
+const RADDR_PAYLOAD_LEN: usize = 21;
+
+/// Length of an X-address payload: prefix, account ID, tag flag, tag and reserved bytes
+const XADDR_PAYLOAD_LEN: usize = 31;
+
+const CHECKSUM_LEN: usize = 4;
+
+/// Most base58 digits of an encoded payload and its checksum
+const MAX_DIGITS: usize = 48;
+
+/// Digit of each ASCII character, `NO_DIGIT` if it isn't in the alphabet
+const DIGITS: [u8; 128] = digits();
+const NO_DIGIT: u8 = 0xFF;
+
+const fn digits() -> [u8; 128] {
+    let mut digits = [NO_DIGIT; 128];
+    let mut digit = 0;
+    while digit < ALPHABET.len() {
+        digits[ALPHABET[digit] as usize] = digit as u8;
+        digit += 1;
+    }
+    digits
+}
+
+/// Encodes an account ID as an r-address
+///
+/// Returns the length of the r-address written at the start of `raddr`,
+/// a buffer of [RADDR_MAX_LEN] bytes always fits.
+///
+/// Works like [util_raddr](crate::util_raddr) without a host call,
+/// so it also runs natively.
+///
+/// # Errors
+///
+/// * [Error::TooSmall] if the r-address doesn't fit in the buffer
+///
+/// # Example
+///
+/// ``` txt
+/// let mut raddr = [0u8; address::RADDR_MAX_LEN];
+/// let len = match address::encode(&account, &mut raddr) {
+///     Ok(len) => len,
+///     Err(e) => rollback(b"encode failed", e.code() as _),
+/// };
+/// trace(b"account", &raddr[..len], DataRepr::AsUTF8);
+/// ```
+pub fn encode(account: &AccountId, raddr: &mut [u8]) -> Result<usize> {
+    let mut payload = [ACCOUNT_VERSION; RADDR_PAYLOAD_LEN];
+    payload[1..].copy_from_slice(account);
+
+    encode_check(&payload, raddr)
+}
+
+/// Decodes the account ID of an r-address
+///
+/// Works like [util_accid](crate::util_accid) without a host call,
+/// so it also runs natively.
+///
+/// # Errors
+///
+/// * [Error::InvalidArgument] if the r-address is malformed, isn't an account
+///   r-address or its checksum is wrong
+pub fn decode(raddr: &[u8]) -> Result<AccountId> {
+    if raddr.len() < RADDR_MIN_LEN || raddr.len() > RADDR_MAX_LEN {
+        return Err(Error::InvalidArgument);
+    }
+
+    let mut payload = [0u8; RADDR_PAYLOAD_LEN + CHECKSUM_LEN];
+    if !decode_check(raddr, &mut payload) || payload[0] != ACCOUNT_VERSION {
+        return Err(Error::InvalidArgument);
+    }
+
+    let mut account: AccountId = [0; 20];
+    account.copy_from_slice(&payload[1..RADDR_PAYLOAD_LEN]);
+    Ok(account)
+}
+
+/// Tests if an r-address is well-formed and its checksum is right
+///
+/// The cheap way to check a stored r-address: no host call and no output buffer.
+pub fn is_valid(raddr: &[u8]) -> bool {
+    matches!(decode(raddr), Ok(_))
+}
+
+/// Account and destination tag of an X-address
+///
+/// X-addresses pack an account ID, an optional destination tag and the network kind
+/// into a single base58check string of [XADDR_LEN] characters.
+/// Main network X-addresses start with `X`, test network ones with `T`.
+///
+/// # Example
+///
+/// ``` txt
+/// let xaddr = XAddress {
+///     account,
+///     tag: Some(14),
+///     is_test: false,
+/// };
+/// let mut buf = [0u8; address::XADDR_LEN];
+/// let len = match xaddr.encode(&mut buf) {
+///     Ok(len) => len,
+///     Err(e) => rollback(b"encode failed", e.code() as _),
+/// };
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct XAddress {
+    /// Account ID
+    pub account: AccountId,
+    /// Destination tag
+    pub tag: Option<u32>,
+    /// Is a test network address
+    pub is_test: bool,
+}
+
+impl XAddress {
+    /// Encodes the X-address
+    ///
+    /// Returns the length of the X-address written at the start of `xaddr`,
+    /// always [XADDR_LEN].
+    ///
+    /// # Errors
+    ///
+    /// * [Error::TooSmall] if the X-address doesn't fit in the buffer
+    pub fn encode(&self, xaddr: &mut [u8]) -> Result<usize> {
+        let mut payload = [0u8; XADDR_PAYLOAD_LEN];
+        payload[..2].copy_from_slice(if self.is_test {
+            &XADDR_TEST_PREFIX
+        } else {
+            &XADDR_MAIN_PREFIX
+        });
+        payload[2..22].copy_from_slice(&self.account);
+        if let Some(tag) = self.tag {
+            payload[22] = 1;
+            payload[23..27].copy_from_slice(&tag.to_le_bytes());
+        }
+
+        encode_check(&payload, xaddr)
+    }
+
+    /// Decodes an X-address
+    ///
+    /// # Errors
+    ///
+    /// * [Error::InvalidArgument] if the X-address is malformed, its checksum is wrong
+    ///   or it holds a 64-bit tag
+    pub fn decode(xaddr: &[u8]) -> Result<XAddress> {
+        if xaddr.len() != XADDR_LEN {
+            return Err(Error::InvalidArgument);
+        }
+
+        let mut payload = [0u8; XADDR_PAYLOAD_LEN + CHECKSUM_LEN];
+        if !decode_check(xaddr, &mut payload) {
+            return Err(Error::InvalidArgument);
+        }
+
+        let is_test = match [payload[0], payload[1]] {
+            XADDR_MAIN_PREFIX => false,
+            XADDR_TEST_PREFIX => true,
+            _ => return Err(Error::InvalidArgument),
+        };
+        let tag = u32::from_le_bytes([payload[23], payload[24], payload[25], payload[26]]);
+        let tag = match payload[22] {
+            0 if tag == 0 => None,
+            1 => Some(tag),
+            _ => return Err(Error::InvalidArgument),
+        };
+        // The upper half of 64-bit tags, which are reserved
+        if payload[27..XADDR_PAYLOAD_LEN] != [0; 4] {
+            return Err(Error::InvalidArgument);
+        }
+
+        let mut account: AccountId = [0; 20];
+        account.copy_from_slice(&payload[2..22]);
+        Ok(XAddress {
+            account,
+            tag,
+            is_test,
+        })
+    }
+}
+
+/// Encodes a payload followed by its checksum in base58
+fn encode_check(payload: &[u8], out: &mut [u8]) -> Result<usize> {
+    let checksum = checksum(payload);
+    let len = payload.len() + CHECKSUM_LEN;
+
+    // Base58 digits, least significant first
+    let mut digits = [0u8; MAX_DIGITS];
+    let mut digits_len = 0;
+    let mut i = 0;
+    crate::guarded_while!(
+        MAX_CONVERSIONS * (XADDR_PAYLOAD_LEN + CHECKSUM_LEN + 1),
+        i < len,
+        {
+            let mut carry = if i < payload.len() {
+                payload[i]
+            } else {
+                checksum[i - payload.len()]
+            } as u32;
+            let mut j = 0;
+            crate::guarded_while!(
+                MAX_CONVERSIONS * (XADDR_PAYLOAD_LEN + CHECKSUM_LEN) * (MAX_DIGITS + 1),
+                j < digits_len,
+                {
+                    carry += (digits[j] as u32) << 8;
+                    digits[j] = (carry % 58) as u8;
+                    carry /= 58;
+                    j += 1;
+                }
+            );
+            crate::guarded_while!(
+                MAX_CONVERSIONS * (XADDR_PAYLOAD_LEN + CHECKSUM_LEN + MAX_DIGITS),
+                carry > 0,
+                {
+                    digits[digits_len] = (carry % 58) as u8;
+                    digits_len += 1;
+                    carry /= 58;
+                }
+            );
+            i += 1;
+        }
+    );
+
+    // Each leading zero byte is encoded as a leading 'r'
+    let mut zeros = 0;
+    crate::guarded_while!(
+        MAX_CONVERSIONS * (XADDR_PAYLOAD_LEN + 1),
+        zeros < payload.len() && payload[zeros] == 0,
+        {
+            zeros += 1;
+        }
+    );
+
+    let encoded_len = zeros + digits_len;
+    if out.len() < encoded_len {
+        return Err(Error::TooSmall);
+    }
+    crate::guarded_for!(k in 0..encoded_len, MAX_CONVERSIONS * (MAX_DIGITS + 1), {
+        out[k] = if k < zeros {
+            ALPHABET[0]
+        } else {
+            ALPHABET[digits[encoded_len - 1 - k] as usize]
+        };
+    });
+
+    Ok(encoded_len)
+}
+
+/// Decodes base58 `chars` into `bytes` and verifies the trailing checksum
+///
+/// Returns `false` if a character isn't in the alphabet, the number doesn't fit in `bytes`,
+/// the leading zeros aren't encoded canonically or the checksum is wrong.
+fn decode_check(chars: &[u8], bytes: &mut [u8]) -> bool {
+    let mut valid = true;
+    let mut i = 0;
+    crate::guarded_while!(
+        MAX_CONVERSIONS * (XADDR_LEN + 1),
+        valid && i < chars.len(),
+        {
+            let digit = match DIGITS.get(chars[i] as usize) {
+                Some(digit) => *digit,
+                None => NO_DIGIT,
+            };
+            let mut carry = digit as u32;
+            let mut j = bytes.len();
+            crate::guarded_while!(
+                MAX_CONVERSIONS * XADDR_LEN * (XADDR_PAYLOAD_LEN + CHECKSUM_LEN + 1),
+                j > 0,
+                {
+                    j -= 1;
+                    carry += 58 * bytes[j] as u32;
+                    bytes[j] = carry as u8;
+                    carry >>= 8;
+                }
+            );
+            valid = digit != NO_DIGIT && carry == 0;
+            i += 1;
+        }
+    );
+    if !valid {
+        return false;
+    }
+
+    // Each leading zero byte is encoded as a leading 'r'
+    let mut zero_chars = 0;
+    crate::guarded_while!(
+        MAX_CONVERSIONS * (RADDR_MAX_LEN + 1),
+        zero_chars < chars.len() && chars[zero_chars] == ALPHABET[0],
+        {
+            zero_chars += 1;
+        }
+    );
+    let mut zero_bytes = 0;
+    crate::guarded_while!(
+        MAX_CONVERSIONS * (XADDR_PAYLOAD_LEN + CHECKSUM_LEN + 1),
+        zero_bytes < bytes.len() && bytes[zero_bytes] == 0,
+        {
+            zero_bytes += 1;
+        }
+    );
+    if zero_chars != zero_bytes {
+        return false;
+    }
+
+    let (payload, expected) = bytes.split_at(bytes.len() - CHECKSUM_LEN);
+    checksum(payload) == expected
+}
+
+/// First bytes of the double SHA-256 of a payload
+fn checksum(payload: &[u8]) -> [u8; CHECKSUM_LEN] {
+    let hash = hash(&hash(payload));
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// SHA-256 of at most 55 bytes, which fit in a single block
+fn hash(data: &[u8]) -> [u8; 32] {
+    let mut w = [0u32; 64];
+    let mut state = sha256::H;
+    // Two hashes per conversion, each of 64 rounds
+    crate::guarded_for!(i in 0..64, MAX_CONVERSIONS * 2 * 65, {
+        w[i] = sha256::schedule(data, 64, 0, &w, i);
+        state = sha256::round(state, i, w[i]);
+    });
+
+    sha256::digest(sha256::add(sha256::H, state))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode, encode, is_valid, XAddress, MAX_CONVERSIONS, RADDR_MAX_LEN, XADDR_LEN};
+    use crate::testing::MockLedger;
+    use crate::{hex, raddr, util_accid, util_raddr, AccountId, Err, Error, Ok};
+
+    fn accounts() -> [AccountId; 6] {
+        let mut counting = [0u8; 20];
+        for (i, byte) in counting.iter_mut().enumerate() {
+            *byte = i as u8 * 13;
+        }
+        let mut leading_zeros = [0xFFu8; 20];
+        leading_zeros[..3].fill(0);
+
+        [
+            [0; 20],
+            [0xFF; 20],
+            counting,
+            leading_zeros,
+            hex!("B5F762798A53D543A014CAF8B297CFF8F2F937E8"),
+            hex!("AA066C988C712815CC37AF71472B7CBBBD4E2A0A"),
+        ]
+    }
+
+    #[test]
+    fn host_cross_check_test() {
+        let mut ledger = MockLedger::new();
+
+        let outcome = ledger.run(|| {
+            for account in accounts() {
+                let mut expected = [0u8; RADDR_MAX_LEN];
+                let expected_len = match util_raddr(&mut expected, &account) {
+                    Ok(len) => len as usize,
+                    Err(_) => panic!("util_raddr failed"),
+                };
+                let mut raddr = [0u8; RADDR_MAX_LEN];
+                let len = match encode(&account, &mut raddr) {
+                    Ok(len) => len,
+                    Err(_) => panic!("encode failed"),
+                };
+                assert_eq!(raddr[..len], expected[..expected_len]);
+
+                let mut host_account = [0u8; 20];
+                assert!(matches!(
+                    util_accid(&mut host_account, &raddr[..len]),
+                    Ok(20)
+                ));
+                assert!(matches!(decode(&raddr[..len]), Ok(decoded) if decoded == host_account));
+            }
+            crate::accept(b"", 0)
+        });
+        assert!(outcome.is_accept(), "{}", ledger.guard_report());
+    }
+
+    #[test]
+    fn guard_budget_test() {
+        let mut ledger = MockLedger::new();
+
+        let xaddr = XAddress {
+            account: [0xFF; 20],
+            tag: Some(u32::MAX),
+            is_test: true,
+        };
+        let mut buf = [0u8; XADDR_LEN];
+
+        // The longest conversions, as many as the budget allows
+        let outcome = ledger.run(|| {
+            for _ in 0..MAX_CONVERSIONS {
+                assert!(matches!(xaddr.encode(&mut buf), Ok(XADDR_LEN)));
+            }
+            crate::accept(b"", 0)
+        });
+        assert!(outcome.is_accept(), "{}", ledger.guard_report());
+
+        let outcome = ledger.run(|| {
+            for _ in 0..MAX_CONVERSIONS {
+                assert!(matches!(XAddress::decode(&buf), Ok(decoded) if decoded == xaddr));
+            }
+            crate::accept(b"", 0)
+        });
+        assert!(outcome.is_accept(), "{}", ledger.guard_report());
+    }
+
+    #[test]
+    fn raddr_test() {
+        let genesis = b"rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh";
+        assert!(
+            matches!(decode(genesis), Ok(account) if account == raddr!("rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh"))
+        );
+        assert!(is_valid(genesis));
+        assert!(is_valid(b"rrrrrrrrrrrrrrrrrrrrrhoLvTp"));
+
+        // Wrong checksum, leading zeros, character outside the alphabet, too short
+        assert!(!is_valid(b"rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTt"));
+        assert!(!is_valid(b"rrHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh"));
+        assert!(!is_valid(b"rHb9CJAWyB4rj91VRWn96DkukG4bwdty0h"));
+        assert!(!is_valid(b"rHb9CJAWyB4rj91VRWn96"));
+        // A valid X-address isn't an r-address
+        assert!(!is_valid(
+            b"XVLhHMPHU98es4dbozjVtdWzVrDjtV5fdx1mHp98tDMoQXb"
+        ));
+
+        let mut small = [0u8; 24];
+        assert!(matches!(encode(&[0; 20], &mut small), Err(Error::TooSmall)));
+    }
+
+    #[test]
+    fn xaddr_test() {
+        let account = raddr!("rGWrZyQqhTp9Xu7G5Pkayo7bXjH4k4QYpf");
+        let vectors: [(Option<u32>, bool, &[u8]); 6] = [
+            (
+                None,
+                false,
+                b"XVLhHMPHU98es4dbozjVtdWzVrDjtV5fdx1mHp98tDMoQXb",
+            ),
+            (
+                None,
+                true,
+                b"TVE26TYGhfLC7tQDno7G8dGtxSkYQn49b3qD26PK7FcGSKE",
+            ),
+            (
+                Some(1),
+                false,
+                b"XVLhHMPHU98es4dbozjVtdWzVrDjtV8xvjGQTYPiAx6gwDC",
+            ),
+            (
+                Some(14),
+                false,
+                b"XVLhHMPHU98es4dbozjVtdWzVrDjtVoD9z4jAcBVsnb97sM",
+            ),
+            (
+                Some(14),
+                true,
+                b"TVE26TYGhfLC7tQDno7G8dGtxSkYQnTEfwLUEHRrdC8Pfa8",
+            ),
+            (
+                Some(u32::MAX),
+                false,
+                b"XVLhHMPHU98es4dbozjVtdWzVrDjtV18pX8yuPT7y4xaEHi",
+            ),
+        ];
+
+        for (tag, is_test, expected) in vectors {
+            let xaddr = XAddress {
+                account,
+                tag,
+                is_test,
+            };
+            let mut buf = [0u8; XADDR_LEN];
+            assert!(matches!(xaddr.encode(&mut buf), Ok(XADDR_LEN)));
+            assert_eq!(&buf[..], expected);
+            assert!(matches!(XAddress::decode(expected), Ok(decoded) if decoded == xaddr));
+        }
+
+        // Wrong checksum, an r-address
+        assert!(matches!(
+            XAddress::decode(b"XVLhHMPHU98es4dbozjVtdWzVrDjtV5fdx1mHp98tDMoQXc"),
+            Err(Error::InvalidArgument)
+        ));
+        assert!(matches!(
+            XAddress::decode(b"rGWrZyQqhTp9Xu7G5Pkayo7bXjH4k4QYpf"),
+            Err(Error::InvalidArgument)
+        ));
+    }
+}
@@ -0,0 +1,295 @@
+use super::*;
+use crate::sto::{Reader, Writer};
+
+/// Amount of XRP or of an issued currency
+///
+/// Parses from the serialized STAmount of [otxn_field], [slot] or a [Reader](crate::sto::Reader)
+/// field and serializes back to the bytes [float_sto] outputs, without host calls.
+///
+/// # Example
+///
+/// ``` txt
+/// match otxn_amount(FieldId::Amount) {
+///     Ok(Amount::Xrp(drops)) => trace_num(b"drops", drops as _),
+///     Ok(Amount::Iou { value, currency, .. }) if currency == USD => trace_float(b"usd", value),
+///     Ok(_) => rollback(b"unexpected currency", 1),
+///     Err(e) => rollback(b"no amount", e.code() as _),
+/// };
+/// ```
+#[derive(Clone, Copy)]
+pub enum Amount {
+    /// XRP amount in drops
+    Xrp(u64),
+    /// Issued currency amount
+    Iou {
+        /// Value
+        value: XFL,
+        /// Currency
+        currency: Currency,
+        /// Issuer account
+        issuer: AccountId,
+    },
+}
+
+/// Bit set in serialized issued currency amounts
+const IOU_BIT: u64 = 1 << 63;
+
+impl Amount {
+    /// Parse a serialized STAmount, with or without its field header
+    ///
+    /// # Errors
+    ///
+    /// * [Error::ParseError] if the amount is malformed
+    /// * [Error::NotAnAmount] if the field header isn't the header of an Amount field
+    /// * [Error::CantReturnNegative] if a native amount is negative, including zero
+    ///   without the sign bit
+    /// * [Error::InvalidArgument] if a native amount exceeds [Drops::MAX]
+    pub fn from_sto(sto: &[u8]) -> Result<Amount> {
+        match sto.len() {
+            NATIVE_AMOUNT_LEN | IOU_AMOUNT_LEN => (),
+            _ => {
+                let mut reader = Reader::new(sto);
+                return match (reader.next(), reader.next()) {
+                    (Some(Ok(field)), None) => field.amount(),
+                    (Some(Err(e)), _) => Err(e),
+                    _ => Err(Error::ParseError),
+                };
+            }
+        }
+
+        let raw = u64::from_be_bytes(*crate::helpers::slice(sto, 0));
+        if raw & IOU_BIT == 0 {
+            if sto.len() != NATIVE_AMOUNT_LEN {
+                return Err(Error::ParseError);
+            }
+            return match Drops::from_sto(crate::helpers::slice(sto, 0)) {
+                Ok(drops) => Ok(Amount::Xrp(drops.get())),
+                Err(e) => Err(e),
+            };
+        }
+
+        if sto.len() != IOU_AMOUNT_LEN {
+            return Err(Error::ParseError);
+        }
+        // XFL shares the layout of an IOU amount
+        Ok(Amount::Iou {
+            value: XFL::from_raw((raw & !IOU_BIT) as i64),
+            currency: Currency::from_code(*crate::helpers::slice(sto, 8)),
+            issuer: *crate::helpers::slice(sto, 28),
+        })
+    }
+
+    /// Serialize the amount as the field `field`, header included
+    ///
+    /// Writes the same bytes as [float_sto] in pure Rust and returns their length.
+    ///
+    /// # Errors
+    ///
+    /// * [Error::TooSmall] if the buffer is too small
+    /// * [Error::InvalidField] if `field` isn't an Amount field
+    /// * [Error::InvalidArgument] if a native amount is out of range
+    /// * [Error::InvalidFloat] if the value of an issued amount is invalid
+    pub fn to_sto(&self, field: FieldId, sto: &mut [u8]) -> Result<usize> {
+        let mut writer = Writer::new(sto);
+        writer.amount(field, self);
+        match writer.finish() {
+            Ok(sto) => Ok(sto.len()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Returns `true` if the amount is an XRP amount
+    #[inline(always)]
+    pub const fn is_xrp(&self) -> bool {
+        matches!(self, Amount::Xrp(_))
+    }
+}
+
+/// Read an Amount field of the originating transaction
+///
+/// Returns [Error::DoesntExist] if the transaction doesn't hold the field.
+#[inline(always)]
+pub fn otxn_amount(field_id: FieldId) -> Result<Amount> {
+    let mut buf = [0u8; IOU_AMOUNT_LEN];
+    match otxn_field(&mut buf, field_id) {
+        Ok(len) => Amount::from_sto(&buf[..len as usize]),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex;
+    use crate::testing::{MockLedger, Payment, TxnScenario};
+
+    const ISSUER: AccountId = [0xB0; 20];
+
+    #[test]
+    fn from_sto_test() {
+        assert!(matches!(
+            Amount::from_sto(&hex!("40000000000003E8")),
+            Ok(Amount::Xrp(1_000))
+        ));
+        assert!(matches!(
+            Amount::from_sto(&hex!("6140000000000003E8")),
+            Ok(Amount::Xrp(1_000))
+        ));
+        assert!(matches!(
+            Amount::from_sto(&hex!("4000000000000000")),
+            Ok(Amount::Xrp(0))
+        ));
+        assert!(matches!(
+            Amount::from_sto(&hex!("0000000000000000")),
+            Err(Error::CantReturnNegative)
+        ));
+        assert!(matches!(
+            Amount::from_sto(&hex!("00000000000003E8")),
+            Err(Error::CantReturnNegative)
+        ));
+
+        let mut iou = [0u8; IOU_AMOUNT_LEN];
+        iou[..8].copy_from_slice(&hex!("D4838D7EA4C68000"));
+        iou[8..28].copy_from_slice(&hex!("0000000000000000000000005553440000000000"));
+        iou[28..].copy_from_slice(&ISSUER);
+        match Amount::from_sto(&iou) {
+            Ok(Amount::Iou {
+                value,
+                currency,
+                issuer,
+            }) => {
+                assert_eq!(value.to_raw(), 0x54838D7EA4C68000);
+                assert!(currency.iso_code() == Some(*b"USD"));
+                assert_eq!(issuer, ISSUER);
+            }
+            _ => panic!("not an IOU amount"),
+        }
+
+        // Wrong lengths, not an Amount field
+        assert!(matches!(
+            Amount::from_sto(&iou[..8]),
+            Err(Error::ParseError)
+        ));
+        assert!(matches!(
+            Amount::from_sto(&hex!("40000000000003E8000000")),
+            Err(Error::ParseError)
+        ));
+        assert!(matches!(
+            Amount::from_sto(&hex!("2400000001")),
+            Err(Error::NotAnAmount)
+        ));
+    }
+
+    #[test]
+    fn float_sto_cross_check_test() {
+        let mut ledger = MockLedger::new();
+
+        let outcome = ledger.run(|| {
+            let usd = match Currency::iso(b"USD") {
+                Ok(usd) => usd,
+                Err(_) => panic!("invalid currency"),
+            };
+            let values = [
+                XFL::from_raw(0),
+                XFL::from_raw(0x54838D7EA4C68000),
+                XFL::from_raw(0x14838D7EA4C68000),
+            ];
+            for value in values {
+                let amount = Amount::Iou {
+                    value,
+                    currency: usd,
+                    issuer: ISSUER,
+                };
+                let mut expected = [0u8; 64];
+                let expected_len = match float_sto(
+                    &mut expected,
+                    usd.as_code(),
+                    &ISSUER,
+                    value,
+                    FieldId::LimitAmount,
+                ) {
+                    Ok(len) => len as usize,
+                    Err(_) => panic!("float_sto failed"),
+                };
+                let mut sto = [0u8; 64];
+                let len = match amount.to_sto(FieldId::LimitAmount, &mut sto) {
+                    Ok(len) => len,
+                    Err(_) => panic!("to_sto failed"),
+                };
+                assert_eq!(sto[..len], expected[..expected_len]);
+                assert!(matches!(
+                    Amount::from_sto(&sto[..len]),
+                    Ok(Amount::Iou { value: parsed, .. }) if parsed.to_raw() == value.to_raw()
+                ));
+            }
+
+            // float_sto serializes XRP as sfAmount when the field code is 0
+            let drops = match float_set(3, 1) {
+                Ok(drops) => drops,
+                Err(_) => panic!("invalid drops"),
+            };
+            let mut expected = [0u8; 9];
+            let res = unsafe {
+                _c::float_sto(
+                    expected.as_mut_ptr() as _,
+                    expected.len() as _,
+                    0,
+                    0,
+                    0,
+                    0,
+                    drops.to_raw(),
+                    0,
+                )
+            };
+            assert_eq!(res, 9);
+            let mut sto = [0u8; 9];
+            assert!(matches!(
+                Amount::Xrp(1_000).to_sto(FieldId::Amount, &mut sto),
+                Ok(9)
+            ));
+            assert_eq!(sto, expected);
+
+            crate::accept(b"", 0)
+        });
+        assert!(outcome.is_accept());
+    }
+
+    #[test]
+    fn otxn_amount_test() {
+        let mut ledger = MockLedger::new();
+
+        let outcome = Payment::new().amount_drops(1_000).run(&mut ledger, || {
+            assert!(matches!(
+                otxn_amount(FieldId::Amount),
+                Ok(Amount::Xrp(1_000))
+            ));
+            assert!(matches!(
+                otxn_amount(FieldId::DeliverMin),
+                Err(Error::DoesntExist)
+            ));
+            crate::accept(b"", 0)
+        });
+        assert!(outcome.is_accept());
+
+        let value = XFL::from_raw(0x54838D7EA4C68000);
+        let usd = hex!("0000000000000000000000005553440000000000");
+        let outcome = Payment::new()
+            .amount(value, &usd, &ISSUER)
+            .run(&mut ledger, || {
+                match otxn_amount(FieldId::Amount) {
+                    Ok(Amount::Iou {
+                        value: parsed,
+                        currency,
+                        issuer,
+                    }) => {
+                        assert_eq!(parsed.to_raw(), value.to_raw());
+                        assert!(*currency.as_code() == usd);
+                        assert_eq!(issuer, ISSUER);
+                    }
+                    _ => panic!("not an IOU amount"),
+                }
+                crate::accept(b"", 0)
+            });
+        assert!(outcome.is_accept());
+    }
+}
@@ -0,0 +1,411 @@
+//! Host codes generated from `c_bindings/h` by `rshooks-api-codegen`
+//!
+//! Do not edit, run `cargo run -p rshooks-api-codegen` instead.
+
+use super::Unknown;
+use crate::_c;
+
+code_enum! {
+    /// Transaction type
+    #[allow(missing_docs)]
+    #[derive(Clone, Copy)]
+    pub enum TxnType: u32 {
+        Payment = _c::ttPAYMENT => "ttPAYMENT",
+        EscrowCreate = _c::ttESCROW_CREATE => "ttESCROW_CREATE",
+        EscrowFinish = _c::ttESCROW_FINISH => "ttESCROW_FINISH",
+        AccountSet = _c::ttACCOUNT_SET => "ttACCOUNT_SET",
+        EscrowCancel = _c::ttESCROW_CANCEL => "ttESCROW_CANCEL",
+        RegularKeySet = _c::ttREGULAR_KEY_SET => "ttREGULAR_KEY_SET",
+        OfferCreate = _c::ttOFFER_CREATE => "ttOFFER_CREATE",
+        OfferCancel = _c::ttOFFER_CANCEL => "ttOFFER_CANCEL",
+        TicketCreate = _c::ttTICKET_CREATE => "ttTICKET_CREATE",
+        SignerListSet = _c::ttSIGNER_LIST_SET => "ttSIGNER_LIST_SET",
+        PaychanCreate = _c::ttPAYCHAN_CREATE => "ttPAYCHAN_CREATE",
+        PaychanFund = _c::ttPAYCHAN_FUND => "ttPAYCHAN_FUND",
+        PaychanClaim = _c::ttPAYCHAN_CLAIM => "ttPAYCHAN_CLAIM",
+        CheckCreate = _c::ttCHECK_CREATE => "ttCHECK_CREATE",
+        CheckCash = _c::ttCHECK_CASH => "ttCHECK_CASH",
+        CheckCancel = _c::ttCHECK_CANCEL => "ttCHECK_CANCEL",
+        DepositPreauth = _c::ttDEPOSIT_PREAUTH => "ttDEPOSIT_PREAUTH",
+        TrustSet = _c::ttTRUST_SET => "ttTRUST_SET",
+        AccountDelete = _c::ttACCOUNT_DELETE => "ttACCOUNT_DELETE",
+        HookSet = _c::ttHOOK_SET => "ttHOOK_SET",
+        NFTokenMint = _c::ttNFTOKEN_MINT => "ttNFTOKEN_MINT",
+        NFTokenBurn = _c::ttNFTOKEN_BURN => "ttNFTOKEN_BURN",
+        NFTokenCreateOffer = _c::ttNFTOKEN_CREATE_OFFER => "ttNFTOKEN_CREATE_OFFER",
+        NFTokenCancelOffer = _c::ttNFTOKEN_CANCEL_OFFER => "ttNFTOKEN_CANCEL_OFFER",
+        NFTokenAcceptOffer = _c::ttNFTOKEN_ACCEPT_OFFER => "ttNFTOKEN_ACCEPT_OFFER",
+        URITokenMint = _c::ttURITOKEN_MINT => "ttURITOKEN_MINT",
+        URITokenBurn = _c::ttURITOKEN_BURN => "ttURITOKEN_BURN",
+        URITokenBuy = _c::ttURITOKEN_BUY => "ttURITOKEN_BUY",
+        URITokenCreateSellOffer = _c::ttURITOKEN_CREATE_SELL_OFFER => "ttURITOKEN_CREATE_SELL_OFFER",
+        URITokenCancelSellOffer = _c::ttURITOKEN_CANCEL_SELL_OFFER => "ttURITOKEN_CANCEL_SELL_OFFER",
+        Remit = _c::ttREMIT => "ttREMIT",
+        GenesisMint = _c::ttGENESIS_MINT => "ttGENESIS_MINT",
+        Import = _c::ttIMPORT => "ttIMPORT",
+        ClaimReward = _c::ttCLAIM_REWARD => "ttCLAIM_REWARD",
+        Invoke = _c::ttINVOKE => "ttINVOKE",
+        Amendment = _c::ttAMENDMENT => "ttAMENDMENT",
+        Fee = _c::ttFEE => "ttFEE",
+        UnlModify = _c::ttUNL_MODIFY => "ttUNL_MODIFY",
+        EmitFailure = _c::ttEMIT_FAILURE => "ttEMIT_FAILURE",
+        UnlReport = _c::ttUNL_REPORT => "ttUNL_REPORT",
+    }
+}
+
+code_enum! {
+    /// Field type
+    #[allow(missing_docs)]
+    #[derive(Clone, Copy)]
+    pub enum FieldId: u32 {
+        CloseResolution = _c::sfCloseResolution => "sfCloseResolution",
+        Method = _c::sfMethod => "sfMethod",
+        TransactionResult = _c::sfTransactionResult => "sfTransactionResult",
+        TickSize = _c::sfTickSize => "sfTickSize",
+        UNLModifyDisabling = _c::sfUNLModifyDisabling => "sfUNLModifyDisabling",
+        HookResult = _c::sfHookResult => "sfHookResult",
+        LedgerEntryType = _c::sfLedgerEntryType => "sfLedgerEntryType",
+        TransactionType = _c::sfTransactionType => "sfTransactionType",
+        SignerWeight = _c::sfSignerWeight => "sfSignerWeight",
+        TransferFee = _c::sfTransferFee => "sfTransferFee",
+        Version = _c::sfVersion => "sfVersion",
+        HookStateChangeCount = _c::sfHookStateChangeCount => "sfHookStateChangeCount",
+        HookEmitCount = _c::sfHookEmitCount => "sfHookEmitCount",
+        HookExecutionIndex = _c::sfHookExecutionIndex => "sfHookExecutionIndex",
+        HookApiVersion = _c::sfHookApiVersion => "sfHookApiVersion",
+        NetworkID = _c::sfNetworkID => "sfNetworkID",
+        Flags = _c::sfFlags => "sfFlags",
+        SourceTag = _c::sfSourceTag => "sfSourceTag",
+        Sequence = _c::sfSequence => "sfSequence",
+        PreviousTxnLgrSeq = _c::sfPreviousTxnLgrSeq => "sfPreviousTxnLgrSeq",
+        LedgerSequence = _c::sfLedgerSequence => "sfLedgerSequence",
+        CloseTime = _c::sfCloseTime => "sfCloseTime",
+        ParentCloseTime = _c::sfParentCloseTime => "sfParentCloseTime",
+        SigningTime = _c::sfSigningTime => "sfSigningTime",
+        Expiration = _c::sfExpiration => "sfExpiration",
+        TransferRate = _c::sfTransferRate => "sfTransferRate",
+        WalletSize = _c::sfWalletSize => "sfWalletSize",
+        OwnerCount = _c::sfOwnerCount => "sfOwnerCount",
+        DestinationTag = _c::sfDestinationTag => "sfDestinationTag",
+        HighQualityIn = _c::sfHighQualityIn => "sfHighQualityIn",
+        HighQualityOut = _c::sfHighQualityOut => "sfHighQualityOut",
+        LowQualityIn = _c::sfLowQualityIn => "sfLowQualityIn",
+        LowQualityOut = _c::sfLowQualityOut => "sfLowQualityOut",
+        QualityIn = _c::sfQualityIn => "sfQualityIn",
+        QualityOut = _c::sfQualityOut => "sfQualityOut",
+        StampEscrow = _c::sfStampEscrow => "sfStampEscrow",
+        BondAmount = _c::sfBondAmount => "sfBondAmount",
+        LoadFee = _c::sfLoadFee => "sfLoadFee",
+        OfferSequence = _c::sfOfferSequence => "sfOfferSequence",
+        FirstLedgerSequence = _c::sfFirstLedgerSequence => "sfFirstLedgerSequence",
+        LastLedgerSequence = _c::sfLastLedgerSequence => "sfLastLedgerSequence",
+        TransactionIndex = _c::sfTransactionIndex => "sfTransactionIndex",
+        OperationLimit = _c::sfOperationLimit => "sfOperationLimit",
+        ReferenceFeeUnits = _c::sfReferenceFeeUnits => "sfReferenceFeeUnits",
+        ReserveBase = _c::sfReserveBase => "sfReserveBase",
+        ReserveIncrement = _c::sfReserveIncrement => "sfReserveIncrement",
+        SetFlag = _c::sfSetFlag => "sfSetFlag",
+        ClearFlag = _c::sfClearFlag => "sfClearFlag",
+        SignerQuorum = _c::sfSignerQuorum => "sfSignerQuorum",
+        CancelAfter = _c::sfCancelAfter => "sfCancelAfter",
+        FinishAfter = _c::sfFinishAfter => "sfFinishAfter",
+        SignerListID = _c::sfSignerListID => "sfSignerListID",
+        SettleDelay = _c::sfSettleDelay => "sfSettleDelay",
+        TicketCount = _c::sfTicketCount => "sfTicketCount",
+        TicketSequence = _c::sfTicketSequence => "sfTicketSequence",
+        NFTokenTaxon = _c::sfNFTokenTaxon => "sfNFTokenTaxon",
+        MintedNFTokens = _c::sfMintedNFTokens => "sfMintedNFTokens",
+        BurnedNFTokens = _c::sfBurnedNFTokens => "sfBurnedNFTokens",
+        HookStateCount = _c::sfHookStateCount => "sfHookStateCount",
+        EmitGeneration = _c::sfEmitGeneration => "sfEmitGeneration",
+        LockCount = _c::sfLockCount => "sfLockCount",
+        FirstNFTokenSequence = _c::sfFirstNFTokenSequence => "sfFirstNFTokenSequence",
+        XahauActivationLgrSeq = _c::sfXahauActivationLgrSeq => "sfXahauActivationLgrSeq",
+        ImportSequence = _c::sfImportSequence => "sfImportSequence",
+        RewardTime = _c::sfRewardTime => "sfRewardTime",
+        RewardLgrFirst = _c::sfRewardLgrFirst => "sfRewardLgrFirst",
+        RewardLgrLast = _c::sfRewardLgrLast => "sfRewardLgrLast",
+        IndexNext = _c::sfIndexNext => "sfIndexNext",
+        IndexPrevious = _c::sfIndexPrevious => "sfIndexPrevious",
+        BookNode = _c::sfBookNode => "sfBookNode",
+        OwnerNode = _c::sfOwnerNode => "sfOwnerNode",
+        BaseFee = _c::sfBaseFee => "sfBaseFee",
+        ExchangeRate = _c::sfExchangeRate => "sfExchangeRate",
+        LowNode = _c::sfLowNode => "sfLowNode",
+        HighNode = _c::sfHighNode => "sfHighNode",
+        DestinationNode = _c::sfDestinationNode => "sfDestinationNode",
+        Cookie = _c::sfCookie => "sfCookie",
+        ServerVersion = _c::sfServerVersion => "sfServerVersion",
+        NFTokenOfferNode = _c::sfNFTokenOfferNode => "sfNFTokenOfferNode",
+        EmitBurden = _c::sfEmitBurden => "sfEmitBurden",
+        HookInstructionCount = _c::sfHookInstructionCount => "sfHookInstructionCount",
+        HookReturnCode = _c::sfHookReturnCode => "sfHookReturnCode",
+        ReferenceCount = _c::sfReferenceCount => "sfReferenceCount",
+        AccountIndex = _c::sfAccountIndex => "sfAccountIndex",
+        AccountCount = _c::sfAccountCount => "sfAccountCount",
+        RewardAccumulator = _c::sfRewardAccumulator => "sfRewardAccumulator",
+        EmailHash = _c::sfEmailHash => "sfEmailHash",
+        TakerPaysCurrency = _c::sfTakerPaysCurrency => "sfTakerPaysCurrency",
+        TakerPaysIssuer = _c::sfTakerPaysIssuer => "sfTakerPaysIssuer",
+        TakerGetsCurrency = _c::sfTakerGetsCurrency => "sfTakerGetsCurrency",
+        TakerGetsIssuer = _c::sfTakerGetsIssuer => "sfTakerGetsIssuer",
+        LedgerHash = _c::sfLedgerHash => "sfLedgerHash",
+        ParentHash = _c::sfParentHash => "sfParentHash",
+        TransactionHash = _c::sfTransactionHash => "sfTransactionHash",
+        AccountHash = _c::sfAccountHash => "sfAccountHash",
+        PreviousTxnID = _c::sfPreviousTxnID => "sfPreviousTxnID",
+        LedgerIndex = _c::sfLedgerIndex => "sfLedgerIndex",
+        WalletLocator = _c::sfWalletLocator => "sfWalletLocator",
+        RootIndex = _c::sfRootIndex => "sfRootIndex",
+        AccountTxnID = _c::sfAccountTxnID => "sfAccountTxnID",
+        NFTokenID = _c::sfNFTokenID => "sfNFTokenID",
+        EmitParentTxnID = _c::sfEmitParentTxnID => "sfEmitParentTxnID",
+        EmitNonce = _c::sfEmitNonce => "sfEmitNonce",
+        EmitHookHash = _c::sfEmitHookHash => "sfEmitHookHash",
+        BookDirectory = _c::sfBookDirectory => "sfBookDirectory",
+        InvoiceID = _c::sfInvoiceID => "sfInvoiceID",
+        Nickname = _c::sfNickname => "sfNickname",
+        Amendment = _c::sfAmendment => "sfAmendment",
+        HookOn = _c::sfHookOn => "sfHookOn",
+        Digest = _c::sfDigest => "sfDigest",
+        Channel = _c::sfChannel => "sfChannel",
+        ConsensusHash = _c::sfConsensusHash => "sfConsensusHash",
+        CheckID = _c::sfCheckID => "sfCheckID",
+        ValidatedHash = _c::sfValidatedHash => "sfValidatedHash",
+        PreviousPageMin = _c::sfPreviousPageMin => "sfPreviousPageMin",
+        NextPageMin = _c::sfNextPageMin => "sfNextPageMin",
+        NFTokenBuyOffer = _c::sfNFTokenBuyOffer => "sfNFTokenBuyOffer",
+        NFTokenSellOffer = _c::sfNFTokenSellOffer => "sfNFTokenSellOffer",
+        HookStateKey = _c::sfHookStateKey => "sfHookStateKey",
+        HookHash = _c::sfHookHash => "sfHookHash",
+        HookNamespace = _c::sfHookNamespace => "sfHookNamespace",
+        HookSetTxnID = _c::sfHookSetTxnID => "sfHookSetTxnID",
+        OfferID = _c::sfOfferID => "sfOfferID",
+        EscrowID = _c::sfEscrowID => "sfEscrowID",
+        URITokenID = _c::sfURITokenID => "sfURITokenID",
+        GovernanceFlags = _c::sfGovernanceFlags => "sfGovernanceFlags",
+        GovernanceMarks = _c::sfGovernanceMarks => "sfGovernanceMarks",
+        EmittedTxnID = _c::sfEmittedTxnID => "sfEmittedTxnID",
+        Amount = _c::sfAmount => "sfAmount",
+        Balance = _c::sfBalance => "sfBalance",
+        LimitAmount = _c::sfLimitAmount => "sfLimitAmount",
+        TakerPays = _c::sfTakerPays => "sfTakerPays",
+        TakerGets = _c::sfTakerGets => "sfTakerGets",
+        LowLimit = _c::sfLowLimit => "sfLowLimit",
+        HighLimit = _c::sfHighLimit => "sfHighLimit",
+        Fee = _c::sfFee => "sfFee",
+        SendMax = _c::sfSendMax => "sfSendMax",
+        DeliverMin = _c::sfDeliverMin => "sfDeliverMin",
+        MinimumOffer = _c::sfMinimumOffer => "sfMinimumOffer",
+        RippleEscrow = _c::sfRippleEscrow => "sfRippleEscrow",
+        DeliveredAmount = _c::sfDeliveredAmount => "sfDeliveredAmount",
+        NFTokenBrokerFee = _c::sfNFTokenBrokerFee => "sfNFTokenBrokerFee",
+        HookCallbackFee = _c::sfHookCallbackFee => "sfHookCallbackFee",
+        LockedBalance = _c::sfLockedBalance => "sfLockedBalance",
+        BaseFeeDrops = _c::sfBaseFeeDrops => "sfBaseFeeDrops",
+        ReserveBaseDrops = _c::sfReserveBaseDrops => "sfReserveBaseDrops",
+        ReserveIncrementDrops = _c::sfReserveIncrementDrops => "sfReserveIncrementDrops",
+        PublicKey = _c::sfPublicKey => "sfPublicKey",
+        MessageKey = _c::sfMessageKey => "sfMessageKey",
+        SigningPubKey = _c::sfSigningPubKey => "sfSigningPubKey",
+        TxnSignature = _c::sfTxnSignature => "sfTxnSignature",
+        URI = _c::sfURI => "sfURI",
+        Signature = _c::sfSignature => "sfSignature",
+        Domain = _c::sfDomain => "sfDomain",
+        FundCode = _c::sfFundCode => "sfFundCode",
+        RemoveCode = _c::sfRemoveCode => "sfRemoveCode",
+        ExpireCode = _c::sfExpireCode => "sfExpireCode",
+        CreateCode = _c::sfCreateCode => "sfCreateCode",
+        MemoType = _c::sfMemoType => "sfMemoType",
+        MemoData = _c::sfMemoData => "sfMemoData",
+        MemoFormat = _c::sfMemoFormat => "sfMemoFormat",
+        Fulfillment = _c::sfFulfillment => "sfFulfillment",
+        Condition = _c::sfCondition => "sfCondition",
+        MasterSignature = _c::sfMasterSignature => "sfMasterSignature",
+        UNLModifyValidator = _c::sfUNLModifyValidator => "sfUNLModifyValidator",
+        ValidatorToDisable = _c::sfValidatorToDisable => "sfValidatorToDisable",
+        ValidatorToReEnable = _c::sfValidatorToReEnable => "sfValidatorToReEnable",
+        HookStateData = _c::sfHookStateData => "sfHookStateData",
+        HookReturnString = _c::sfHookReturnString => "sfHookReturnString",
+        HookParameterName = _c::sfHookParameterName => "sfHookParameterName",
+        HookParameterValue = _c::sfHookParameterValue => "sfHookParameterValue",
+        Blob = _c::sfBlob => "sfBlob",
+        Account = _c::sfAccount => "sfAccount",
+        Owner = _c::sfOwner => "sfOwner",
+        Destination = _c::sfDestination => "sfDestination",
+        Issuer = _c::sfIssuer => "sfIssuer",
+        Authorize = _c::sfAuthorize => "sfAuthorize",
+        Unauthorize = _c::sfUnauthorize => "sfUnauthorize",
+        RegularKey = _c::sfRegularKey => "sfRegularKey",
+        NFTokenMinter = _c::sfNFTokenMinter => "sfNFTokenMinter",
+        EmitCallback = _c::sfEmitCallback => "sfEmitCallback",
+        HookAccount = _c::sfHookAccount => "sfHookAccount",
+        Inform = _c::sfInform => "sfInform",
+        Indexes = _c::sfIndexes => "sfIndexes",
+        Hashes = _c::sfHashes => "sfHashes",
+        Amendments = _c::sfAmendments => "sfAmendments",
+        NFTokenOffers = _c::sfNFTokenOffers => "sfNFTokenOffers",
+        HookNamespaces = _c::sfHookNamespaces => "sfHookNamespaces",
+        URITokenIDs = _c::sfURITokenIDs => "sfURITokenIDs",
+        Paths = _c::sfPaths => "sfPaths",
+        TransactionMetaData = _c::sfTransactionMetaData => "sfTransactionMetaData",
+        CreatedNode = _c::sfCreatedNode => "sfCreatedNode",
+        DeletedNode = _c::sfDeletedNode => "sfDeletedNode",
+        ModifiedNode = _c::sfModifiedNode => "sfModifiedNode",
+        PreviousFields = _c::sfPreviousFields => "sfPreviousFields",
+        FinalFields = _c::sfFinalFields => "sfFinalFields",
+        NewFields = _c::sfNewFields => "sfNewFields",
+        TemplateEntry = _c::sfTemplateEntry => "sfTemplateEntry",
+        Memo = _c::sfMemo => "sfMemo",
+        SignerEntry = _c::sfSignerEntry => "sfSignerEntry",
+        NFToken = _c::sfNFToken => "sfNFToken",
+        EmitDetails = _c::sfEmitDetails => "sfEmitDetails",
+        Hook = _c::sfHook => "sfHook",
+        Signer = _c::sfSigner => "sfSigner",
+        Majority = _c::sfMajority => "sfMajority",
+        DisabledValidator = _c::sfDisabledValidator => "sfDisabledValidator",
+        EmittedTxn = _c::sfEmittedTxn => "sfEmittedTxn",
+        HookExecution = _c::sfHookExecution => "sfHookExecution",
+        HookDefinition = _c::sfHookDefinition => "sfHookDefinition",
+        HookParameter = _c::sfHookParameter => "sfHookParameter",
+        HookGrant = _c::sfHookGrant => "sfHookGrant",
+        GenesisMint = _c::sfGenesisMint => "sfGenesisMint",
+        ActiveValidator = _c::sfActiveValidator => "sfActiveValidator",
+        ImportVLKey = _c::sfImportVLKey => "sfImportVLKey",
+        HookEmission = _c::sfHookEmission => "sfHookEmission",
+        MintURIToken = _c::sfMintURIToken => "sfMintURIToken",
+        AmountEntry = _c::sfAmountEntry => "sfAmountEntry",
+        Signers = _c::sfSigners => "sfSigners",
+        SignerEntries = _c::sfSignerEntries => "sfSignerEntries",
+        Template = _c::sfTemplate => "sfTemplate",
+        Necessary = _c::sfNecessary => "sfNecessary",
+        Sufficient = _c::sfSufficient => "sfSufficient",
+        AffectedNodes = _c::sfAffectedNodes => "sfAffectedNodes",
+        Memos = _c::sfMemos => "sfMemos",
+        NFTokens = _c::sfNFTokens => "sfNFTokens",
+        Hooks = _c::sfHooks => "sfHooks",
+        Majorities = _c::sfMajorities => "sfMajorities",
+        DisabledValidators = _c::sfDisabledValidators => "sfDisabledValidators",
+        HookExecutions = _c::sfHookExecutions => "sfHookExecutions",
+        HookParameters = _c::sfHookParameters => "sfHookParameters",
+        HookGrants = _c::sfHookGrants => "sfHookGrants",
+        GenesisMints = _c::sfGenesisMints => "sfGenesisMints",
+        ActiveValidators = _c::sfActiveValidators => "sfActiveValidators",
+        ImportVLKeys = _c::sfImportVLKeys => "sfImportVLKeys",
+        HookEmissions = _c::sfHookEmissions => "sfHookEmissions",
+        Amounts = _c::sfAmounts => "sfAmounts",
+    }
+}
+
+code_enum! {
+    /// Possible errors returned by Hook APIs.
+    ///
+    /// Errors are global across all Hook APIs. Non-negative return codes refer always to success
+    /// and usually indicate the number of bytes written or events performed, depending on the specific API.
+    #[derive(Clone, Copy)]
+    pub enum Error: i32 {
+        /// A pointer or buffer length provided as a parameter described memory outside of the Hook's allowed memory region.
+        OutOfBounds = _c::OUT_OF_BOUNDS => "OUT_OF_BOUNDS",
+        /// Reserved for internal invariant trips, generally unrelated to inputs.
+        /// These should be reported with an issue.
+        InternalError = _c::INTERNAL_ERROR => "INTERNAL_ERROR",
+        /// Attempted to set a parameter or value larger than the allowed space .
+        TooBig = _c::TOO_BIG => "TOO_BIG",
+        /// The API was unable to produce output to the write_ptr because the specified write_len was too small
+        TooSmall = _c::TOO_SMALL => "TOO_SMALL",
+        /// The requested object or item wasn't found
+        DoesntExist = _c::DOESNT_EXIST => "DOESNT_EXIST",
+        /// The Hook attempted to allocate an item into a slot, but there were no slots free.
+        /// To avoid ensure re-use of existing slots. The maximum number of slots is 255.
+        NoFreeSlots = _c::NO_FREE_SLOTS => "NO_FREE_SLOTS",
+        /// One or more of the parameters to the API were invalid according to the individual API's specification.
+        InvalidArgument = _c::INVALID_ARGUMENT => "INVALID_ARGUMENT",
+        /// Some APIs allow for a once-per-execution parameter to be set.
+        /// A second attempt to set a once-per-execution parameter results in this error.
+        AlreadySet = _c::ALREADY_SET => "ALREADY_SET",
+        /// An API required the Hook to do something before the API is allowed to be called.
+        /// Check the API's documentation.
+        PrerequisiteNotMet = _c::PREREQUISITE_NOT_MET => "PREREQUISITE_NOT_MET",
+        /// During fee calculation if an absurdly large fee is calculated this error is returned.
+        FeeTooLarge = _c::FEE_TOO_LARGE => "FEE_TOO_LARGE",
+        /// An attempt to emit() a TXN was unsccessful for any of a number of reasons.
+        /// Check the trace log of the rippled to which you are submitting the originating TXN.
+        EmissionFailure = _c::EMISSION_FAILURE => "EMISSION_FAILURE",
+        /// A Hook may only use up to 256 calls to nonce() per execution.
+        /// Further calls result in this error code.
+        TooManyNonces = _c::TOO_MANY_NONCES => "TOO_MANY_NONCES",
+        /// A Hook must declare ahead of time how many TXN it intends to emit().
+        /// If it emits fewer than this many, this is allowed.
+        /// If it emits more than this many this error is returned.
+        TooManyEmittedTxn = _c::TOO_MANY_EMITTED_TXN => "TOO_MANY_EMITTED_TXN",
+        /// While Hooks is/was in development an API may return this if some or all of that API is planned but not yet implemented.
+        NotImplemented = _c::NOT_IMPLEMENTED => "NOT_IMPLEMENTED",
+        /// An API which accepts a 20 byte Account ID may return this if, in its opinion, the Account ID was not valid for any reason.
+        InvalidAccount = _c::INVALID_ACCOUNT => "INVALID_ACCOUNT",
+        /// All loops inside a Hook must declare at the top of the loop, as the first non trivial instruction,
+        /// before any branch instruction, the promised maximum number of iterations of the loop.
+        /// If this promise is violated the hook terminates immediately with this error code.
+        GuardViolation = _c::GUARD_VIOLATION => "GUARD_VIOLATION",
+        /// The requested serialized field could not be found in the specified object.
+        InvalidField = _c::INVALID_FIELD => "INVALID_FIELD",
+        /// While parsing serialized content an error was encountered (typically indicating an invalidly serialized object).
+        ParseError = _c::PARSE_ERROR => "PARSE_ERROR",
+        /// Used internally to communicate a rollback event.
+        RcRollback = _c::RC_ROLLBACK => "RC_ROLLBACK",
+        /// Used internally to communicate an accept event.
+        RcAccept = _c::RC_ACCEPT => "RC_ACCEPT",
+        /// Specified keylet could not be found, or keylet is invalid
+        NoSuchKeylet = _c::NO_SUCH_KEYLET => "NO_SUCH_KEYLET",
+        /// API was asked to assume object under analysis is an STArray but it was not.
+        NotAnArray = _c::NOT_AN_ARRAY => "NOT_AN_ARRAY",
+        /// API was asked to assume object under analysis is an STObject but it was not.
+        NotAnObject = _c::NOT_AN_OBJECT => "NOT_AN_OBJECT",
+        /// A floating point operation resulted in Not-A-Number or API call attempted to specify an XFL floating point number outside of the expressible range of XFL.
+        InvalidFloat = _c::INVALID_FLOAT => "INVALID_FLOAT",
+        /// API call would result in a division by zero, so API ended early.
+        DivisionByZero = _c::DIVISION_BY_ZERO => "DIVISION_BY_ZERO",
+        /// When attempting to create an XFL the mantissa must be 16 decimal digits.
+        MantissaOversized = _c::MANTISSA_OVERSIZED => "MANTISSA_OVERSIZED",
+        /// When attempting to create an XFL the mantissa must be 16 decimal digits.
+        MantissaUndersized = _c::MANTISSA_UNDERSIZED => "MANTISSA_UNDERSIZED",
+        /// When attempting to create an XFL the exponent must not exceed 80.
+        ExponentOversized = _c::EXPONENT_OVERSIZED => "EXPONENT_OVERSIZED",
+        /// When attempting to create an XFL the exponent must not be less than -96.
+        ExponentUndersized = _c::EXPONENT_UNDERSIZED => "EXPONENT_UNDERSIZED",
+        /// A floating point operation done on an XFL resulted in a value larger than XFL format is able to represent.
+        XflOverflow = _c::XFL_OVERFLOW => "XFL_OVERFLOW",
+        /// An API assumed an STAmount was an IOU when in fact it was XRP.
+        NotIouAmount = _c::NOT_IOU_AMOUNT => "NOT_IOU_AMOUNT",
+        /// An API assumed an STObject was an STAmount when in fact it was not.
+        NotAnAmount = _c::NOT_AN_AMOUNT => "NOT_AN_AMOUNT",
+        /// An API would have returned a negative integer except that negative integers are reserved for error codes (i.e. what you are reading.)
+        CantReturnNegative = _c::CANT_RETURN_NEGATIVE => "CANT_RETURN_NEGATIVE",
+        /// Hook attempted to set foreign state but was not authorized to do so (grant was missing or invalid.)
+        NotAuthorized = _c::NOT_AUTHORIZED => "NOT_AUTHORIZED",
+        /// Hook previously received a NOT_AUTHORIZED return code and is not allowed to retry.
+        PreviousFailurePreventsRetry = _c::PREVIOUS_FAILURE_PREVENTS_RETRY => "PREVIOUS_FAILURE_PREVENTS_RETRY",
+        /// Attempted to set a hook parameter for a later hook in the chain, but there are now too many parameters.
+        TooManyParams = _c::TOO_MANY_PARAMS => "TOO_MANY_PARAMS",
+        /// Serialized transaction was not a valid transaction (usually because of a missing required field or data corruption / truncation.)
+        InvalidTxn = _c::INVALID_TXN => "INVALID_TXN",
+        /// Setting an additional state object on this account would cause the reserve requirements to exceed the account's balance.
+        ReserveInssuficient = _c::RESERVE_INSUFFICIENT => "RESERVE_INSUFFICIENT",
+        /// Hook API would be forced to return a complex number, which it cannot do.
+        ComplexNotSupported = _c::COMPLEX_NOT_SUPPORTED => "COMPLEX_NOT_SUPPORTED",
+        /// Two arguments were required to be of the same type but are not.
+        DoesNotMatch = _c::DOES_NOT_MATCH => "DOES_NOT_MATCH",
+        /// The provided public key was not valid.
+        InvalidKey = _c::INVALID_KEY => "INVALID_KEY",
+        /// The buffer did not contain a nul terminated string.
+        NotAString = _c::NOT_A_STRING => "NOT_A_STRING",
+        /// The writing pointer points to a buffer that overlaps with the reading pointer.
+        MemOverlap = _c::MEM_OVERLAP => "MEM_OVERLAP",
+        /// More than 5000 modified state entries in the combined hook chains
+        TooManyStateModifications = _c::TOO_MANY_STATE_MODIFICATIONS => "TOO_MANY_STATE_MODIFICATIONS",
+        /// More than 256 namespaces on this account
+        TooManyNamespaces = _c::TOO_MANY_NAMESPACES => "TOO_MANY_NAMESPACES",
+    }
+
+    /// Error code not known to this crate, such as one added by a later amendment
+    Unknown => "UNKNOWN"
+}
@@ -1,4 +1,4 @@
-use crate::_c;
+use super::*;
 
 /// Guard function
 ///
@@ -6,6 +6,9 @@ use crate::_c;
 /// the first branch instruction in wasm binary after the beginning of the loop.
 /// In order to achieve this in Rust use the `while` loop with expression block.
 ///
+/// Prefer the [guard!](crate::guard!), [guarded_while!](crate::guarded_while!)
+/// and [guarded_for!](crate::guarded_for!) macros, which derive a unique guard ID.
+///
 /// # Example
 ///
 /// ```no_run
@@ -30,7 +33,7 @@ pub fn _g(id: u32, maxiter: u32) {
 #[inline(always)]
 pub fn accept(msg: &[u8], error_code: i64) -> ! {
     unsafe {
-        _c::accept(msg.as_ptr() as u32, msg.len() as u32, error_code);
+        _c::accept(msg.as_ptr() as _, msg.len() as u32, error_code);
         core::hint::unreachable_unchecked()
     }
 }
@@ -39,7 +42,92 @@ pub fn accept(msg: &[u8], error_code: i64) -> ! {
 #[inline(always)]
 pub fn rollback(msg: &[u8], error_code: i64) -> ! {
     unsafe {
-        _c::rollback(msg.as_ptr() as u32, msg.len() as u32, error_code);
+        _c::rollback(msg.as_ptr() as _, msg.len() as u32, error_code);
         core::hint::unreachable_unchecked()
     }
 }
+
+/// Request the hook to be executed again, weakly, after the originating transaction is applied
+///
+/// Only a strongly executing hook can request this.
+#[inline(always)]
+pub fn hook_again() -> Result<HookAgainOutcome> {
+    let res = unsafe { _c::hook_again() };
+
+    match res {
+        res if res >= 0 => Ok(HookAgainOutcome::WillExecuteWeakly),
+        res if res == _c::PREREQUISITE_NOT_MET as i64 => Ok(HookAgainOutcome::PrerequisiteNotMet),
+        _ => Err(Error::from_code(res as _)),
+    }
+}
+
+/// Skip (or cancel skipping) a hook later in the current chain
+#[inline(always)]
+pub fn hook_skip(hook_hash: &Hash, mode: SkipMode) -> Result<i64> {
+    let res = unsafe { _c::hook_skip(hook_hash.as_ptr() as _, hook_hash.len() as u32, mode as _) };
+
+    result_i64(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::MockLedger;
+
+    const OTHER_HOOK: Hash = [0xAB; 32];
+
+    #[test]
+    fn hook_again_test() {
+        let mut ledger = MockLedger::new();
+
+        let outcome = ledger.run(|| {
+            assert!(matches!(
+                hook_again(),
+                Ok(HookAgainOutcome::WillExecuteWeakly)
+            ));
+            // the host only takes the first request
+            assert!(matches!(hook_again(), Err(Error::AlreadySet)));
+            accept(b"", 0)
+        });
+        assert!(outcome.is_accept());
+        assert!(ledger.hook_again);
+
+        // the second execution is weak, so it can't ask again
+        ledger.execution = ExecutionContext::AgainAsWeak;
+        let outcome = ledger.run(|| {
+            assert!(matches!(
+                hook_again(),
+                Ok(HookAgainOutcome::PrerequisiteNotMet)
+            ));
+            accept(b"", 0)
+        });
+        assert!(outcome.is_accept());
+    }
+
+    #[test]
+    fn hook_skip_test() {
+        let mut ledger = MockLedger::new();
+
+        let outcome = ledger.run(|| {
+            assert!(matches!(hook_skip(&OTHER_HOOK, SkipMode::Skip), Ok(1)));
+            accept(b"", 0)
+        });
+        assert!(outcome.is_accept());
+        assert!(ledger.hook_skips.contains(&OTHER_HOOK));
+
+        let outcome = ledger.run(|| {
+            assert!(matches!(
+                hook_skip(&OTHER_HOOK, SkipMode::CancelSkip),
+                Ok(1)
+            ));
+            // never skipped
+            assert!(matches!(
+                hook_skip(&[0xCD; 32], SkipMode::CancelSkip),
+                Err(Error::DoesntExist)
+            ));
+            accept(b"", 0)
+        });
+        assert!(outcome.is_accept());
+        assert!(ledger.hook_skips.is_empty());
+    }
+}
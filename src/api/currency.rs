@@ -0,0 +1,243 @@
+use super::*;
+use crate::macros::hex_digit;
+
+/// Currency of an amount
+///
+/// Wraps the 20 bytes [CurrencyCode] of the ledger. A standard currency has a three
+/// character ISO code at bytes 12 to 14 and zeros elsewhere, XRP is all zeros, and any
+/// other code is a non-standard 160-bit currency.
+///
+/// Constructors are `const fn`, so currencies can be constants:
+///
+/// ``` txt
+/// const USD: Currency = match Currency::iso(b"USD") {
+///     Ok(currency) => currency,
+///     Err(_) => panic!(),
+/// };
+///
+/// let keylet_type = KeyletType::Line(&hook_account, &issuer, USD.as_code());
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Currency(CurrencyCode);
+
+/// Position of the ISO code in a standard currency code
+const ISO_START: usize = 12;
+
+/// ISO code bits of the first 16 bytes of a standard currency code
+const ISO_MASK: u128 = 0xFFFF_FF00;
+
+impl Currency {
+    /// XRP, the native currency
+    pub const XRP: Currency = Currency([0; CURRENCY_CODE_SIZE]);
+
+    /// Create a standard currency from its ISO code
+    ///
+    /// # Errors
+    ///
+    /// * [Error::InvalidArgument] if a character isn't allowed or the code is the reserved `XRP`
+    pub const fn iso(code: &[u8; 3]) -> Result<Currency> {
+        if !is_iso_char(code[0]) || !is_iso_char(code[1]) || !is_iso_char(code[2]) {
+            return Err(Error::InvalidArgument);
+        }
+
+        let mut bytes = [0; CURRENCY_CODE_SIZE];
+        bytes[ISO_START] = code[0];
+        bytes[ISO_START + 1] = code[1];
+        bytes[ISO_START + 2] = code[2];
+        Currency::checked(bytes)
+    }
+
+    /// Create a currency from the 40 hex digits of its code, in either case
+    ///
+    /// # Errors
+    ///
+    /// * [Error::InvalidArgument] if the length or a digit is invalid,
+    ///   or the code is the standard layout of the reserved `XRP` ISO code
+    pub const fn from_hex(hex: &[u8]) -> Result<Currency> {
+        if hex.len() != 2 * CURRENCY_CODE_SIZE {
+            return Err(Error::InvalidArgument);
+        }
+
+        let mut code = [0; CURRENCY_CODE_SIZE];
+        // Unrolled, so that no loop needs a guard
+        macro_rules! decode {
+            ($($i:literal)*) => {
+                $(
+                    code[$i] = match (hex_digit(hex[2 * $i]), hex_digit(hex[2 * $i + 1])) {
+                        (Some(high), Some(low)) => (high << 4) | low,
+                        _ => return Err(Error::InvalidArgument),
+                    };
+                )*
+            };
+        }
+        decode!(0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19);
+        Currency::checked(code)
+    }
+
+    /// Create a currency from a currency code as read from the ledger
+    ///
+    /// The code is not validated.
+    #[inline(always)]
+    pub const fn from_code(code: CurrencyCode) -> Currency {
+        Currency(code)
+    }
+
+    /// Get the currency code, as taken by [float_sto] and [KeyletType::Line]
+    #[inline(always)]
+    pub const fn as_code(&self) -> &CurrencyCode {
+        &self.0
+    }
+
+    /// Returns `true` if the currency is XRP
+    #[inline(always)]
+    pub const fn is_xrp(&self) -> bool {
+        let (high, low) = self.words();
+        high == 0 && low == 0
+    }
+
+    /// Returns `true` if the currency has an ISO code, XRP excluded
+    #[inline(always)]
+    pub const fn is_standard(&self) -> bool {
+        let (high, low) = self.words();
+        high & !ISO_MASK == 0 && low == 0 && !self.is_xrp()
+    }
+
+    /// Get the ISO code of a standard currency
+    #[inline(always)]
+    pub const fn iso_code(&self) -> Option<[u8; 3]> {
+        if self.is_standard() {
+            Some([
+                self.0[ISO_START],
+                self.0[ISO_START + 1],
+                self.0[ISO_START + 2],
+            ])
+        } else {
+            None
+        }
+    }
+
+    /// Bytes 0 to 15 and 16 to 19 of the code as big-endian numbers
+    const fn words(&self) -> (u128, u32) {
+        let [high @ .., a, b, c, d] = self.0;
+        (u128::from_be_bytes(high), u32::from_be_bytes([a, b, c, d]))
+    }
+
+    /// Rejects the standard layout of the reserved `XRP` ISO code
+    const fn checked(code: CurrencyCode) -> Result<Currency> {
+        let currency = Currency(code);
+        match currency.iso_code() {
+            Some(iso) if iso[0] == b'X' && iso[1] == b'R' && iso[2] == b'P' => {
+                Err(Error::InvalidArgument)
+            }
+            _ => Ok(currency),
+        }
+    }
+}
+
+impl From<CurrencyCode> for Currency {
+    #[inline(always)]
+    fn from(code: CurrencyCode) -> Self {
+        Currency(code)
+    }
+}
+
+impl From<Currency> for CurrencyCode {
+    #[inline(always)]
+    fn from(currency: Currency) -> Self {
+        currency.0
+    }
+}
+
+/// Tests if a character is allowed in ISO codes
+const fn is_iso_char(c: u8) -> bool {
+    c.is_ascii_alphanumeric()
+        || matches!(
+            c,
+            b'<' | b'>'
+                | b'('
+                | b')'
+                | b'{'
+                | b'}'
+                | b'['
+                | b']'
+                | b'|'
+                | b'?'
+                | b'!'
+                | b'@'
+                | b'#'
+                | b'$'
+                | b'%'
+                | b'^'
+                | b'&'
+                | b'*'
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex;
+
+    #[test]
+    fn iso_test() {
+        const USD: Currency = match Currency::iso(b"USD") {
+            Ok(currency) => currency,
+            Err(_) => panic!(),
+        };
+        assert!(*USD.as_code() == hex!("0000000000000000000000005553440000000000"));
+        assert!(USD.is_standard());
+        assert!(!USD.is_xrp());
+        assert!(USD.iso_code() == Some(*b"USD"));
+
+        assert!(matches!(Currency::iso(b"XRP"), Err(Error::InvalidArgument)));
+        assert!(matches!(Currency::iso(b"US "), Err(Error::InvalidArgument)));
+        assert!(matches!(Currency::iso(b"xrp"), Ok(_)));
+    }
+
+    #[test]
+    fn from_hex_test() {
+        let usd = Currency::from_hex(b"0000000000000000000000005553440000000000");
+        assert!(matches!(usd, Ok(usd) if usd.iso_code() == Some(*b"USD")));
+        const EUR: Result<Currency> =
+            Currency::from_hex(b"0000000000000000000000004555520000000000");
+        assert!(matches!(EUR, Ok(eur) if eur.iso_code() == Some(*b"EUR")));
+
+        let xau = Currency::from_hex(b"0158415500000000c1f76ff6ecb0bac600000000");
+        assert!(matches!(xau, Ok(xau) if !xau.is_standard() && !xau.is_xrp()));
+        assert!(
+            matches!(xau, Ok(xau) if *xau.as_code() == hex!("0158415500000000C1F76FF6ECB0BAC600000000"))
+        );
+
+        assert!(matches!(
+            Currency::from_hex(b"0000000000000000000000000000000000000000"),
+            Ok(Currency::XRP)
+        ));
+        assert!(matches!(
+            Currency::from_hex(b"0000000000000000000000005852500000000000"),
+            Err(Error::InvalidArgument)
+        ));
+        assert!(matches!(
+            Currency::from_hex(b"000000000000000000000000555344000000000g"),
+            Err(Error::InvalidArgument)
+        ));
+        assert!(matches!(
+            Currency::from_hex(b"G000000000000000000000005553440000000000"),
+            Err(Error::InvalidArgument)
+        ));
+        assert!(matches!(
+            Currency::from_hex(b"00000000000000000000000055534400"),
+            Err(Error::InvalidArgument)
+        ));
+    }
+
+    #[test]
+    fn xrp_test() {
+        assert!(Currency::XRP.is_xrp());
+        assert!(!Currency::XRP.is_standard());
+        assert!(Currency::XRP.iso_code().is_none());
+
+        let code: CurrencyCode = Currency::XRP.into();
+        assert!(code == [0; CURRENCY_CODE_SIZE]);
+        assert!(Currency::from(code) == Currency::XRP);
+    }
+}
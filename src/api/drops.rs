@@ -0,0 +1,332 @@
+use super::*;
+use crate::xfl::mul_div;
+
+/// Native XRP amount in drops
+///
+/// Always within the XRP supply, [Drops::MAX]. Arithmetic is checked against it,
+/// like the `checked_*` methods of integers.
+///
+/// # Example
+///
+/// ``` txt
+/// let amount = match otxn_amount(FieldId::Amount) {
+///     Ok(Amount::Xrp(drops)) => Drops::new(drops).expect(b"out of range"),
+///     _ => rollback(b"not an XRP amount", 1),
+/// };
+/// let fee = match amount.mul_ratio(FEE_PER_MILLION, 1_000_000, true) {
+///     Some(fee) => fee,
+///     None => rollback(b"fee out of range", 1),
+/// };
+/// let payout = amount.checked_sub(fee);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Drops(u64);
+
+/// Bit set in serialized issued currency amounts
+const IOU_BIT: u64 = 1 << 63;
+
+/// Bit set in serialized positive native amounts
+const POSITIVE_BIT: u64 = 1 << 62;
+
+impl Drops {
+    /// No drops
+    pub const ZERO: Drops = Drops(0);
+
+    /// The XRP supply, 100 billion XRP
+    pub const MAX: Drops = Drops(100_000_000_000_000_000);
+
+    /// Create an amount of drops
+    ///
+    /// # Errors
+    ///
+    /// * [Error::InvalidArgument] if `drops` exceeds [Drops::MAX]
+    #[inline(always)]
+    pub const fn new(drops: u64) -> Result<Drops> {
+        if drops > Drops::MAX.0 {
+            return Err(Error::InvalidArgument);
+        }
+        Ok(Drops(drops))
+    }
+
+    /// Get the number of drops
+    #[inline(always)]
+    pub const fn get(self) -> u64 {
+        self.0
+    }
+
+    /// Add two amounts, `None` if the sum exceeds [Drops::MAX]
+    #[inline(always)]
+    pub const fn checked_add(self, other: Drops) -> Option<Drops> {
+        // Both are at most 10^17, the sum can't overflow
+        match self.0 + other.0 {
+            sum if sum > Drops::MAX.0 => None,
+            sum => Some(Drops(sum)),
+        }
+    }
+
+    /// Subtract an amount, `None` if the difference is negative
+    #[inline(always)]
+    pub const fn checked_sub(self, other: Drops) -> Option<Drops> {
+        match self.0.checked_sub(other.0) {
+            Some(difference) => Some(Drops(difference)),
+            None => None,
+        }
+    }
+
+    /// Multiply by `numerator / denominator`, rounding down or up
+    ///
+    /// The product doesn't overflow before the division.
+    /// `None` if `denominator` is zero or the result exceeds [Drops::MAX].
+    #[inline(always)]
+    pub const fn mul_ratio(
+        self,
+        numerator: u64,
+        denominator: u64,
+        round_up: bool,
+    ) -> Option<Drops> {
+        let (mut result, remainder) = match mul_div(self.0, numerator, denominator) {
+            Some(division) => division,
+            None => return None,
+        };
+        if result > Drops::MAX.0 {
+            return None;
+        }
+        if round_up && remainder != 0 {
+            result += 1;
+        }
+
+        if result > Drops::MAX.0 {
+            return None;
+        }
+        Some(Drops(result))
+    }
+
+    /// Decode a serialized native amount, without field header
+    ///
+    /// # Errors
+    ///
+    /// * [Error::InvalidArgument] if it's an issued amount or exceeds [Drops::MAX]
+    /// * [Error::CantReturnNegative] if it's negative, including zero without the sign bit
+    #[inline(always)]
+    pub const fn from_sto(sto: &NativeAmount) -> Result<Drops> {
+        let raw = u64::from_be_bytes(*sto);
+        if raw & IOU_BIT != 0 {
+            return Err(Error::InvalidArgument);
+        }
+        if raw & POSITIVE_BIT == 0 {
+            return Err(Error::CantReturnNegative);
+        }
+
+        Drops::new(raw & !POSITIVE_BIT)
+    }
+
+    /// Encode as a serialized native amount, without field header
+    #[inline(always)]
+    pub const fn to_sto(self) -> NativeAmount {
+        (self.0 | POSITIVE_BIT).to_be_bytes()
+    }
+}
+
+impl From<Drops> for u64 {
+    #[inline(always)]
+    fn from(drops: Drops) -> Self {
+        drops.0
+    }
+}
+
+impl From<Drops> for Amount {
+    #[inline(always)]
+    fn from(drops: Drops) -> Self {
+        Amount::Xrp(drops.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{amount_to_drops, drops_to_amount};
+
+    const MAX: u64 = 100_000_000_000_000_000;
+
+    fn drops(value: u64) -> Drops {
+        match Drops::new(value) {
+            Ok(drops) => drops,
+            Err(_) => panic!("{value} is out of range"),
+        }
+    }
+
+    /// Values of the 62-bit range of native amounts, spread over all magnitudes
+    ///
+    /// SplitMix64 values shifted right by a random number of bits, plus the edges.
+    fn samples() -> impl Iterator<Item = u64> {
+        let mut state: u64 = 0x2545_F491_4F6C_DD1D;
+        let mut next = move || {
+            state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^ (z >> 31)
+        };
+        let edges = [0, 1, MAX - 1, MAX, MAX + 1, (1 << 62) - 1];
+
+        edges.into_iter().chain((0..100_000).map(move |_| {
+            let value = next();
+            (value >> (next() % 64)) & ((1 << 62) - 1)
+        }))
+    }
+
+    #[test]
+    fn new_test() {
+        assert!(matches!(Drops::new(0), Ok(Drops::ZERO)));
+        assert!(matches!(Drops::new(MAX), Ok(Drops::MAX)));
+        assert!(matches!(Drops::new(MAX + 1), Err(Error::InvalidArgument)));
+        assert!(matches!(Drops::new(u64::MAX), Err(Error::InvalidArgument)));
+    }
+
+    #[test]
+    fn arithmetic_test() {
+        assert!(drops(1).checked_add(drops(2)) == Some(drops(3)));
+        assert!(Drops::MAX.checked_add(drops(1)).is_none());
+        assert!(drops(1).checked_sub(drops(2)).is_none());
+        assert!(drops(1_000).mul_ratio(3, 7, false) == Some(drops(428)));
+        assert!(drops(1_000).mul_ratio(3, 7, true) == Some(drops(429)));
+        assert!(drops(1_000).mul_ratio(7, 7, true) == Some(drops(1_000)));
+        assert!(drops(1).mul_ratio(1, 0, false).is_none());
+        assert!(Drops::MAX.mul_ratio(u64::MAX, u64::MAX, false) == Some(Drops::MAX));
+        assert!(Drops::MAX.mul_ratio(2, 1, false).is_none());
+    }
+
+    #[test]
+    fn mul_div_test() {
+        assert!(mul_div(u64::MAX, u64::MAX, u64::MAX) == Some((u64::MAX, 0)));
+        assert!(mul_div(u64::MAX, u64::MAX, 1).is_none());
+        assert!(mul_div(1 << 63, 2, 1).is_none());
+        assert!(mul_div(1 << 63, 2, 2) == Some((1 << 63, 0)));
+        assert!(mul_div(7, 3, 0).is_none());
+        assert!(mul_div(0, 0, 0).is_none());
+
+        let values: std::vec::Vec<u64> = samples().take(30_000).collect();
+        for (i, terms) in values.windows(3).enumerate() {
+            let a = terms[0] << 2 | terms[1] >> 60;
+            let b = !terms[1] >> (i % 64);
+            let d = terms[2].rotate_left(i as u32);
+            let product = a as u128 * b as u128;
+            let expected = match d {
+                0 => None,
+                d if product / d as u128 > u64::MAX as u128 => None,
+                d => Some(((product / d as u128) as u64, (product % d as u128) as u64)),
+            };
+            assert!(mul_div(a, b, d) == expected, "{a} * {b} / {d}");
+        }
+    }
+
+    #[test]
+    fn sto_test() {
+        assert!(Drops::ZERO.to_sto() == [0x40, 0, 0, 0, 0, 0, 0, 0]);
+        assert!(Drops::MAX.to_sto() == [0x41, 0x63, 0x45, 0x78, 0x5D, 0x8A, 0, 0]);
+        assert!(matches!(
+            Drops::from_sto(&[0x40, 0, 0, 0, 0, 0, 0, 0]),
+            Ok(Drops::ZERO)
+        ));
+        assert!(matches!(
+            Drops::from_sto(&[0; 8]),
+            Err(Error::CantReturnNegative)
+        ));
+        assert!(matches!(
+            Drops::from_sto(&[0, 0, 0, 0, 0, 0, 0, 1]),
+            Err(Error::CantReturnNegative)
+        ));
+        assert!(matches!(
+            Drops::from_sto(&[0xD4, 0, 0, 0, 0, 0, 0, 0]),
+            Err(Error::InvalidArgument)
+        ));
+        assert!(matches!(
+            Drops::from_sto(&[0x41, 0x63, 0x45, 0x78, 0x5D, 0x8A, 0, 1]),
+            Err(Error::InvalidArgument)
+        ));
+    }
+
+    #[test]
+    fn sto_property_test() {
+        for value in samples() {
+            let raw = (value | POSITIVE_BIT).to_be_bytes();
+            if value <= MAX {
+                assert!(drops(value).to_sto() == raw, "{value}");
+                assert!(
+                    matches!(Drops::from_sto(&raw), Ok(d) if d.get() == value),
+                    "{value}"
+                );
+                assert!(
+                    matches!(drops_to_amount(value), Ok(a) if a == raw),
+                    "{value}"
+                );
+                assert!(
+                    matches!(amount_to_drops(&raw), Ok(v) if v == value),
+                    "{value}"
+                );
+            } else {
+                assert!(Drops::new(value).is_err(), "{value}");
+                assert!(Drops::from_sto(&raw).is_err(), "{value}");
+                assert!(drops_to_amount(value).is_err(), "{value}");
+                assert!(amount_to_drops(&raw).is_err(), "{value}");
+            }
+            // without the sign bit, even zero is negative
+            assert!(
+                matches!(
+                    Drops::from_sto(&value.to_be_bytes()),
+                    Err(Error::CantReturnNegative)
+                ),
+                "{value}"
+            );
+            // the sign and type bits never leak into the value
+            assert!(amount_to_drops(&(value | IOU_BIT).to_be_bytes()).is_err());
+        }
+    }
+
+    #[test]
+    fn arithmetic_property_test() {
+        let mut previous = Drops::ZERO;
+        for value in samples() {
+            let value = match Drops::new(value) {
+                Ok(value) => value,
+                Err(_) => continue,
+            };
+            let (a, b) = (value.get(), previous.get());
+
+            let sum = value.checked_add(previous);
+            assert!(sum.map(Drops::get) == Some(a + b).filter(|sum| *sum <= MAX));
+            let difference = value.checked_sub(previous);
+            assert!(difference.map(Drops::get) == a.checked_sub(b));
+
+            // small ratios, and ratios whose terms have their top bits set
+            let ratios = [
+                (b % 1_000_003, a % 999_983),
+                (b, a),
+                (u64::MAX - b, a | 1 << 63),
+                (b << 7, a << 6 | 1),
+            ];
+            for (numerator, denominator) in ratios {
+                let ratio = value.mul_ratio(numerator, denominator, false);
+                let ratio_up = value.mul_ratio(numerator, denominator, true);
+                if denominator == 0 {
+                    assert!(ratio.is_none() && ratio_up.is_none());
+                    continue;
+                }
+                let product = a as u128 * numerator as u128;
+                let expected = product / denominator as u128;
+                let expected_up = product.div_ceil(denominator as u128);
+                assert!(
+                    ratio.map(|r| r.get() as u128) == Some(expected).filter(|r| *r <= MAX as u128),
+                    "{a} * {numerator} / {denominator}"
+                );
+                assert!(
+                    ratio_up.map(|r| r.get() as u128)
+                        == Some(expected_up).filter(|r| *r <= MAX as u128),
+                    "{a} * {numerator} / {denominator}"
+                );
+            }
+
+            previous = value;
+        }
+    }
+}
@@ -1,4 +1,6 @@
 use super::*;
+use crate::sto::Writer;
+use core::marker::PhantomData;
 
 /// Get the burden of a hypothetically emitted transaction
 #[inline(always)]
@@ -35,3 +37,1859 @@ pub fn etxn_generation() -> i64 {
 pub fn emit(hash: &mut [u8], tx_buf: &[u8]) -> Result<i64> {
     buf_write_read(hash, tx_buf, _c::emit)
 }
+
+/// Required field of a [TxnBuilder] not set yet
+#[derive(Clone, Copy)]
+pub struct Missing;
+
+/// Required field of a [TxnBuilder] set
+#[derive(Clone, Copy)]
+pub struct Present;
+
+/// States of a [TxnBuilder] where all required fields are set
+pub trait Complete {}
+
+impl Complete for () {}
+impl Complete for Present {}
+impl Complete for (Present, Present) {}
+impl Complete for (Present, Present, Present, Present) {}
+
+/// Fields of a transaction type, set with its [TxnBuilder]
+///
+/// Implemented by the fields of each builder, such as [PaymentFields].
+pub trait TxnFields: Copy + Default + sealed::WriteFields {
+    /// Type of the transaction
+    const TXN_TYPE: TxnType;
+}
+
+mod sealed {
+    use super::*;
+
+    /// Writes the fields of a transaction type
+    pub trait WriteFields {
+        /// Write all fields, which are listed in canonical order
+        fn write(&self, w: &mut TxnWriter);
+    }
+
+    /// Values of the fields common to all emitted transactions
+    #[derive(Clone, Copy)]
+    pub(super) struct Header<'h> {
+        pub(super) txn_type: u16,
+        pub(super) flags: u32,
+        pub(super) source_tag: Option<u32>,
+        pub(super) ledger_seq: u32,
+        pub(super) account: &'h AccountId,
+        pub(super) details: &'h [u8],
+        pub(super) memos: Option<&'h [u8]>,
+    }
+
+    /// Writer of the fields of a transaction type, which writes the common fields
+    /// of the [Header] in between to keep all of them in canonical order
+    pub struct TxnWriter<'w, 'buf, 'h> {
+        writer: &'w mut Writer<'buf>,
+        header: Header<'h>,
+        written: u32,
+        fee_pos: usize,
+    }
+
+    impl<'w, 'buf, 'h> TxnWriter<'w, 'buf, 'h> {
+        #[inline(always)]
+        pub(super) fn new(writer: &'w mut Writer<'buf>, header: Header<'h>) -> Self {
+            Self {
+                writer,
+                header,
+                written: 0,
+                fee_pos: 0,
+            }
+        }
+
+        /// Write the remaining common fields, returns the position of the fee
+        #[inline(always)]
+        pub(super) fn finish(mut self) -> usize {
+            self.flush(u32::MAX);
+            self.fee_pos
+        }
+
+        /// Whether the common `field` is due before `next`, marks it written if so
+        #[inline(always)]
+        fn due(&mut self, field: FieldId, next: u32) -> bool {
+            let field = field as u32;
+            let due = field > self.written && field < next;
+            if due {
+                self.written = field;
+            }
+            due
+        }
+
+        /// Write the common fields ordered before `next`
+        #[inline(always)]
+        fn flush(&mut self, next: u32) {
+            let header = self.header;
+            if self.due(FieldId::TransactionType, next) {
+                self.writer.u16(FieldId::TransactionType, header.txn_type);
+            }
+            if self.due(FieldId::Flags, next) {
+                self.writer.u32(FieldId::Flags, TF_CANONICAL | header.flags);
+            }
+            if self.due(FieldId::SourceTag, next) {
+                if let Some(tag) = header.source_tag {
+                    self.writer.u32(FieldId::SourceTag, tag);
+                }
+            }
+            if self.due(FieldId::Sequence, next) {
+                self.writer.u32(FieldId::Sequence, 0);
+            }
+            if self.due(FieldId::FirstLedgerSequence, next) {
+                self.writer
+                    .u32(FieldId::FirstLedgerSequence, header.ledger_seq + 1);
+            }
+            if self.due(FieldId::LastLedgerSequence, next) {
+                self.writer
+                    .u32(FieldId::LastLedgerSequence, header.ledger_seq + 5);
+            }
+            if self.due(FieldId::Fee, next) {
+                // The fee is computed on the whole transaction, then written over the placeholder
+                self.fee_pos = self.writer.len() + 1;
+                self.writer.amount_drops(FieldId::Fee, 0);
+            }
+            if self.due(FieldId::SigningPubKey, next) {
+                self.writer.blob(FieldId::SigningPubKey, &[]);
+            }
+            if self.due(FieldId::Account, next) {
+                self.writer.account(FieldId::Account, header.account);
+            }
+            if self.due(FieldId::EmitDetails, next) {
+                self.writer.serialized(FieldId::EmitDetails, header.details);
+            }
+            if self.due(FieldId::Memos, next) {
+                if let Some(memos) = header.memos {
+                    self.writer.serialized(FieldId::Memos, memos);
+                }
+            }
+        }
+
+        #[inline(always)]
+        pub(super) fn u8(&mut self, field: FieldId, value: Option<u8>) {
+            self.flush(field as u32);
+            if let Some(value) = value {
+                self.writer.u8(field, value);
+            }
+        }
+
+        #[inline(always)]
+        pub(super) fn u32(&mut self, field: FieldId, value: Option<u32>) {
+            self.flush(field as u32);
+            if let Some(value) = value {
+                self.writer.u32(field, value);
+            }
+        }
+
+        #[inline(always)]
+        pub(super) fn hash128(&mut self, field: FieldId, value: Option<&[u8; 16]>) {
+            self.flush(field as u32);
+            if let Some(value) = value {
+                self.writer.hash128(field, value);
+            }
+        }
+
+        #[inline(always)]
+        pub(super) fn hash256(&mut self, field: FieldId, value: Option<&Hash>) {
+            self.flush(field as u32);
+            if let Some(value) = value {
+                self.writer.hash256(field, value);
+            }
+        }
+
+        #[inline(always)]
+        pub(super) fn amount(&mut self, field: FieldId, value: Option<Amount>) {
+            self.flush(field as u32);
+            if let Some(value) = value {
+                self.writer.amount(field, &value);
+            }
+        }
+
+        #[inline(always)]
+        pub(super) fn blob(&mut self, field: FieldId, value: Option<&[u8]>) {
+            self.flush(field as u32);
+            if let Some(value) = value {
+                self.writer.blob(field, value);
+            }
+        }
+
+        #[inline(always)]
+        pub(super) fn account(&mut self, field: FieldId, value: Option<&AccountId>) {
+            self.flush(field as u32);
+            if let Some(value) = value {
+                self.writer.account(field, value);
+            }
+        }
+
+        #[inline(always)]
+        pub(super) fn serialized(&mut self, field: FieldId, value: Option<&[u8]>) {
+            self.flush(field as u32);
+            if let Some(value) = value {
+                self.writer.serialized(field, value);
+            }
+        }
+    }
+}
+
+use sealed::{Header, TxnWriter};
+
+/// Setters of optional fields, which keep the state of the builder
+macro_rules! optional {
+    ($($(#[$meta:meta])* $field:ident: $ty:ty,)*) => {
+        $(
+            $(#[$meta])*
+            #[inline(always)]
+            pub fn $field(mut self, $field: $ty) -> Self {
+                self.fields.$field = Some($field);
+                self
+            }
+        )*
+    };
+}
+
+/// Setters of required fields, which mark them [Present] in the state of the builder
+macro_rules! required {
+    ($($(#[$meta:meta])* $field:ident: $ty:ty => $next:ty,)*) => {
+        $(
+            $(#[$meta])*
+            #[inline(always)]
+            pub fn $field(mut self, $field: $ty) -> $next {
+                self.fields.$field = Some($field);
+                self.with_state()
+            }
+        )*
+    };
+}
+
+/// Declares the builder alias of a transaction type, its initial state and its fields,
+/// which are listed in canonical order
+macro_rules! builder {
+    (
+        $(#[$meta:meta])*
+        $name:ident($fields:ident): $txn_type:ident, $initial:ty {
+            $($field:ident: $ty:ty => $put:ident($id:ident),)*
+        }
+    ) => {
+        #[doc = concat!("Fields of [", stringify!($name), "]")]
+        #[derive(Clone, Copy, Default)]
+        pub struct $fields<'a> {
+            $($field: Option<$ty>,)*
+            lifetime: PhantomData<&'a ()>,
+        }
+
+        impl sealed::WriteFields for $fields<'_> {
+            #[inline(always)]
+            fn write(&self, w: &mut TxnWriter) {
+                $(w.$put(FieldId::$id, self.$field);)*
+            }
+        }
+
+        impl TxnFields for $fields<'_> {
+            const TXN_TYPE: TxnType = TxnType::$txn_type;
+        }
+
+        $(#[$meta])*
+        pub type $name<'a, S = $initial> = TxnBuilder<'a, $fields<'a>, S>;
+
+        impl<'a> $name<'a> {
+            /// Builder with no field set
+            #[inline(always)]
+            pub fn new() -> Self {
+                Self {
+                    flags: 0,
+                    source_tag: None,
+                    memos: None,
+                    fields: $fields::default(),
+                    state: PhantomData,
+                }
+            }
+        }
+
+        impl<'a> Default for $name<'a> {
+            #[inline(always)]
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+    };
+}
+
+/// Builder of a transaction ready to [emit]
+///
+/// `F` holds the fields of the transaction type, such as [PaymentFields], and `S` tracks
+/// which required fields are set: [build](TxnBuilder::build) is only available once all
+/// of them are [Present]. Use the alias of each transaction type, such as [PaymentBuilder],
+/// whose methods are the fields valid for that type.
+///
+/// All builders fill `TransactionType`, `Flags` ([TF_CANONICAL] and the flags set with
+/// [flags](TxnBuilder::flags)), `Sequence` 0, `FirstLedgerSequence` and
+/// `LastLedgerSequence` from [ledger_seq], an empty `SigningPubKey`, `Account` from
+/// [hook_account], `EmitDetails` from [etxn_details] and `Fee` from [etxn_fee_base],
+/// like `PREPARE_PAYMENT_SIMPLE` of the C SDK.
+///
+/// There is no builder for NFToken transactions, which Xahau doesn't enable, nor for
+/// `Import` and pseudo-transactions, which hooks can't emit.
+///
+/// Array and object fields, such as `Hooks` or `Memos`, are taken already serialized
+/// with their header, for example by [sto::Writer](crate::sto::Writer).
+///
+/// [etxn_reserve] must be called before [build](TxnBuilder::build).
+///
+/// # Example
+///
+/// ``` txt
+/// etxn_reserve(1);
+///
+/// let mut buf = [0u8; 512];
+/// let txn = match PaymentBuilder::new()
+///     .destination(&destination)
+///     .amount(Amount::Xrp(1_000))
+///     .destination_tag(42)
+///     .build(&mut buf)
+/// {
+///     Ok(txn) => txn,
+///     Err(e) => rollback(b"build failed", e.code() as _),
+/// };
+///
+/// let mut hash = [0u8; 32];
+/// if let Err(e) = emit(&mut hash, txn) {
+///     rollback(b"emit failed", e.code() as _);
+/// }
+/// ```
+///
+/// [build](TxnBuilder::build) doesn't exist until all required fields are set:
+///
+/// ```compile_fail,E0599
+/// # #![no_std]
+/// # #![no_main]
+/// # use rshooks_api::*;
+/// # #[no_mangle]
+/// # pub extern "C" fn hook(_: u32) -> i64 {
+/// let mut buf = [0u8; PaymentBuilder::MAX_LEN];
+/// // the destination is still missing
+/// let _ = PaymentBuilder::new()
+///     .amount(Amount::Xrp(1_000))
+///     .build(&mut buf);
+/// # accept(b"", 0)
+/// # }
+/// ```
+#[derive(Clone, Copy)]
+pub struct TxnBuilder<'a, F, S> {
+    flags: u32,
+    source_tag: Option<u32>,
+    memos: Option<&'a [u8]>,
+    fields: F,
+    state: PhantomData<S>,
+}
+
+impl<'a, F, S> TxnBuilder<'a, F, S> {
+    #[inline(always)]
+    fn with_state<N>(self) -> TxnBuilder<'a, F, N> {
+        TxnBuilder {
+            flags: self.flags,
+            source_tag: self.source_tag,
+            memos: self.memos,
+            fields: self.fields,
+            state: PhantomData,
+        }
+    }
+
+    /// Set flags of the transaction type, [TF_CANONICAL] is always set
+    #[inline(always)]
+    pub fn flags(mut self, flags: u32) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Set the source tag
+    #[inline(always)]
+    pub fn source_tag(mut self, source_tag: u32) -> Self {
+        self.source_tag = Some(source_tag);
+        self
+    }
+
+    /// Set the `Memos` array, serialized with its header
+    #[inline(always)]
+    pub fn memos(mut self, memos: &'a [u8]) -> Self {
+        self.memos = Some(memos);
+        self
+    }
+}
+
+impl<'a, F: TxnFields, S: Complete> TxnBuilder<'a, F, S> {
+    /// Serialize the transaction into `buf`
+    ///
+    /// # Errors
+    ///
+    /// Errors of [hook_account], [etxn_details] and [etxn_fee_base],
+    /// and of [sto::Writer](crate::sto::Writer) for invalid fields or a too small buffer.
+    #[inline(always)]
+    pub fn build<'buf>(&self, buf: &'buf mut [u8]) -> Result<&'buf [u8]> {
+        let mut account: AccountId = [0; ACC_ID_LEN];
+        if let Err(e) = hook_account(&mut account) {
+            return Err(e);
+        }
+        let mut details: EmitDetails = [0; EMIT_DETAILS_SIZE];
+        let details_len = match etxn_details(&mut details) {
+            Ok(len) => len as usize,
+            Err(e) => return Err(e),
+        };
+        let header = Header {
+            txn_type: F::TXN_TYPE as u16,
+            flags: self.flags,
+            source_tag: self.source_tag,
+            ledger_seq: ledger_seq() as u32,
+            account: &account,
+            details: &details[..details_len],
+            memos: self.memos,
+        };
+
+        let mut writer = Writer::new(&mut *buf);
+        let mut txn = TxnWriter::new(&mut writer, header);
+        self.fields.write(&mut txn);
+        let fee_pos = txn.finish();
+        let len = match writer.finish() {
+            Ok(txn) => txn.len(),
+            Err(e) => return Err(e),
+        };
+
+        let fee = match etxn_fee_base(&buf[..len]) {
+            Ok(fee) => fee as u64,
+            Err(e) => return Err(e),
+        };
+        buf[fee_pos..fee_pos + NATIVE_AMOUNT_LEN].copy_from_slice(&(fee | (1 << 62)).to_be_bytes());
+
+        Ok(&buf[..len])
+    }
+}
+
+builder! {
+    /// Builder of a Payment, requires `Destination` and `Amount`
+    PaymentBuilder(PaymentFields): Payment, (Missing, Missing) {
+        destination_tag: u32 => u32(DestinationTag),
+        invoice_id: &'a Hash => hash256(InvoiceID),
+        amount: Amount => amount(Amount),
+        send_max: Amount => amount(SendMax),
+        deliver_min: Amount => amount(DeliverMin),
+        destination: &'a AccountId => account(Destination),
+    }
+}
+
+impl<'a> PaymentBuilder<'a> {
+    /// Longest serialized payment built with [drops](Self::drops) or [iou](Self::iou),
+    /// an IOU payment with both tags
+    pub const MAX_LEN: usize = 277;
+
+    /// Payment of native drops
+    #[inline(always)]
+    pub fn drops(destination: &'a AccountId, drops: u64) -> PaymentBuilder<'a, (Present, Present)> {
+        Self::new()
+            .destination(destination)
+            .amount(Amount::Xrp(drops))
+    }
+
+    /// Payment of an issued currency
+    #[inline(always)]
+    pub fn iou(
+        destination: &'a AccountId,
+        amount: XFL,
+        currency: &'a CurrencyCode,
+        issuer: &'a AccountId,
+    ) -> PaymentBuilder<'a, (Present, Present)> {
+        Self::new().destination(destination).amount(Amount::Iou {
+            value: amount,
+            currency: Currency::from_code(*currency),
+            issuer: *issuer,
+        })
+    }
+}
+
+impl<'a, A> PaymentBuilder<'a, (Missing, A)> {
+    required! {
+        /// Set the receiving account
+        destination: &'a AccountId => PaymentBuilder<'a, (Present, A)>,
+    }
+}
+
+impl<'a, D> PaymentBuilder<'a, (D, Missing)> {
+    required! {
+        /// Set the amount to deliver
+        amount: Amount => PaymentBuilder<'a, (D, Present)>,
+    }
+}
+
+impl<'a, S> PaymentBuilder<'a, S> {
+    optional! {
+        /// Set the destination tag
+        destination_tag: u32,
+        /// Set the invoice ID
+        invoice_id: &'a Hash,
+        /// Set the most the payment can spend, including transfer fees
+        send_max: Amount,
+        /// Set the least a partial payment must deliver
+        deliver_min: Amount,
+    }
+}
+
+builder! {
+    /// Builder of an AccountSet
+    AccountSetBuilder(AccountSetFields): AccountSet, () {
+        transfer_rate: u32 => u32(TransferRate),
+        set_flag: u32 => u32(SetFlag),
+        clear_flag: u32 => u32(ClearFlag),
+        email_hash: &'a [u8; 16] => hash128(EmailHash),
+        message_key: &'a [u8] => blob(MessageKey),
+        domain: &'a [u8] => blob(Domain),
+        tick_size: u8 => u8(TickSize),
+    }
+}
+
+impl<'a> AccountSetBuilder<'a, ()> {
+    optional! {
+        /// Set an account flag
+        set_flag: u32,
+        /// Clear an account flag
+        clear_flag: u32,
+        /// Set the transfer rate, in billionths
+        transfer_rate: u32,
+        /// Set the hash of the email address used for the avatar
+        email_hash: &'a [u8; 16],
+        /// Set the public key for encrypted messages
+        message_key: &'a [u8],
+        /// Set the domain, hex-encoded ASCII lowercase
+        domain: &'a [u8],
+        /// Set the tick size of offers involving currencies issued by the account
+        tick_size: u8,
+    }
+}
+
+builder! {
+    /// Builder of a SetRegularKey, removing the regular key unless it's set
+    RegularKeySetBuilder(RegularKeySetFields): RegularKeySet, () {
+        regular_key: &'a AccountId => account(RegularKey),
+    }
+}
+
+impl<'a> RegularKeySetBuilder<'a, ()> {
+    optional! {
+        /// Set the regular key
+        regular_key: &'a AccountId,
+    }
+}
+
+builder! {
+    /// Builder of an OfferCreate, requires `TakerPays` and `TakerGets`
+    OfferCreateBuilder(OfferCreateFields): OfferCreate, (Missing, Missing) {
+        expiration: u32 => u32(Expiration),
+        offer_sequence: u32 => u32(OfferSequence),
+        taker_pays: Amount => amount(TakerPays),
+        taker_gets: Amount => amount(TakerGets),
+    }
+}
+
+impl<'a, G> OfferCreateBuilder<'a, (Missing, G)> {
+    required! {
+        /// Set the amount the taker pays
+        taker_pays: Amount => OfferCreateBuilder<'a, (Present, G)>,
+    }
+}
+
+impl<'a, P> OfferCreateBuilder<'a, (P, Missing)> {
+    required! {
+        /// Set the amount the taker gets
+        taker_gets: Amount => OfferCreateBuilder<'a, (P, Present)>,
+    }
+}
+
+impl<'a, S> OfferCreateBuilder<'a, S> {
+    optional! {
+        /// Set the time after which the offer is no longer active
+        expiration: u32,
+        /// Set the sequence of an offer to cancel first
+        offer_sequence: u32,
+    }
+}
+
+builder! {
+    /// Builder of an OfferCancel, requires `OfferSequence`
+    OfferCancelBuilder(OfferCancelFields): OfferCancel, Missing {
+        offer_sequence: u32 => u32(OfferSequence),
+    }
+}
+
+impl<'a> OfferCancelBuilder<'a, Missing> {
+    required! {
+        /// Set the sequence of the offer to cancel
+        offer_sequence: u32 => OfferCancelBuilder<'a, Present>,
+    }
+}
+
+builder! {
+    /// Builder of a TicketCreate, requires `TicketCount`
+    TicketCreateBuilder(TicketCreateFields): TicketCreate, Missing {
+        ticket_count: u32 => u32(TicketCount),
+    }
+}
+
+impl<'a> TicketCreateBuilder<'a, Missing> {
+    required! {
+        /// Set the number of tickets to create
+        ticket_count: u32 => TicketCreateBuilder<'a, Present>,
+    }
+}
+
+builder! {
+    /// Builder of a SignerListSet, requires `SignerQuorum`
+    SignerListSetBuilder(SignerListSetFields): SignerListSet, Missing {
+        signer_quorum: u32 => u32(SignerQuorum),
+        signer_entries: &'a [u8] => serialized(SignerEntries),
+    }
+}
+
+impl<'a> SignerListSetBuilder<'a, Missing> {
+    required! {
+        /// Set the target weight of signatures, 0 deletes the signer list
+        signer_quorum: u32 => SignerListSetBuilder<'a, Present>,
+    }
+}
+
+impl<'a, S> SignerListSetBuilder<'a, S> {
+    optional! {
+        /// Set the `SignerEntries` array, serialized with its header
+        signer_entries: &'a [u8],
+    }
+}
+
+builder! {
+    /// Builder of an EscrowCreate, requires `Destination` and `Amount`
+    EscrowCreateBuilder(EscrowCreateFields): EscrowCreate, (Missing, Missing) {
+        destination_tag: u32 => u32(DestinationTag),
+        cancel_after: u32 => u32(CancelAfter),
+        finish_after: u32 => u32(FinishAfter),
+        amount: Amount => amount(Amount),
+        condition: &'a [u8] => blob(Condition),
+        destination: &'a AccountId => account(Destination),
+    }
+}
+
+impl<'a, A> EscrowCreateBuilder<'a, (Missing, A)> {
+    required! {
+        /// Set the account receiving the escrowed amount
+        destination: &'a AccountId => EscrowCreateBuilder<'a, (Present, A)>,
+    }
+}
+
+impl<'a, D> EscrowCreateBuilder<'a, (D, Missing)> {
+    required! {
+        /// Set the escrowed amount
+        amount: Amount => EscrowCreateBuilder<'a, (D, Present)>,
+    }
+}
+
+impl<'a, S> EscrowCreateBuilder<'a, S> {
+    optional! {
+        /// Set the destination tag
+        destination_tag: u32,
+        /// Set the time after which the escrow can be cancelled
+        cancel_after: u32,
+        /// Set the time after which the escrow can be finished
+        finish_after: u32,
+        /// Set the crypto-condition of the escrow
+        condition: &'a [u8],
+    }
+}
+
+builder! {
+    /// Builder of an EscrowFinish, requires `Owner` and `OfferSequence`
+    EscrowFinishBuilder(EscrowFinishFields): EscrowFinish, (Missing, Missing) {
+        offer_sequence: u32 => u32(OfferSequence),
+        fulfillment: &'a [u8] => blob(Fulfillment),
+        condition: &'a [u8] => blob(Condition),
+        owner: &'a AccountId => account(Owner),
+    }
+}
+
+impl<'a, Q> EscrowFinishBuilder<'a, (Missing, Q)> {
+    required! {
+        /// Set the account that created the escrow
+        owner: &'a AccountId => EscrowFinishBuilder<'a, (Present, Q)>,
+    }
+}
+
+impl<'a, O> EscrowFinishBuilder<'a, (O, Missing)> {
+    required! {
+        /// Set the sequence of the EscrowCreate transaction
+        offer_sequence: u32 => EscrowFinishBuilder<'a, (O, Present)>,
+    }
+}
+
+impl<'a, S> EscrowFinishBuilder<'a, S> {
+    optional! {
+        /// Set the crypto-condition of the escrow
+        condition: &'a [u8],
+        /// Set the fulfillment of the crypto-condition
+        fulfillment: &'a [u8],
+    }
+}
+
+builder! {
+    /// Builder of an EscrowCancel, requires `Owner` and `OfferSequence`
+    EscrowCancelBuilder(EscrowCancelFields): EscrowCancel, (Missing, Missing) {
+        offer_sequence: u32 => u32(OfferSequence),
+        owner: &'a AccountId => account(Owner),
+    }
+}
+
+impl<'a, Q> EscrowCancelBuilder<'a, (Missing, Q)> {
+    required! {
+        /// Set the account that created the escrow
+        owner: &'a AccountId => EscrowCancelBuilder<'a, (Present, Q)>,
+    }
+}
+
+impl<'a, O> EscrowCancelBuilder<'a, (O, Missing)> {
+    required! {
+        /// Set the sequence of the EscrowCreate transaction
+        offer_sequence: u32 => EscrowCancelBuilder<'a, (O, Present)>,
+    }
+}
+
+builder! {
+    /// Builder of a PaymentChannelCreate, requires `Destination`, `Amount`,
+    /// `SettleDelay` and `PublicKey`
+    PaychanCreateBuilder(PaychanCreateFields): PaychanCreate, (Missing, Missing, Missing, Missing) {
+        destination_tag: u32 => u32(DestinationTag),
+        cancel_after: u32 => u32(CancelAfter),
+        settle_delay: u32 => u32(SettleDelay),
+        amount: Amount => amount(Amount),
+        public_key: &'a [u8] => blob(PublicKey),
+        destination: &'a AccountId => account(Destination),
+    }
+}
+
+impl<'a, A, D, K> PaychanCreateBuilder<'a, (Missing, A, D, K)> {
+    required! {
+        /// Set the account receiving claims of the channel
+        destination: &'a AccountId => PaychanCreateBuilder<'a, (Present, A, D, K)>,
+    }
+}
+
+impl<'a, R, D, K> PaychanCreateBuilder<'a, (R, Missing, D, K)> {
+    required! {
+        /// Set the amount set aside in the channel
+        amount: Amount => PaychanCreateBuilder<'a, (R, Present, D, K)>,
+    }
+}
+
+impl<'a, R, A, K> PaychanCreateBuilder<'a, (R, A, Missing, K)> {
+    required! {
+        /// Set the seconds the source waits to close a channel with unclaimed funds
+        settle_delay: u32 => PaychanCreateBuilder<'a, (R, A, Present, K)>,
+    }
+}
+
+impl<'a, R, A, D> PaychanCreateBuilder<'a, (R, A, D, Missing)> {
+    required! {
+        /// Set the public key signing claims against the channel
+        public_key: &'a [u8] => PaychanCreateBuilder<'a, (R, A, D, Present)>,
+    }
+}
+
+impl<'a, S> PaychanCreateBuilder<'a, S> {
+    optional! {
+        /// Set the destination tag
+        destination_tag: u32,
+        /// Set the time after which the channel expires
+        cancel_after: u32,
+    }
+}
+
+builder! {
+    /// Builder of a PaymentChannelFund, requires `Channel` and `Amount`
+    PaychanFundBuilder(PaychanFundFields): PaychanFund, (Missing, Missing) {
+        expiration: u32 => u32(Expiration),
+        channel: &'a Hash => hash256(Channel),
+        amount: Amount => amount(Amount),
+    }
+}
+
+impl<'a, A> PaychanFundBuilder<'a, (Missing, A)> {
+    required! {
+        /// Set the ID of the channel
+        channel: &'a Hash => PaychanFundBuilder<'a, (Present, A)>,
+    }
+}
+
+impl<'a, C> PaychanFundBuilder<'a, (C, Missing)> {
+    required! {
+        /// Set the amount added to the channel
+        amount: Amount => PaychanFundBuilder<'a, (C, Present)>,
+    }
+}
+
+impl<'a, S> PaychanFundBuilder<'a, S> {
+    optional! {
+        /// Set the new expiration of the channel
+        expiration: u32,
+    }
+}
+
+builder! {
+    /// Builder of a PaymentChannelClaim, requires `Channel`
+    PaychanClaimBuilder(PaychanClaimFields): PaychanClaim, Missing {
+        channel: &'a Hash => hash256(Channel),
+        amount: Amount => amount(Amount),
+        balance: Amount => amount(Balance),
+        public_key: &'a [u8] => blob(PublicKey),
+        signature: &'a [u8] => blob(Signature),
+    }
+}
+
+impl<'a> PaychanClaimBuilder<'a, Missing> {
+    required! {
+        /// Set the ID of the channel
+        channel: &'a Hash => PaychanClaimBuilder<'a, Present>,
+    }
+}
+
+impl<'a, S> PaychanClaimBuilder<'a, S> {
+    optional! {
+        /// Set the total amount delivered by the channel after this claim
+        balance: Amount,
+        /// Set the amount authorized by the signature
+        amount: Amount,
+        /// Set the signature of the claim
+        signature: &'a [u8],
+        /// Set the public key of the channel
+        public_key: &'a [u8],
+    }
+}
+
+builder! {
+    /// Builder of a CheckCreate, requires `Destination` and `SendMax`
+    CheckCreateBuilder(CheckCreateFields): CheckCreate, (Missing, Missing) {
+        expiration: u32 => u32(Expiration),
+        destination_tag: u32 => u32(DestinationTag),
+        invoice_id: &'a Hash => hash256(InvoiceID),
+        send_max: Amount => amount(SendMax),
+        destination: &'a AccountId => account(Destination),
+    }
+}
+
+impl<'a, M> CheckCreateBuilder<'a, (Missing, M)> {
+    required! {
+        /// Set the account that can cash the check
+        destination: &'a AccountId => CheckCreateBuilder<'a, (Present, M)>,
+    }
+}
+
+impl<'a, D> CheckCreateBuilder<'a, (D, Missing)> {
+    required! {
+        /// Set the most the check can debit, including transfer fees
+        send_max: Amount => CheckCreateBuilder<'a, (D, Present)>,
+    }
+}
+
+impl<'a, S> CheckCreateBuilder<'a, S> {
+    optional! {
+        /// Set the destination tag
+        destination_tag: u32,
+        /// Set the time after which the check is no longer valid
+        expiration: u32,
+        /// Set the invoice ID
+        invoice_id: &'a Hash,
+    }
+}
+
+builder! {
+    /// Builder of a CheckCash, requires `CheckID` and either `Amount` or `DeliverMin`
+    CheckCashBuilder(CheckCashFields): CheckCash, (Missing, Missing) {
+        check_id: &'a Hash => hash256(CheckID),
+        amount: Amount => amount(Amount),
+        deliver_min: Amount => amount(DeliverMin),
+    }
+}
+
+impl<'a, A> CheckCashBuilder<'a, (Missing, A)> {
+    required! {
+        /// Set the ID of the check
+        check_id: &'a Hash => CheckCashBuilder<'a, (Present, A)>,
+    }
+}
+
+impl<'a, C> CheckCashBuilder<'a, (C, Missing)> {
+    required! {
+        /// Set the exact amount to receive
+        amount: Amount => CheckCashBuilder<'a, (C, Present)>,
+        /// Set the least amount to receive, cashing as much as possible
+        deliver_min: Amount => CheckCashBuilder<'a, (C, Present)>,
+    }
+}
+
+builder! {
+    /// Builder of a CheckCancel, requires `CheckID`
+    CheckCancelBuilder(CheckCancelFields): CheckCancel, Missing {
+        check_id: &'a Hash => hash256(CheckID),
+    }
+}
+
+impl<'a> CheckCancelBuilder<'a, Missing> {
+    required! {
+        /// Set the ID of the check
+        check_id: &'a Hash => CheckCancelBuilder<'a, Present>,
+    }
+}
+
+builder! {
+    /// Builder of a DepositPreauth, requires either `Authorize` or `Unauthorize`
+    DepositPreauthBuilder(DepositPreauthFields): DepositPreauth, Missing {
+        authorize: &'a AccountId => account(Authorize),
+        unauthorize: &'a AccountId => account(Unauthorize),
+    }
+}
+
+impl<'a> DepositPreauthBuilder<'a, Missing> {
+    required! {
+        /// Set the account to preauthorize
+        authorize: &'a AccountId => DepositPreauthBuilder<'a, Present>,
+        /// Set the account whose preauthorization is revoked
+        unauthorize: &'a AccountId => DepositPreauthBuilder<'a, Present>,
+    }
+}
+
+builder! {
+    /// Builder of a TrustSet, requires `LimitAmount`
+    TrustSetBuilder(TrustSetFields): TrustSet, Missing {
+        quality_in: u32 => u32(QualityIn),
+        quality_out: u32 => u32(QualityOut),
+        limit_amount: Amount => amount(LimitAmount),
+    }
+}
+
+impl<'a> TrustSetBuilder<'a, Missing> {
+    required! {
+        /// Set the limit of the trust line, whose issuer is the counterparty
+        limit_amount: Amount => TrustSetBuilder<'a, Present>,
+    }
+}
+
+impl<'a, S> TrustSetBuilder<'a, S> {
+    optional! {
+        /// Set the value of incoming balances, in billionths
+        quality_in: u32,
+        /// Set the value of outgoing balances, in billionths
+        quality_out: u32,
+    }
+}
+
+builder! {
+    /// Builder of an AccountDelete, requires `Destination`
+    AccountDeleteBuilder(AccountDeleteFields): AccountDelete, Missing {
+        destination_tag: u32 => u32(DestinationTag),
+        destination: &'a AccountId => account(Destination),
+    }
+}
+
+impl<'a> AccountDeleteBuilder<'a, Missing> {
+    required! {
+        /// Set the account receiving the remaining balance
+        destination: &'a AccountId => AccountDeleteBuilder<'a, Present>,
+    }
+}
+
+impl<'a, S> AccountDeleteBuilder<'a, S> {
+    optional! {
+        /// Set the destination tag
+        destination_tag: u32,
+    }
+}
+
+builder! {
+    /// Builder of a SetHook, requires `Hooks`
+    HookSetBuilder(HookSetFields): HookSet, Missing {
+        hooks: &'a [u8] => serialized(Hooks),
+    }
+}
+
+impl<'a> HookSetBuilder<'a, Missing> {
+    required! {
+        /// Set the `Hooks` array, serialized with its header
+        hooks: &'a [u8] => HookSetBuilder<'a, Present>,
+    }
+}
+
+builder! {
+    /// Builder of a URITokenMint, requires `URI`
+    URITokenMintBuilder(URITokenMintFields): URITokenMint, Missing {
+        digest: &'a Hash => hash256(Digest),
+        amount: Amount => amount(Amount),
+        uri: &'a [u8] => blob(URI),
+        destination: &'a AccountId => account(Destination),
+    }
+}
+
+impl<'a> URITokenMintBuilder<'a, Missing> {
+    required! {
+        /// Set the URI of the token
+        uri: &'a [u8] => URITokenMintBuilder<'a, Present>,
+    }
+}
+
+impl<'a, S> URITokenMintBuilder<'a, S> {
+    optional! {
+        /// Set the digest of the content the URI points to
+        digest: &'a Hash,
+        /// Set the amount of a sell offer created with the token
+        amount: Amount,
+        /// Set the only account allowed to buy the token
+        destination: &'a AccountId,
+    }
+}
+
+builder! {
+    /// Builder of a URITokenBurn, requires `URITokenID`
+    URITokenBurnBuilder(URITokenBurnFields): URITokenBurn, Missing {
+        uri_token_id: &'a Hash => hash256(URITokenID),
+    }
+}
+
+impl<'a> URITokenBurnBuilder<'a, Missing> {
+    required! {
+        /// Set the ID of the token
+        uri_token_id: &'a Hash => URITokenBurnBuilder<'a, Present>,
+    }
+}
+
+builder! {
+    /// Builder of a URITokenBuy, requires `URITokenID` and `Amount`
+    URITokenBuyBuilder(URITokenBuyFields): URITokenBuy, (Missing, Missing) {
+        uri_token_id: &'a Hash => hash256(URITokenID),
+        amount: Amount => amount(Amount),
+    }
+}
+
+impl<'a, A> URITokenBuyBuilder<'a, (Missing, A)> {
+    required! {
+        /// Set the ID of the token
+        uri_token_id: &'a Hash => URITokenBuyBuilder<'a, (Present, A)>,
+    }
+}
+
+impl<'a, I> URITokenBuyBuilder<'a, (I, Missing)> {
+    required! {
+        /// Set the amount paid, matching the sell offer
+        amount: Amount => URITokenBuyBuilder<'a, (I, Present)>,
+    }
+}
+
+builder! {
+    /// Builder of a URITokenCreateSellOffer, requires `URITokenID` and `Amount`
+    URITokenCreateSellOfferBuilder(URITokenCreateSellOfferFields): URITokenCreateSellOffer, (Missing, Missing) {
+        uri_token_id: &'a Hash => hash256(URITokenID),
+        amount: Amount => amount(Amount),
+        destination: &'a AccountId => account(Destination),
+    }
+}
+
+impl<'a, A> URITokenCreateSellOfferBuilder<'a, (Missing, A)> {
+    required! {
+        /// Set the ID of the token
+        uri_token_id: &'a Hash => URITokenCreateSellOfferBuilder<'a, (Present, A)>,
+    }
+}
+
+impl<'a, I> URITokenCreateSellOfferBuilder<'a, (I, Missing)> {
+    required! {
+        /// Set the asked amount
+        amount: Amount => URITokenCreateSellOfferBuilder<'a, (I, Present)>,
+    }
+}
+
+impl<'a, S> URITokenCreateSellOfferBuilder<'a, S> {
+    optional! {
+        /// Set the only account allowed to buy the token
+        destination: &'a AccountId,
+    }
+}
+
+builder! {
+    /// Builder of a URITokenCancelSellOffer, requires `URITokenID`
+    URITokenCancelSellOfferBuilder(URITokenCancelSellOfferFields): URITokenCancelSellOffer, Missing {
+        uri_token_id: &'a Hash => hash256(URITokenID),
+    }
+}
+
+impl<'a> URITokenCancelSellOfferBuilder<'a, Missing> {
+    required! {
+        /// Set the ID of the token
+        uri_token_id: &'a Hash => URITokenCancelSellOfferBuilder<'a, Present>,
+    }
+}
+
+builder! {
+    /// Builder of a Remit, requires `Destination`
+    RemitBuilder(RemitFields): Remit, Missing {
+        destination_tag: u32 => u32(DestinationTag),
+        invoice_id: &'a Hash => hash256(InvoiceID),
+        blob: &'a [u8] => blob(Blob),
+        destination: &'a AccountId => account(Destination),
+        inform: &'a AccountId => account(Inform),
+        mint_uri_token: &'a [u8] => serialized(MintURIToken),
+        amounts: &'a [u8] => serialized(Amounts),
+        uri_token_ids: &'a [u8] => serialized(URITokenIDs),
+    }
+}
+
+impl<'a> RemitBuilder<'a, Missing> {
+    required! {
+        /// Set the receiving account
+        destination: &'a AccountId => RemitBuilder<'a, Present>,
+    }
+}
+
+impl<'a, S> RemitBuilder<'a, S> {
+    optional! {
+        /// Set the destination tag
+        destination_tag: u32,
+        /// Set the invoice ID
+        invoice_id: &'a Hash,
+        /// Set the blob passed to the hooks of the destination
+        blob: &'a [u8],
+        /// Set an account whose hooks are informed of the remittance
+        inform: &'a AccountId,
+        /// Set the `MintURIToken` object, serialized with its header
+        mint_uri_token: &'a [u8],
+        /// Set the `Amounts` array, serialized with its header
+        amounts: &'a [u8],
+        /// Set the `URITokenIDs` vector, serialized with its header
+        uri_token_ids: &'a [u8],
+    }
+}
+
+builder! {
+    /// Builder of a GenesisMint, which only the hook of the genesis account can emit,
+    /// requires `GenesisMints`
+    GenesisMintBuilder(GenesisMintFields): GenesisMint, Missing {
+        genesis_mints: &'a [u8] => serialized(GenesisMints),
+    }
+}
+
+impl<'a> GenesisMintBuilder<'a, Missing> {
+    required! {
+        /// Set the `GenesisMints` array, serialized with its header
+        genesis_mints: &'a [u8] => GenesisMintBuilder<'a, Present>,
+    }
+}
+
+builder! {
+    /// Builder of a ClaimReward, opting in to rewards with the issuer set,
+    /// or out with flag 1
+    ClaimRewardBuilder(ClaimRewardFields): ClaimReward, () {
+        issuer: &'a AccountId => account(Issuer),
+    }
+}
+
+impl<'a> ClaimRewardBuilder<'a, ()> {
+    optional! {
+        /// Set the issuer of the rewards, the genesis account
+        issuer: &'a AccountId,
+    }
+}
+
+builder! {
+    /// Builder of an Invoke
+    InvokeBuilder(InvokeFields): Invoke, () {
+        invoice_id: &'a Hash => hash256(InvoiceID),
+        blob: &'a [u8] => blob(Blob),
+        destination: &'a AccountId => account(Destination),
+    }
+}
+
+impl<'a> InvokeBuilder<'a, ()> {
+    optional! {
+        /// Set the account whose hooks are invoked
+        destination: &'a AccountId,
+        /// Set the invoice ID
+        invoice_id: &'a Hash,
+        /// Set the blob passed to the invoked hooks
+        blob: &'a [u8],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{txn_field, MockLedger};
+
+    const HOOK: AccountId = [0xA1; 20];
+    const BOB: AccountId = [0xB0; 20];
+
+    fn ledger() -> MockLedger {
+        let mut ledger = MockLedger::new();
+        ledger.hook_account = HOOK;
+        ledger.ledger_seq = 100;
+        ledger.fee_base = 12;
+        ledger
+    }
+
+    fn emitted<F: TxnFields, S: Complete>(builder: TxnBuilder<'_, F, S>) -> std::vec::Vec<u8> {
+        let mut ledger = ledger();
+
+        let outcome = ledger.run(|| {
+            assert!(matches!(etxn_reserve(1), Ok(1)));
+            let mut buf = [0; 4096];
+            let txn = match builder.build(&mut buf) {
+                Ok(txn) => txn,
+                Err(e) => panic!("{}", e.code()),
+            };
+            let mut hash = [0; 32];
+            assert!(matches!(emit(&mut hash, txn), Ok(32)));
+
+            accept(b"", 0)
+        });
+
+        assert!(outcome.is_accept());
+        ledger.emitted.remove(0)
+    }
+
+    /// Asserts the type and the content of the fields of an emitted transaction
+    fn assert_fields(txn: &[u8], txn_type: TxnType, fields: &[(FieldId, &[u8])]) {
+        assert_eq!(
+            txn_field(txn, FieldId::TransactionType),
+            Some(&(txn_type as u16).to_be_bytes()[..])
+        );
+        for &(field, value) in fields {
+            assert_eq!(txn_field(txn, field), Some(value), "{}", field.name());
+        }
+    }
+
+    fn serialized(field: FieldId, value: &[u8]) -> std::vec::Vec<u8> {
+        let mut header = [0; 3];
+        let len = crate::sto::encode_header(field as u32, &mut header);
+        let mut serialized = header[..len].to_vec();
+        serialized.extend_from_slice(value);
+        serialized
+    }
+
+    fn drops(drops: u64) -> [u8; 8] {
+        (drops | 1 << 62).to_be_bytes()
+    }
+
+    #[test]
+    fn payment_builder_test() {
+        let txn = emitted(PaymentBuilder::drops(&BOB, 1_000).destination_tag(42));
+
+        let field = |field| txn_field(&txn, field).unwrap();
+        assert_eq!(field(FieldId::TransactionType), [0, 0]);
+        assert_eq!(field(FieldId::Flags), TF_CANONICAL.to_be_bytes());
+        assert_eq!(field(FieldId::Sequence), [0; 4]);
+        assert_eq!(field(FieldId::DestinationTag), 42u32.to_be_bytes());
+        assert_eq!(field(FieldId::FirstLedgerSequence), 101u32.to_be_bytes());
+        assert_eq!(field(FieldId::LastLedgerSequence), 105u32.to_be_bytes());
+        assert_eq!(field(FieldId::Amount), (1_000u64 | 1 << 62).to_be_bytes());
+        assert_eq!(field(FieldId::Fee), (12u64 | 1 << 62).to_be_bytes());
+        assert_eq!(field(FieldId::SigningPubKey), []);
+        assert_eq!(field(FieldId::Account), HOOK);
+        assert_eq!(field(FieldId::Destination), BOB);
+        assert!(txn_field(&txn, FieldId::EmitDetails).is_some());
+        assert!(txn_field(&txn, FieldId::SourceTag).is_none());
+    }
+
+    #[test]
+    fn payment_builder_max_len_test() {
+        let mut ledger = ledger();
+
+        ledger.run(|| {
+            assert!(matches!(etxn_reserve(1), Ok(1)));
+            let amount = match float_set(-2, 12345) {
+                Ok(amount) => amount,
+                Err(e) => panic!("{}", e.code()),
+            };
+            let payment = PaymentBuilder::iou(&BOB, amount, &[0xC0; 20], &HOOK)
+                .source_tag(1)
+                .destination_tag(2);
+
+            let mut buf = [0; PaymentBuilder::MAX_LEN];
+            assert!(
+                matches!(payment.build(&mut buf), Ok(txn) if txn.len() == PaymentBuilder::MAX_LEN)
+            );
+            let mut buf = [0; PaymentBuilder::MAX_LEN - 1];
+            assert!(matches!(payment.build(&mut buf), Err(Error::TooSmall)));
+
+            accept(b"", 0)
+        });
+    }
+
+    #[test]
+    fn account_set_builder_test() {
+        let txn = emitted(
+            AccountSetBuilder::new()
+                .set_flag(8)
+                .clear_flag(9)
+                .transfer_rate(1_002_000_000)
+                .email_hash(&[0xE0; 16])
+                .message_key(&[0x02; 33])
+                .domain(b"example.com")
+                .tick_size(5),
+        );
+        assert_fields(
+            &txn,
+            TxnType::AccountSet,
+            &[
+                (FieldId::SetFlag, &8u32.to_be_bytes()),
+                (FieldId::ClearFlag, &9u32.to_be_bytes()),
+                (FieldId::TransferRate, &1_002_000_000u32.to_be_bytes()),
+                (FieldId::EmailHash, &[0xE0; 16]),
+                (FieldId::MessageKey, &[0x02; 33]),
+                (FieldId::Domain, b"example.com"),
+                (FieldId::TickSize, &[5]),
+            ],
+        );
+    }
+
+    #[test]
+    fn regular_key_set_builder_test() {
+        let txn = emitted(RegularKeySetBuilder::new().regular_key(&BOB));
+        assert_fields(&txn, TxnType::RegularKeySet, &[(FieldId::RegularKey, &BOB)]);
+
+        let txn = emitted(RegularKeySetBuilder::new());
+        assert_fields(&txn, TxnType::RegularKeySet, &[]);
+        assert!(txn_field(&txn, FieldId::RegularKey).is_none());
+    }
+
+    #[test]
+    fn offer_create_builder_test() {
+        let txn = emitted(
+            OfferCreateBuilder::new()
+                .taker_gets(Amount::Xrp(20))
+                .taker_pays(Amount::Xrp(10))
+                .expiration(1)
+                .offer_sequence(2),
+        );
+        assert_fields(
+            &txn,
+            TxnType::OfferCreate,
+            &[
+                (FieldId::TakerPays, &drops(10)),
+                (FieldId::TakerGets, &drops(20)),
+                (FieldId::Expiration, &1u32.to_be_bytes()),
+                (FieldId::OfferSequence, &2u32.to_be_bytes()),
+            ],
+        );
+    }
+
+    #[test]
+    fn offer_cancel_builder_test() {
+        let txn = emitted(OfferCancelBuilder::new().offer_sequence(3));
+        assert_fields(
+            &txn,
+            TxnType::OfferCancel,
+            &[(FieldId::OfferSequence, &3u32.to_be_bytes())],
+        );
+    }
+
+    #[test]
+    fn ticket_create_builder_test() {
+        let txn = emitted(TicketCreateBuilder::new().ticket_count(10));
+        assert_fields(
+            &txn,
+            TxnType::TicketCreate,
+            &[(FieldId::TicketCount, &10u32.to_be_bytes())],
+        );
+    }
+
+    #[test]
+    fn signer_list_set_builder_test() {
+        let entries = serialized(FieldId::SignerEntries, &[0xF1]);
+        let txn = emitted(
+            SignerListSetBuilder::new()
+                .signer_entries(&entries)
+                .signer_quorum(2),
+        );
+        assert_fields(
+            &txn,
+            TxnType::SignerListSet,
+            &[
+                (FieldId::SignerQuorum, &2u32.to_be_bytes()),
+                (FieldId::SignerEntries, &[]),
+            ],
+        );
+    }
+
+    #[test]
+    fn escrow_create_builder_test() {
+        let txn = emitted(
+            EscrowCreateBuilder::new()
+                .amount(Amount::Xrp(1_000))
+                .destination(&BOB)
+                .destination_tag(1)
+                .cancel_after(3)
+                .finish_after(2)
+                .condition(&[0xA0; 39]),
+        );
+        assert_fields(
+            &txn,
+            TxnType::EscrowCreate,
+            &[
+                (FieldId::Destination, &BOB),
+                (FieldId::Amount, &drops(1_000)),
+                (FieldId::DestinationTag, &1u32.to_be_bytes()),
+                (FieldId::CancelAfter, &3u32.to_be_bytes()),
+                (FieldId::FinishAfter, &2u32.to_be_bytes()),
+                (FieldId::Condition, &[0xA0; 39]),
+            ],
+        );
+    }
+
+    #[test]
+    fn escrow_finish_builder_test() {
+        let txn = emitted(
+            EscrowFinishBuilder::new()
+                .offer_sequence(7)
+                .owner(&BOB)
+                .condition(&[0xA0; 39])
+                .fulfillment(&[0xA0; 36]),
+        );
+        assert_fields(
+            &txn,
+            TxnType::EscrowFinish,
+            &[
+                (FieldId::Owner, &BOB),
+                (FieldId::OfferSequence, &7u32.to_be_bytes()),
+                (FieldId::Condition, &[0xA0; 39]),
+                (FieldId::Fulfillment, &[0xA0; 36]),
+            ],
+        );
+    }
+
+    #[test]
+    fn escrow_cancel_builder_test() {
+        let txn = emitted(EscrowCancelBuilder::new().owner(&BOB).offer_sequence(7));
+        assert_fields(
+            &txn,
+            TxnType::EscrowCancel,
+            &[
+                (FieldId::Owner, &BOB),
+                (FieldId::OfferSequence, &7u32.to_be_bytes()),
+            ],
+        );
+    }
+
+    #[test]
+    fn paychan_create_builder_test() {
+        let txn = emitted(
+            PaychanCreateBuilder::new()
+                .public_key(&[0xED; 33])
+                .settle_delay(86_400)
+                .amount(Amount::Xrp(1_000))
+                .destination(&BOB)
+                .destination_tag(1)
+                .cancel_after(2),
+        );
+        assert_fields(
+            &txn,
+            TxnType::PaychanCreate,
+            &[
+                (FieldId::Destination, &BOB),
+                (FieldId::Amount, &drops(1_000)),
+                (FieldId::SettleDelay, &86_400u32.to_be_bytes()),
+                (FieldId::PublicKey, &[0xED; 33]),
+                (FieldId::DestinationTag, &1u32.to_be_bytes()),
+                (FieldId::CancelAfter, &2u32.to_be_bytes()),
+            ],
+        );
+    }
+
+    #[test]
+    fn paychan_fund_builder_test() {
+        let txn = emitted(
+            PaychanFundBuilder::new()
+                .amount(Amount::Xrp(500))
+                .channel(&[0xCA; 32])
+                .expiration(4),
+        );
+        assert_fields(
+            &txn,
+            TxnType::PaychanFund,
+            &[
+                (FieldId::Channel, &[0xCA; 32]),
+                (FieldId::Amount, &drops(500)),
+                (FieldId::Expiration, &4u32.to_be_bytes()),
+            ],
+        );
+    }
+
+    #[test]
+    fn paychan_claim_builder_test() {
+        let txn = emitted(
+            PaychanClaimBuilder::new()
+                .channel(&[0xCA; 32])
+                .balance(Amount::Xrp(300))
+                .amount(Amount::Xrp(400))
+                .signature(&[0x30; 64])
+                .public_key(&[0xED; 33]),
+        );
+        assert_fields(
+            &txn,
+            TxnType::PaychanClaim,
+            &[
+                (FieldId::Channel, &[0xCA; 32]),
+                (FieldId::Balance, &drops(300)),
+                (FieldId::Amount, &drops(400)),
+                (FieldId::Signature, &[0x30; 64]),
+                (FieldId::PublicKey, &[0xED; 33]),
+            ],
+        );
+    }
+
+    #[test]
+    fn check_create_builder_test() {
+        let txn = emitted(
+            CheckCreateBuilder::new()
+                .send_max(Amount::Xrp(100))
+                .destination(&BOB)
+                .destination_tag(1)
+                .expiration(2)
+                .invoice_id(&[0x1D; 32]),
+        );
+        assert_fields(
+            &txn,
+            TxnType::CheckCreate,
+            &[
+                (FieldId::Destination, &BOB),
+                (FieldId::SendMax, &drops(100)),
+                (FieldId::DestinationTag, &1u32.to_be_bytes()),
+                (FieldId::Expiration, &2u32.to_be_bytes()),
+                (FieldId::InvoiceID, &[0x1D; 32]),
+            ],
+        );
+    }
+
+    #[test]
+    fn check_cash_builder_test() {
+        let txn = emitted(
+            CheckCashBuilder::new()
+                .deliver_min(Amount::Xrp(5))
+                .check_id(&[0xCC; 32])
+                .source_tag(7),
+        );
+        assert_fields(
+            &txn,
+            TxnType::CheckCash,
+            &[
+                (FieldId::CheckID, &[0xCC; 32]),
+                (FieldId::DeliverMin, &drops(5)),
+                (FieldId::SourceTag, &7u32.to_be_bytes()),
+            ],
+        );
+        assert!(txn_field(&txn, FieldId::Amount).is_none());
+
+        let txn = emitted(
+            CheckCashBuilder::new()
+                .check_id(&[0xCC; 32])
+                .amount(Amount::Xrp(5)),
+        );
+        assert_fields(&txn, TxnType::CheckCash, &[(FieldId::Amount, &drops(5))]);
+        assert!(txn_field(&txn, FieldId::DeliverMin).is_none());
+    }
+
+    #[test]
+    fn check_cancel_builder_test() {
+        let txn = emitted(CheckCancelBuilder::new().check_id(&[0xCC; 32]));
+        assert_fields(
+            &txn,
+            TxnType::CheckCancel,
+            &[(FieldId::CheckID, &[0xCC; 32])],
+        );
+    }
+
+    #[test]
+    fn deposit_preauth_builder_test() {
+        let txn = emitted(DepositPreauthBuilder::new().authorize(&BOB));
+        assert_fields(&txn, TxnType::DepositPreauth, &[(FieldId::Authorize, &BOB)]);
+        assert!(txn_field(&txn, FieldId::Unauthorize).is_none());
+
+        let txn = emitted(DepositPreauthBuilder::new().unauthorize(&BOB));
+        assert_fields(
+            &txn,
+            TxnType::DepositPreauth,
+            &[(FieldId::Unauthorize, &BOB)],
+        );
+        assert!(txn_field(&txn, FieldId::Authorize).is_none());
+    }
+
+    #[test]
+    fn trust_set_builder_test() {
+        let txn = emitted(
+            TrustSetBuilder::new()
+                .limit_amount(Amount::Iou {
+                    value: XFL::from_raw(0),
+                    currency: Currency::from_code([0xC0; 20]),
+                    issuer: BOB,
+                })
+                .quality_in(1)
+                .quality_out(2)
+                .flags(0x0002_0000),
+        );
+        assert_fields(
+            &txn,
+            TxnType::TrustSet,
+            &[
+                (FieldId::Flags, &(TF_CANONICAL | 0x0002_0000).to_be_bytes()),
+                (FieldId::QualityIn, &1u32.to_be_bytes()),
+                (FieldId::QualityOut, &2u32.to_be_bytes()),
+            ],
+        );
+        assert_eq!(txn_field(&txn, FieldId::LimitAmount).unwrap().len(), 48);
+    }
+
+    #[test]
+    fn account_delete_builder_test() {
+        let txn = emitted(
+            AccountDeleteBuilder::new()
+                .destination(&BOB)
+                .destination_tag(1),
+        );
+        assert_fields(
+            &txn,
+            TxnType::AccountDelete,
+            &[
+                (FieldId::Destination, &BOB),
+                (FieldId::DestinationTag, &1u32.to_be_bytes()),
+            ],
+        );
+    }
+
+    #[test]
+    fn uri_token_mint_builder_test() {
+        let txn = emitted(
+            URITokenMintBuilder::new()
+                .uri(b"ipfs://token")
+                .digest(&[0xD1; 32])
+                .amount(Amount::Xrp(10))
+                .destination(&BOB),
+        );
+        assert_fields(
+            &txn,
+            TxnType::URITokenMint,
+            &[
+                (FieldId::URI, b"ipfs://token"),
+                (FieldId::Digest, &[0xD1; 32]),
+                (FieldId::Amount, &drops(10)),
+                (FieldId::Destination, &BOB),
+            ],
+        );
+    }
+
+    #[test]
+    fn uri_token_burn_builder_test() {
+        let txn = emitted(URITokenBurnBuilder::new().uri_token_id(&[0x70; 32]));
+        assert_fields(
+            &txn,
+            TxnType::URITokenBurn,
+            &[(FieldId::URITokenID, &[0x70; 32])],
+        );
+    }
+
+    #[test]
+    fn uri_token_buy_builder_test() {
+        let txn = emitted(
+            URITokenBuyBuilder::new()
+                .amount(Amount::Xrp(10))
+                .uri_token_id(&[0x70; 32]),
+        );
+        assert_fields(
+            &txn,
+            TxnType::URITokenBuy,
+            &[
+                (FieldId::URITokenID, &[0x70; 32]),
+                (FieldId::Amount, &drops(10)),
+            ],
+        );
+    }
+
+    #[test]
+    fn uri_token_create_sell_offer_builder_test() {
+        let txn = emitted(
+            URITokenCreateSellOfferBuilder::new()
+                .uri_token_id(&[0x70; 32])
+                .amount(Amount::Xrp(10))
+                .destination(&BOB),
+        );
+        assert_fields(
+            &txn,
+            TxnType::URITokenCreateSellOffer,
+            &[
+                (FieldId::URITokenID, &[0x70; 32]),
+                (FieldId::Amount, &drops(10)),
+                (FieldId::Destination, &BOB),
+            ],
+        );
+    }
+
+    #[test]
+    fn uri_token_cancel_sell_offer_builder_test() {
+        let txn = emitted(URITokenCancelSellOfferBuilder::new().uri_token_id(&[0x70; 32]));
+        assert_fields(
+            &txn,
+            TxnType::URITokenCancelSellOffer,
+            &[(FieldId::URITokenID, &[0x70; 32])],
+        );
+    }
+
+    #[test]
+    fn remit_builder_test() {
+        let object = serialized(FieldId::MintURIToken, &[0xE1]);
+        let amounts = serialized(FieldId::Amounts, &[0xF1]);
+        let mut ids = serialized(FieldId::URITokenIDs, &[32]);
+        ids.extend_from_slice(&[0x70; 32]);
+        let txn = emitted(
+            RemitBuilder::new()
+                .destination(&BOB)
+                .destination_tag(1)
+                .invoice_id(&[0x1D; 32])
+                .blob(b"data")
+                .inform(&[0x1F; 20])
+                .mint_uri_token(&object)
+                .amounts(&amounts)
+                .uri_token_ids(&ids),
+        );
+        assert_fields(
+            &txn,
+            TxnType::Remit,
+            &[
+                (FieldId::Destination, &BOB),
+                (FieldId::DestinationTag, &1u32.to_be_bytes()),
+                (FieldId::InvoiceID, &[0x1D; 32]),
+                (FieldId::Blob, b"data"),
+                (FieldId::Inform, &[0x1F; 20]),
+                (FieldId::MintURIToken, &[]),
+                (FieldId::Amounts, &[]),
+                (FieldId::URITokenIDs, &[0x70; 32]),
+            ],
+        );
+    }
+
+    #[test]
+    fn genesis_mint_builder_test() {
+        let mints = serialized(FieldId::GenesisMints, &[0xF1]);
+        let txn = emitted(GenesisMintBuilder::new().genesis_mints(&mints));
+        assert_fields(&txn, TxnType::GenesisMint, &[(FieldId::GenesisMints, &[])]);
+    }
+
+    #[test]
+    fn claim_reward_builder_test() {
+        let txn = emitted(ClaimRewardBuilder::new().issuer(&BOB));
+        assert_fields(&txn, TxnType::ClaimReward, &[(FieldId::Issuer, &BOB)]);
+
+        let txn = emitted(ClaimRewardBuilder::new().flags(1));
+        assert_fields(
+            &txn,
+            TxnType::ClaimReward,
+            &[(FieldId::Flags, &(TF_CANONICAL | 1).to_be_bytes())],
+        );
+        assert!(txn_field(&txn, FieldId::Issuer).is_none());
+    }
+
+    #[test]
+    fn invoke_builder_test() {
+        let txn = emitted(
+            InvokeBuilder::new()
+                .destination(&BOB)
+                .invoice_id(&[0x1D; 32])
+                .blob(b"data"),
+        );
+        assert_fields(
+            &txn,
+            TxnType::Invoke,
+            &[
+                (FieldId::Destination, &BOB),
+                (FieldId::InvoiceID, &[0x1D; 32]),
+                (FieldId::Blob, b"data"),
+            ],
+        );
+    }
+
+    #[test]
+    fn large_hook_set_test() {
+        let code = [0xC0; 3000];
+        let mut hooks = [0; 3100];
+        let mut writer = Writer::new(&mut hooks);
+        writer.array(FieldId::Hooks, |hooks| {
+            hooks.object(FieldId::Hook, |hook| {
+                hook.blob(FieldId::CreateCode, &code);
+            });
+        });
+        let hooks = match writer.finish() {
+            Ok(hooks) => hooks,
+            Err(e) => panic!("{}", e.code()),
+        };
+
+        // the hooks and the emit details of each build fit in the copy budget
+        let txn = emitted(HookSetBuilder::new().hooks(hooks));
+        assert_eq!(
+            txn_field(&txn, FieldId::TransactionType),
+            Some(&[0, 22][..])
+        );
+        // without the header and the end marker
+        assert_eq!(
+            txn_field(&txn, FieldId::Hooks),
+            Some(&hooks[1..hooks.len() - 1])
+        );
+    }
+
+    #[test]
+    fn canonical_order_test() {
+        use sealed::WriteFields;
+
+        let details = serialized(FieldId::EmitDetails, &[0xE1]);
+        let object = serialized(FieldId::MintURIToken, &[0xE1]);
+        let memos = serialized(FieldId::Memos, &[0xF1]);
+        let amounts = serialized(FieldId::Amounts, &[0xF1]);
+        let mut ids = serialized(FieldId::URITokenIDs, &[32]);
+        ids.extend_from_slice(&[1; 32]);
+
+        // the fields of a Remit fall before and after most of the common fields
+        let builder = RemitBuilder::new()
+            .destination(&BOB)
+            .destination_tag(1)
+            .invoice_id(&[1; 32])
+            .blob(&[1])
+            .inform(&BOB)
+            .mint_uri_token(&object)
+            .amounts(&amounts)
+            .uri_token_ids(&ids)
+            .source_tag(1)
+            .memos(&memos);
+        let header = Header {
+            txn_type: TxnType::Remit as u16,
+            flags: 0,
+            source_tag: builder.source_tag,
+            ledger_seq: 1,
+            account: &HOOK,
+            details: &details,
+            memos: builder.memos,
+        };
+
+        let mut buf = [0; 1024];
+        let mut writer = Writer::new(&mut buf);
+        let mut txn = TxnWriter::new(&mut writer, header);
+        builder.fields.write(&mut txn);
+        let fee_pos = txn.finish();
+        let txn = match writer.finish() {
+            Ok(txn) => txn,
+            Err(e) => panic!("{}", e.code()),
+        };
+
+        assert_eq!(
+            txn_field(txn, FieldId::Fee),
+            Some(&txn[fee_pos..fee_pos + 8])
+        );
+        assert_eq!(txn_field(txn, FieldId::SourceTag), Some(&[0, 0, 0, 1][..]));
+        assert_eq!(txn_field(txn, FieldId::Memos), Some(&[][..]));
+        assert_eq!(txn_field(txn, FieldId::URITokenIDs), Some(&[1; 32][..]));
+    }
+}
@@ -1,9 +1,390 @@
 use super::*;
+use crate::xfl;
+use core::cmp::Ordering;
 
 /// XFL floating point numbers
 #[derive(Clone, Copy)]
 pub struct XFL(pub(super) i64 /* enclosing number */);
 
+impl XFL {
+    /// Create an XFL from its enclosing number
+    ///
+    /// The enclosing number is not validated, invalid numbers are rejected by the XFL API.
+    #[inline(always)]
+    pub const fn from_raw(enclosing_number: i64) -> Self {
+        XFL(enclosing_number)
+    }
+
+    /// Get the enclosing number of an XFL
+    #[inline(always)]
+    pub const fn to_raw(self) -> i64 {
+        self.0
+    }
+
+    /// Returns `true` if the number is zero
+    #[inline(always)]
+    pub const fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns `true` if the number is negative
+    #[inline(always)]
+    pub const fn is_negative(self) -> bool {
+        self.0 > 0 && (self.0 >> 62) & 1 == 0
+    }
+
+    /// Multiply two XFL numbers together
+    #[inline(always)]
+    pub fn checked_mul(self, other: XFL) -> Result<XFL> {
+        float_multiply(self, other)
+    }
+
+    /// Divide an XFL by another XFL
+    #[inline(always)]
+    pub fn checked_div(self, other: XFL) -> Result<XFL> {
+        float_divide(self, other)
+    }
+
+    /// Add two XFL numbers together
+    #[inline(always)]
+    pub fn checked_add(self, other: XFL) -> Result<XFL> {
+        float_sum(self, other)
+    }
+
+    /// Subtract an XFL from another XFL
+    #[inline(always)]
+    pub fn checked_sub(self, other: XFL) -> Result<XFL> {
+        match float_negate(other) {
+            Ok(other) => float_sum(self, other),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Negate an XFL
+    #[inline(always)]
+    pub fn checked_neg(self) -> Result<XFL> {
+        float_negate(self)
+    }
+
+    /// Divide one by an XFL
+    #[inline(always)]
+    pub fn checked_inv(self) -> Result<XFL> {
+        float_invert(self)
+    }
+
+    /// Compare two XFL numbers
+    #[inline(always)]
+    pub fn checked_cmp(self, other: XFL) -> Result<Ordering> {
+        match float_compare(self, other, XFLCompareMode::Less) {
+            Ok(true) => return Ok(Ordering::Less),
+            Ok(false) => {}
+            Err(e) => return Err(e),
+        }
+
+        match float_compare(self, other, XFLCompareMode::Equal) {
+            Ok(true) => Ok(Ordering::Equal),
+            Ok(false) => Ok(Ordering::Greater),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Arithmetic on XFL numbers
+///
+/// These methods rollback on error,
+/// use the `checked_` variants to handle errors explicitly.
+impl XFL {
+    /// Multiply two XFL numbers together
+    #[inline(always)]
+    pub fn mul_or_rollback(self, other: XFL) -> XFL {
+        self.checked_mul(other).expect(b"XFL mul error")
+    }
+
+    /// Divide an XFL by another XFL
+    #[inline(always)]
+    pub fn div_or_rollback(self, other: XFL) -> XFL {
+        self.checked_div(other).expect(b"XFL div error")
+    }
+
+    /// Add two XFL numbers together
+    #[inline(always)]
+    pub fn add_or_rollback(self, other: XFL) -> XFL {
+        self.checked_add(other).expect(b"XFL add error")
+    }
+
+    /// Subtract an XFL from another XFL
+    #[inline(always)]
+    pub fn sub_or_rollback(self, other: XFL) -> XFL {
+        self.checked_sub(other).expect(b"XFL sub error")
+    }
+
+    /// Negate an XFL
+    #[inline(always)]
+    pub fn neg_or_rollback(self) -> XFL {
+        self.checked_neg().expect(b"XFL neg error")
+    }
+
+    /// Divide one by an XFL
+    #[inline(always)]
+    pub fn inv_or_rollback(self) -> XFL {
+        self.checked_inv().expect(b"XFL inv error")
+    }
+
+    /// Compare two XFL numbers
+    #[inline(always)]
+    pub fn cmp_or_rollback(self, other: XFL) -> Ordering {
+        self.checked_cmp(other).expect(b"XFL cmp error")
+    }
+}
+
+/// Max length of a decimal string accepted by [XFL::parse_decimal]
+pub const XFL_DECIMAL_MAX_LEN: usize = 64;
+
+/// Decimal strings
+impl XFL {
+    /// Parse a decimal string such as `-123.456`
+    ///
+    /// At most 18 significant digits are used, further digits are truncated.
+    /// The guard budget of the parsing loop is shared by all calls during a hook execution.
+    /// Returns [Error::InvalidArgument] if the string is not a decimal number
+    /// and [Error::TooBig] if it is longer than [XFL_DECIMAL_MAX_LEN].
+    #[inline(always)]
+    pub fn parse_decimal(decimal: &[u8]) -> Result<XFL> {
+        let len = decimal.len();
+        if len > XFL_DECIMAL_MAX_LEN {
+            return Err(Error::TooBig);
+        }
+
+        let mut i = 0;
+        let mut negative = false;
+        if len > 0 && (decimal[0] == b'-' || decimal[0] == b'+') {
+            negative = decimal[0] == b'-';
+            i = 1;
+        }
+
+        let mut mantissa: i64 = 0;
+        let mut exponent: i32 = 0;
+        let mut digits = 0;
+        let mut significant = 0;
+        let mut point = false;
+
+        // The error is the only branch of the body: LLVM wraps a loop whose branches
+        // leave some of the state unchanged in unguarded loops, which hooks can't have.
+        while {
+            crate::guard!(XFL_DECIMAL_MAX_LEN);
+            i < len
+        } {
+            let c = decimal[i];
+            i += 1;
+
+            let is_point = c == b'.';
+            if (is_point && point) || (!is_point && !c.is_ascii_digit()) {
+                return Err(Error::InvalidArgument);
+            }
+
+            // leading zeros only move the decimal point, digits past 18 significant
+            // ones are dropped, and digits after the point lower the exponent
+            let digit = !is_point;
+            let leading = mantissa == 0 && c == b'0';
+            let kept = digit && !leading && significant < 18;
+            let dropped = digit && !leading && !kept;
+
+            mantissa = if kept {
+                mantissa * 10 + (c - b'0') as i64
+            } else {
+                mantissa
+            };
+            exponent += dropped as i32 - (digit && point) as i32;
+            significant += kept as i32;
+            digits += digit as i32;
+            point |= is_point;
+        }
+
+        if digits == 0 {
+            return Err(Error::InvalidArgument);
+        }
+
+        float_set(exponent, if negative { -mantissa } else { mantissa })
+    }
+
+    /// Write the number as a decimal string such as `-123.456`
+    ///
+    /// Writes the significant digits of the mantissa without trailing zeros
+    /// and without exponent notation. Returns the number of bytes written,
+    /// [Error::TooSmall] if the buffer is too small
+    /// or [Error::InvalidFloat] if the mantissa or the exponent is out of range.
+    /// The guard budget of the formatting loops is shared by all calls during a hook execution.
+    #[inline(always)]
+    pub fn write_decimal(self, buf: &mut [u8]) -> Result<usize> {
+        // the digit buffer and the guards rely on the ranges of a valid number
+        if !xfl::is_valid(self) {
+            return Err(Error::InvalidFloat);
+        }
+
+        let len = buf.len();
+
+        if self.0 == 0 {
+            if len < 1 {
+                return Err(Error::TooSmall);
+            }
+            buf[0] = b'0';
+            return Ok(1);
+        }
+
+        let mut mantissa = self.0 & xfl::MANTISSA_MASK;
+        let mut exponent = (((self.0 & xfl::EXPONENT_MASK) >> 54) as i32) - xfl::EXPONENT_BIAS;
+
+        while {
+            crate::guard!(15);
+            mantissa % 10 == 0
+        } {
+            mantissa /= 10;
+            exponent += 1;
+        }
+
+        let mut digits = [0u8; 16];
+        let mut count = 0;
+        while {
+            crate::guard!(16);
+            mantissa > 0
+        } {
+            digits[15 - count] = b'0' + (mantissa % 10) as u8;
+            mantissa /= 10;
+            count += 1;
+        }
+
+        // number of digits before the decimal point
+        let integer = count as i32 + exponent;
+        let zeros = if integer <= 0 {
+            2 - integer
+        } else if exponent > 0 {
+            exponent
+        } else {
+            0
+        } as usize;
+        let total =
+            self.is_negative() as usize + count + zeros + (exponent < 0 && integer > 0) as usize;
+
+        if len < total {
+            return Err(Error::TooSmall);
+        }
+
+        let mut pos = 0;
+        if self.is_negative() {
+            buf[pos] = b'-';
+            pos += 1;
+        }
+
+        // leading "0." and zeros for numbers below one
+        let mut i = 0;
+        while {
+            crate::guard!(82);
+            integer <= 0 && i < zeros
+        } {
+            buf[pos] = if i == 1 { b'.' } else { b'0' };
+            pos += 1;
+            i += 1;
+        }
+
+        let mut i = 0;
+        while {
+            crate::guard!(16);
+            i < count
+        } {
+            if integer > 0 && i as i32 == integer {
+                buf[pos] = b'.';
+                pos += 1;
+            }
+            buf[pos] = digits[16 - count + i];
+            pos += 1;
+            i += 1;
+        }
+
+        // trailing zeros for large integers
+        let mut i = 0;
+        while {
+            crate::guard!(95);
+            exponent > 0 && i < zeros
+        } {
+            buf[pos] = b'0';
+            pos += 1;
+            i += 1;
+        }
+
+        Ok(pos)
+    }
+}
+
+impl PartialEq for XFL {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        matches!(
+            float_compare(*self, *other, XFLCompareMode::Equal),
+            Ok(true)
+        )
+    }
+}
+
+impl PartialOrd for XFL {
+    #[inline(always)]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.checked_cmp(*other) {
+            Ok(ordering) => Some(ordering),
+            Err(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "ops")]
+mod ops {
+    use super::XFL;
+
+    impl core::ops::Add for XFL {
+        type Output = XFL;
+
+        #[inline(always)]
+        fn add(self, other: XFL) -> XFL {
+            XFL::add_or_rollback(self, other)
+        }
+    }
+
+    impl core::ops::Sub for XFL {
+        type Output = XFL;
+
+        #[inline(always)]
+        fn sub(self, other: XFL) -> XFL {
+            XFL::sub_or_rollback(self, other)
+        }
+    }
+
+    impl core::ops::Mul for XFL {
+        type Output = XFL;
+
+        #[inline(always)]
+        fn mul(self, other: XFL) -> XFL {
+            XFL::mul_or_rollback(self, other)
+        }
+    }
+
+    impl core::ops::Div for XFL {
+        type Output = XFL;
+
+        #[inline(always)]
+        fn div(self, other: XFL) -> XFL {
+            XFL::div_or_rollback(self, other)
+        }
+    }
+
+    impl core::ops::Neg for XFL {
+        type Output = XFL;
+
+        #[inline(always)]
+        fn neg(self) -> XFL {
+            XFL::neg_or_rollback(self)
+        }
+    }
+}
+
 /// Create a float from an exponent and mantissa
 #[inline(always)]
 pub fn float_set(exponent: i32, mantissa: i64) -> Result<XFL> {
@@ -133,8 +514,8 @@ pub fn float_divide(float1: XFL, float2: XFL) -> Result<XFL> {
 
 /// Return the number 1 represented in an XFL enclosing number
 #[inline(always)]
-pub fn float_one() -> XFL {
-    XFL(unsafe { _c::float_one() })
+pub const fn float_one() -> XFL {
+    xfl::ONE
 }
 
 /// Get the mantissa of an XFL enclosing number
@@ -143,6 +524,75 @@ pub fn float_mantissa(float: XFL) -> i64 {
     unsafe { _c::float_mantissa(float.0) }
 }
 
+/// Get the exponent of an XFL enclosing number
+#[inline(always)]
+pub const fn float_exponent(float: XFL) -> Result<i32> {
+    if float.0 < 0 {
+        return Err(Error::InvalidFloat);
+    }
+
+    if float.0 == 0 {
+        return Ok(0);
+    }
+
+    Ok((((float.0 & xfl::EXPONENT_MASK) >> 54) as i32) - xfl::EXPONENT_BIAS)
+}
+
+/// Replace the mantissa of an XFL enclosing number
+///
+/// The mantissa must be 16 decimal digits, a zero mantissa results in zero.
+/// Zero has no sign nor exponent to keep, so it only takes a zero mantissa.
+#[inline(always)]
+pub const fn float_mantissa_set(float: XFL, mantissa: i64) -> Result<XFL> {
+    if float.0 < 0 {
+        return Err(Error::InvalidFloat);
+    }
+
+    if mantissa == 0 {
+        return Ok(XFL(0));
+    }
+
+    if float.0 == 0 {
+        return Err(Error::InvalidFloat);
+    }
+
+    if mantissa > xfl::MAX_MANTISSA {
+        return Err(Error::MantissaOversized);
+    }
+
+    if mantissa < xfl::MIN_MANTISSA {
+        return Err(Error::MantissaUndersized);
+    }
+
+    Ok(XFL((float.0 & !xfl::MANTISSA_MASK) | mantissa))
+}
+
+/// Replace the exponent of an XFL enclosing number
+///
+/// The exponent must be in the range -96 to 80, zero stays zero.
+#[inline(always)]
+pub const fn float_exponent_set(float: XFL, exponent: i32) -> Result<XFL> {
+    if float.0 < 0 {
+        return Err(Error::InvalidFloat);
+    }
+
+    if float.0 == 0 {
+        return Ok(XFL(0));
+    }
+
+    if exponent > xfl::MAX_EXPONENT {
+        return Err(Error::ExponentOversized);
+    }
+
+    if exponent < xfl::MIN_EXPONENT {
+        return Err(Error::ExponentUndersized);
+    }
+
+    Ok(XFL(
+        (float.0 & !xfl::EXPONENT_MASK) | (((exponent + xfl::EXPONENT_BIAS) as i64) << 54)
+    ))
+}
+
 /// Get the sign of an XFL enclosing number
 #[inline(always)]
 pub fn float_sign(float: XFL) -> Result<bool> {
@@ -160,3 +610,306 @@ pub fn float_int(float: XFL, decimal_places: u32, absolute: bool) -> Result<i64>
 
     result_i64(res)
 }
+
+/// Compute the decimal log of an XFL floating point number
+///
+/// Returns [Error::ComplexNotSupported] for negative numbers.
+#[inline(always)]
+pub fn float_log(float: XFL) -> Result<XFL> {
+    let res = unsafe { _c::float_log(float.0) };
+
+    result_xfl(res)
+}
+
+/// Compute the nth root of an XFL floating point number
+///
+/// Returns [Error::ComplexNotSupported] for negative numbers.
+#[inline(always)]
+pub fn float_root(float: XFL, n: u32) -> Result<XFL> {
+    let res = unsafe { _c::float_root(float.0, n) };
+
+    result_xfl(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ONE: XFL = XFL::from_raw(6089866696204910592);
+
+    fn float(exponent: i32, mantissa: i64) -> XFL {
+        match xfl::float_set(exponent, mantissa) {
+            Ok(float) => float,
+            Err(e) => panic!("{}", e.code()),
+        }
+    }
+
+    #[test]
+    fn checked_arithmetic_test() {
+        let (two, three, six, half) = (float(0, 2), float(0, 3), float(0, 6), float(-1, 5));
+
+        assert!(matches!(two.checked_add(ONE), Ok(sum) if sum == three));
+        assert!(matches!(three.checked_sub(ONE), Ok(difference) if difference == two));
+        assert!(matches!(two.checked_mul(three), Ok(product) if product == six));
+        assert!(matches!(six.checked_div(three), Ok(quotient) if quotient == two));
+        assert!(matches!(two.checked_inv(), Ok(inverse) if inverse == half));
+        assert!(matches!(two.checked_neg(), Ok(negative) if negative.is_negative()));
+        assert!(matches!(two.checked_cmp(three), Ok(Ordering::Less)));
+        assert!(matches!(two.checked_cmp(two), Ok(Ordering::Equal)));
+        assert!(matches!(three.checked_cmp(two), Ok(Ordering::Greater)));
+
+        assert!(matches!(
+            ONE.checked_div(XFL(0)),
+            Err(Error::DivisionByZero)
+        ));
+        assert!(matches!(XFL(0).checked_inv(), Err(Error::DivisionByZero)));
+        assert!(matches!(ONE.checked_add(XFL(-1)), Err(Error::InvalidFloat)));
+        assert!(ONE.partial_cmp(&XFL(-1)).is_none());
+    }
+
+    #[test]
+    fn rollback_arithmetic_test() {
+        let mut ledger = crate::testing::MockLedger::new();
+
+        let outcome = ledger.run(|| {
+            let (two, three, six) = (float(0, 2), float(0, 3), float(0, 6));
+            assert!(two.add_or_rollback(ONE) == three);
+            assert!(three.sub_or_rollback(ONE) == two);
+            assert!(two.mul_or_rollback(three) == six);
+            assert!(six.div_or_rollback(three) == two);
+            assert!(two.inv_or_rollback() == float(-1, 5));
+            assert!(two.neg_or_rollback() == float(0, -2));
+            assert!(two.cmp_or_rollback(three) == Ordering::Less);
+            accept(b"", 0)
+        });
+        assert!(outcome.is_accept());
+
+        let outcome = ledger.run(|| {
+            let _ = ONE.div_or_rollback(XFL(0));
+            accept(b"", 0)
+        });
+        assert!(outcome.is_rollback());
+        assert_eq!(outcome.message(), b"XFL div error");
+    }
+
+    #[cfg(feature = "ops")]
+    #[test]
+    fn ops_test() {
+        let mut ledger = crate::testing::MockLedger::new();
+
+        let outcome = ledger.run(|| {
+            let (two, three, six) = (float(0, 2), float(0, 3), float(0, 6));
+            assert!(two + ONE == three);
+            assert!(three - ONE == two);
+            assert!(two * three == six);
+            assert!(six / three == two);
+            assert!(-two == float(0, -2));
+            accept(b"", 0)
+        });
+        assert!(outcome.is_accept());
+
+        let outcome = ledger.run(|| {
+            let _ = ONE / XFL(0);
+            accept(b"", 0)
+        });
+        assert!(outcome.is_rollback());
+        assert_eq!(outcome.message(), b"XFL div error");
+    }
+
+    #[test]
+    fn float_exponent_test() {
+        assert!(matches!(float_exponent(ONE), Ok(-15)));
+        assert!(matches!(float_exponent(XFL(0)), Ok(0)));
+        assert!(float_exponent(XFL(-1)).is_err());
+    }
+
+    #[test]
+    fn float_mantissa_set_test() {
+        match float_mantissa_set(ONE, 2_000_000_000_000_000) {
+            Ok(two) => assert_eq!(two.to_raw() & xfl::MANTISSA_MASK, 2_000_000_000_000_000),
+            Err(_) => panic!(),
+        }
+        assert!(matches!(float_mantissa_set(ONE, 0), Ok(XFL(0))));
+        assert!(matches!(float_mantissa_set(XFL(0), 0), Ok(XFL(0))));
+        assert!(matches!(
+            float_mantissa_set(XFL(0), xfl::MIN_MANTISSA),
+            Err(Error::InvalidFloat)
+        ));
+        assert!(matches!(
+            float_mantissa_set(ONE, xfl::MAX_MANTISSA + 1),
+            Err(Error::MantissaOversized)
+        ));
+        assert!(matches!(
+            float_mantissa_set(ONE, xfl::MIN_MANTISSA - 1),
+            Err(Error::MantissaUndersized)
+        ));
+    }
+
+    #[test]
+    fn float_exponent_set_test() {
+        match float_exponent_set(ONE, -14) {
+            Ok(ten) => {
+                assert!(matches!(float_exponent(ten), Ok(-14)));
+                assert_eq!(ten.to_raw() & xfl::MANTISSA_MASK, 1_000_000_000_000_000);
+            }
+            Err(_) => panic!(),
+        }
+        assert!(matches!(
+            float_exponent_set(ONE, 81),
+            Err(Error::ExponentOversized)
+        ));
+        assert!(matches!(
+            float_exponent_set(ONE, -97),
+            Err(Error::ExponentUndersized)
+        ));
+    }
+
+    #[test]
+    fn float_log_root_test() {
+        let int = |float: Result<XFL>| match float {
+            Ok(float) => float_int(float, 0, false),
+            Err(e) => Err(e),
+        };
+        let (hundred, minus_one) = match (xfl::float_set(2, 1), xfl::float_set(0, -1)) {
+            (Ok(hundred), Ok(minus_one)) => (hundred, minus_one),
+            _ => panic!(),
+        };
+
+        assert!(matches!(int(float_log(hundred)), Ok(2)));
+        assert!(matches!(int(float_log(ONE)), Ok(0)));
+        assert!(matches!(
+            float_log(minus_one),
+            Err(Error::ComplexNotSupported)
+        ));
+        assert!(matches!(float_log(XFL(0)), Err(Error::InvalidArgument)));
+
+        assert!(matches!(int(float_root(hundred, 2)), Ok(10)));
+        assert!(matches!(int(float_root(ONE, 3)), Ok(1)));
+        assert!(matches!(
+            float_root(minus_one, 2),
+            Err(Error::ComplexNotSupported)
+        ));
+        assert!(matches!(
+            float_root(hundred, 0),
+            Err(Error::InvalidArgument)
+        ));
+    }
+
+    #[test]
+    fn parse_decimal_test() {
+        let parse = |decimal: &[u8]| match XFL::parse_decimal(decimal) {
+            Ok(float) => float,
+            Err(e) => panic!("{}: {}", core::str::from_utf8(decimal).unwrap(), e.code()),
+        };
+
+        assert!(parse(b"-123.456") == float(-3, -123_456));
+        assert!(parse(b"0.05") == float(-2, 5));
+        assert!(parse(b"+1") == ONE);
+        assert!(parse(b"1.") == ONE);
+        assert!(parse(b".5") == float(-1, 5));
+        assert!(parse(b"000") == XFL(0));
+        // digits past the 16th of the mantissa are truncated
+        assert!(parse(b"1234567890123456789") == float(3, 1_234_567_890_123_456));
+
+        for invalid in [&b""[..], b"-", b"+", b".", b"1.2.3", b"1e5", b"1 ", b"--1"] {
+            assert!(
+                matches!(XFL::parse_decimal(invalid), Err(Error::InvalidArgument)),
+                "{}",
+                core::str::from_utf8(invalid).unwrap()
+            );
+        }
+        assert!(matches!(
+            XFL::parse_decimal(&[b'1'; XFL_DECIMAL_MAX_LEN + 1]),
+            Err(Error::TooBig)
+        ));
+    }
+
+    #[test]
+    fn write_decimal_test() {
+        let write = |float: XFL| {
+            let mut buf = [0u8; 128];
+            match float.write_decimal(&mut buf) {
+                Ok(len) => std::string::String::from_utf8(buf[..len].to_vec()).unwrap(),
+                Err(e) => panic!("{}", e.code()),
+            }
+        };
+
+        assert_eq!(write(float(-3, -123_456)), "-123.456");
+        assert_eq!(write(float(-2, 5)), "0.05");
+        assert_eq!(write(ONE), "1");
+        assert_eq!(write(XFL(0)), "0");
+        assert_eq!(
+            write(float(3, 1_234_567_890_123_456)),
+            "1234567890123456000"
+        );
+
+        let mut buf = [0u8; 8];
+        assert!(matches!(
+            float(-3, -123_456).write_decimal(&mut buf[..7]),
+            Err(Error::TooSmall)
+        ));
+        assert!(matches!(float(-3, -123_456).write_decimal(&mut buf), Ok(8)));
+
+        // 17 digit mantissa and out of range exponent of unvalidated enclosing numbers
+        let mantissa = XFL::from_raw(ONE.to_raw() - xfl::MIN_MANTISSA + 10 * xfl::MIN_MANTISSA);
+        assert!(matches!(
+            mantissa.write_decimal(&mut buf),
+            Err(Error::InvalidFloat)
+        ));
+        let exponent = XFL::from_raw(ONE.to_raw() | xfl::EXPONENT_MASK);
+        assert!(matches!(
+            exponent.write_decimal(&mut buf),
+            Err(Error::InvalidFloat)
+        ));
+        assert!(matches!(
+            XFL(-1).write_decimal(&mut buf),
+            Err(Error::InvalidFloat)
+        ));
+    }
+
+    #[test]
+    fn decimal_guards_test() {
+        let mut ledger = crate::testing::MockLedger::new();
+        let extremes = [
+            (xfl::MIN_EXPONENT, xfl::MIN_MANTISSA),
+            (xfl::MIN_EXPONENT, -xfl::MAX_MANTISSA),
+            (xfl::MAX_EXPONENT, xfl::MIN_MANTISSA),
+            (xfl::MAX_EXPONENT, -xfl::MAX_MANTISSA),
+            (-15, xfl::MAX_MANTISSA),
+            (-1, xfl::MIN_MANTISSA + 1),
+        ];
+
+        for (exponent, mantissa) in extremes {
+            let outcome = ledger.run(|| {
+                let float = match xfl::float_set(exponent, mantissa) {
+                    Ok(float) => float,
+                    Err(e) => panic!("{}", e.code()),
+                };
+                let mut buf = [0; 128];
+                let len = match float.write_decimal(&mut buf) {
+                    Ok(len) => len,
+                    Err(e) => panic!("{}", e.code()),
+                };
+                if len <= XFL_DECIMAL_MAX_LEN {
+                    match XFL::parse_decimal(&buf[..len]) {
+                        Ok(parsed) => assert!(parsed == float),
+                        Err(e) => panic!("{}", e.code()),
+                    }
+                }
+                accept(b"", 0)
+            });
+            assert!(outcome.is_accept(), "{}", ledger.guard_report());
+        }
+
+        let outcome = ledger.run(|| {
+            assert!(XFL::parse_decimal(&[b'1'; XFL_DECIMAL_MAX_LEN]).is_ok());
+            accept(b"", 0)
+        });
+        assert!(outcome.is_accept(), "{}", ledger.guard_report());
+
+        // the extremes reach every bound
+        for guard in ledger.guards.values() {
+            assert_eq!(guard.max_calls, guard.maxiter, "{}", ledger.guard_report());
+        }
+    }
+}
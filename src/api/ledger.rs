@@ -9,11 +9,17 @@ pub fn hook_account(accid: &mut [u8]) -> Result<i64> {
 /// Retreive the 32 byte namespace biased SHA512H of the currently executing Hook
 #[inline(always)]
 pub fn hook_hash(hash: &mut [u8], hook_no: i32) -> Result<i64> {
-    let res = unsafe { _c::hook_hash(hash.as_ptr() as u32, hash.len() as u32, hook_no as i32) };
+    let res = unsafe { _c::hook_hash(hash.as_ptr() as _, hash.len() as u32, hook_no) };
 
     result_i64(res)
 }
 
+/// Get the position of the currently executing hook in the hook chain
+#[inline(always)]
+pub fn hook_pos() -> i64 {
+    unsafe { _c::hook_pos() }
+}
+
 /// Fetch the fee base of the current ledger
 #[inline(always)]
 pub fn fee_base() -> i64 {
@@ -37,3 +43,26 @@ pub fn ledger_last_hash(hash: &mut [u8]) -> Result<i64> {
 pub fn ledger_nonce(n: &mut [u8]) -> Result<i64> {
     buf_write(n, _c::ledger_nonce)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::MockLedger;
+
+    #[test]
+    fn hook_pos_test() {
+        let mut ledger = MockLedger::new();
+        let outcome = ledger.run(|| {
+            assert_eq!(hook_pos(), 0);
+            accept(b"", 0)
+        });
+        assert!(outcome.is_accept());
+
+        ledger.hook_pos = 3;
+        let outcome = ledger.run(|| {
+            assert_eq!(hook_pos(), 3);
+            accept(b"", 0)
+        });
+        assert!(outcome.is_accept());
+    }
+}
@@ -0,0 +1,326 @@
+use super::*;
+
+/// Execution context of a hook
+///
+/// Passed by the host as the `reserved` argument of the `hook` entry point.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ExecutionContext {
+    /// The hook is executing strongly, before the originating transaction is applied
+    Strong,
+    /// The hook is executing weakly, after the originating transaction is applied
+    Weak,
+    /// The hook is executing weakly because it requested [hook_again] during its strong execution
+    AgainAsWeak,
+}
+
+impl ExecutionContext {
+    /// Create an execution context from the `reserved` argument of the `hook` entry point
+    #[inline(always)]
+    pub const fn from_reserved(reserved: u32) -> Result<Self> {
+        match reserved {
+            0 => Ok(Self::Strong),
+            1 => Ok(Self::Weak),
+            2 => Ok(Self::AgainAsWeak),
+            _ => Err(Error::InvalidArgument),
+        }
+    }
+
+    /// Returns `true` if the hook is executing strongly
+    #[inline(always)]
+    pub const fn is_strong(self) -> bool {
+        matches!(self, Self::Strong)
+    }
+
+    /// Returns `true` if the hook is executing weakly, including the again-as-weak pass
+    #[inline(always)]
+    pub const fn is_weak(self) -> bool {
+        !self.is_strong()
+    }
+}
+
+/// Callback context of a hook
+///
+/// Passed by the host as the `reserved` argument of the `cbak` entry point.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum CallbackContext {
+    /// The emitted transaction was accepted into a ledger
+    EmitSuccess,
+    /// The emitted transaction failed and will never be accepted into a ledger
+    EmitFailure,
+}
+
+impl CallbackContext {
+    /// Create a callback context from the `reserved` argument of the `cbak` entry point
+    #[inline(always)]
+    pub const fn from_reserved(reserved: u32) -> Result<Self> {
+        match reserved {
+            0 => Ok(Self::EmitSuccess),
+            1 => Ok(Self::EmitFailure),
+            _ => Err(Error::InvalidArgument),
+        }
+    }
+}
+
+/// Load the metadata of the originating transaction into a slot
+///
+/// Only available during weak execution.
+#[inline(always)]
+pub fn meta_slot(slot_no: u32) -> Result<i64> {
+    api_1arg_call(slot_no, _c::meta_slot)
+}
+
+/// Kind of an affected ledger node
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum AffectedNodeKind {
+    /// [FieldId::CreatedNode]
+    Created,
+    /// [FieldId::ModifiedNode]
+    Modified,
+    /// [FieldId::DeletedNode]
+    Deleted,
+}
+
+/// A ledger node affected by the originating transaction
+#[derive(Clone, Copy)]
+pub struct AffectedNode {
+    /// Kind of the node
+    pub kind: AffectedNodeKind,
+    /// Slot holding the node object
+    ///
+    /// The slot is reused by the next iteration of [AffectedNodes].
+    pub slot: u32,
+}
+
+/// Iterator over the [FieldId::AffectedNodes] of slotted transaction metadata
+///
+/// Each loop over this iterator must be guarded with [_g].
+#[derive(Clone, Copy)]
+pub struct AffectedNodes {
+    array_slot: u32,
+    node_slot: u32,
+    index: u32,
+    count: u32,
+}
+
+/// Iterate over the affected nodes of the metadata in `meta_slot_no`
+///
+/// `array_slot` receives the [FieldId::AffectedNodes] array and
+/// `node_slot` receives each node in turn. Pass 0 to allocate new slots.
+#[inline(always)]
+pub fn affected_nodes(meta_slot_no: u32, array_slot: u32, node_slot: u32) -> Result<AffectedNodes> {
+    let array_slot = match slot_subfield(meta_slot_no, FieldId::AffectedNodes, array_slot) {
+        Ok(slot) => slot as u32,
+        Err(e) => return Err(e),
+    };
+
+    let count = match slot_count(array_slot) {
+        Ok(count) => count as u32,
+        Err(e) => return Err(e),
+    };
+
+    Ok(AffectedNodes {
+        array_slot,
+        node_slot,
+        index: 0,
+        count,
+    })
+}
+
+impl AffectedNodes {
+    /// Number of affected nodes
+    #[inline(always)]
+    pub const fn count(&self) -> u32 {
+        self.count
+    }
+}
+
+impl Iterator for AffectedNodes {
+    type Item = Result<AffectedNode>;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.count {
+            return None;
+        }
+
+        let index = self.index;
+        self.index += 1;
+
+        let slot = match slot_subarray(self.array_slot, index, self.node_slot) {
+            Ok(slot) => slot as u32,
+            Err(e) => return Some(Err(e)),
+        };
+        self.node_slot = slot;
+
+        let kind = match slot_type(slot, SlotTypeFlags::Field) {
+            Ok(FieldOrXrpAmount::Field(field)) => match field as u32 {
+                _c::sfCreatedNode => AffectedNodeKind::Created,
+                _c::sfModifiedNode => AffectedNodeKind::Modified,
+                _c::sfDeletedNode => AffectedNodeKind::Deleted,
+                _ => return Some(Err(Error::NotAnObject)),
+            },
+            Ok(_) => return Some(Err(Error::NotAnObject)),
+            Err(e) => return Some(Err(e)),
+        };
+
+        Some(Ok(AffectedNode { kind, slot }))
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.count - self.index) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sto::Writer;
+    use crate::testing::MockLedger;
+    use std::vec::Vec;
+
+    /// Metadata whose affected nodes are objects of the given fields
+    fn meta(nodes: &[FieldId]) -> Vec<u8> {
+        let mut buf = [0u8; 256];
+        let mut writer = Writer::new(&mut buf);
+        writer
+            .array(FieldId::AffectedNodes, |array| {
+                for &node in nodes {
+                    array.object(node, |node| {
+                        node.u16(FieldId::LedgerEntryType, 0x61);
+                    });
+                }
+            })
+            .u8(FieldId::TransactionResult, 0);
+        match writer.finish() {
+            Ok(meta) => meta.to_vec(),
+            Err(e) => panic!("{}", e.code()),
+        }
+    }
+
+    #[test]
+    fn from_reserved_test() {
+        assert!(matches!(
+            ExecutionContext::from_reserved(0),
+            Ok(ExecutionContext::Strong)
+        ));
+        assert!(matches!(
+            ExecutionContext::from_reserved(1),
+            Ok(ExecutionContext::Weak)
+        ));
+        assert!(matches!(
+            ExecutionContext::from_reserved(2),
+            Ok(ExecutionContext::AgainAsWeak)
+        ));
+        assert!(matches!(
+            ExecutionContext::from_reserved(3),
+            Err(Error::InvalidArgument)
+        ));
+        assert!(matches!(
+            ExecutionContext::from_reserved(u32::MAX),
+            Err(Error::InvalidArgument)
+        ));
+        assert!(ExecutionContext::Strong.is_strong());
+        assert!(ExecutionContext::Weak.is_weak());
+        assert!(ExecutionContext::AgainAsWeak.is_weak());
+
+        assert!(matches!(
+            CallbackContext::from_reserved(0),
+            Ok(CallbackContext::EmitSuccess)
+        ));
+        assert!(matches!(
+            CallbackContext::from_reserved(1),
+            Ok(CallbackContext::EmitFailure)
+        ));
+        assert!(matches!(
+            CallbackContext::from_reserved(2),
+            Err(Error::InvalidArgument)
+        ));
+    }
+
+    #[test]
+    fn affected_nodes_test() {
+        let mut ledger = MockLedger::new();
+        ledger.meta = Some(meta(&[
+            FieldId::CreatedNode,
+            FieldId::ModifiedNode,
+            FieldId::DeletedNode,
+            FieldId::Memo,
+        ]));
+
+        let outcome = ledger.run(|| {
+            let meta = match meta_slot(0) {
+                Ok(slot) => slot as u32,
+                Err(e) => panic!("{}", e.code()),
+            };
+            let mut nodes = match affected_nodes(meta, 0, 0) {
+                Ok(nodes) => nodes,
+                Err(e) => panic!("{}", e.code()),
+            };
+            assert_eq!(nodes.count(), 4);
+            assert_eq!(nodes.size_hint(), (4, Some(4)));
+
+            let node = match nodes.next() {
+                Some(Ok(node)) => node,
+                _ => panic!("no created node"),
+            };
+            assert!(node.kind == AffectedNodeKind::Created);
+            assert!(matches!(
+                slot_subfield(node.slot, FieldId::LedgerEntryType, 0),
+                Ok(_)
+            ));
+            // the node slot is reused
+            let slot = node.slot;
+            assert!(matches!(nodes.next(), Some(Ok(node))
+                if node.kind == AffectedNodeKind::Modified && node.slot == slot));
+            assert!(matches!(nodes.next(), Some(Ok(node))
+                if node.kind == AffectedNodeKind::Deleted));
+            assert!(matches!(nodes.next(), Some(Err(Error::NotAnObject))));
+            assert!(nodes.next().is_none());
+            assert_eq!(nodes.size_hint(), (0, Some(0)));
+            crate::accept(b"", 0)
+        });
+        assert!(outcome.is_accept());
+    }
+
+    #[test]
+    fn empty_meta_test() {
+        let mut ledger = MockLedger::new();
+        ledger.meta = Some(meta(&[]));
+
+        let outcome = ledger.run(|| {
+            assert!(matches!(meta_slot(1), Ok(1)));
+            let mut nodes = match affected_nodes(1, 0, 0) {
+                Ok(nodes) => nodes,
+                Err(e) => panic!("{}", e.code()),
+            };
+            assert_eq!(nodes.count(), 0);
+            assert!(nodes.next().is_none());
+            crate::accept(b"", 0)
+        });
+        assert!(outcome.is_accept());
+    }
+
+    #[test]
+    fn bad_slot_test() {
+        let mut ledger = MockLedger::new();
+
+        let outcome = ledger.run(|| {
+            // weak executions only
+            assert!(matches!(meta_slot(0), Err(Error::PrerequisiteNotMet)));
+            assert!(matches!(affected_nodes(1, 0, 0), Err(Error::DoesntExist)));
+            crate::accept(b"", 0)
+        });
+        assert!(outcome.is_accept());
+
+        // metadata without affected nodes
+        ledger.meta = Some(Vec::new());
+        let outcome = ledger.run(|| {
+            assert!(matches!(meta_slot(1), Ok(1)));
+            assert!(matches!(affected_nodes(1, 0, 0), Err(Error::DoesntExist)));
+            crate::accept(b"", 0)
+        });
+        assert!(outcome.is_accept());
+    }
+}
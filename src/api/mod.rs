@@ -1,54 +1,172 @@
 use crate::_c;
 
+/// Declares an enum of host codes, its lookup by code and its names in the C headers
+///
+/// Without a fallback variant, `from_code` returns `None` and `TryFrom` returns
+/// [Unknown] for codes the enum doesn't list. With a fallback variant,
+/// `from_code` wraps such codes in it.
+macro_rules! code_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident: $repr:ident {
+            $($(#[$variant_meta:meta])* $variant:ident = $value:expr => $c_name:literal,)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[repr($repr)]
+        pub enum $name {
+            $($(#[$variant_meta])* $variant = $value,)*
+        }
+
+        impl $name {
+            /// Variant of a code, `None` if the code is not known to this crate
+            #[inline(always)]
+            pub const fn from_code(code: $repr) -> Option<Self> {
+                match code {
+                    $(code if code == $value => Some(Self::$variant),)*
+                    _ => None,
+                }
+            }
+
+            /// Name of the code in the C headers
+            #[inline(always)]
+            pub const fn name(self) -> &'static str {
+                match self {
+                    $(Self::$variant => $c_name,)*
+                }
+            }
+        }
+
+        impl TryFrom<$repr> for $name {
+            type Error = Unknown;
+
+            #[inline(always)]
+            fn try_from(code: $repr) -> core::result::Result<Self, Unknown> {
+                match Self::from_code(code) {
+                    Some(variant) => core::result::Result::Ok(variant),
+                    None => core::result::Result::Err(Unknown(code as _)),
+                }
+            }
+        }
+    };
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident: $repr:ident {
+            $($(#[$variant_meta:meta])* $variant:ident = $value:expr => $c_name:literal,)*
+        }
+        $(#[$fallback_meta:meta])*
+        $fallback:ident => $fallback_name:literal
+    ) => {
+        $(#[$meta])*
+        #[repr($repr)]
+        pub enum $name {
+            $($(#[$variant_meta])* $variant = $value,)*
+            $(#[$fallback_meta])*
+            $fallback($repr) = $repr::MIN,
+        }
+
+        impl $name {
+            /// Variant of a code, wrapped in the fallback variant if not known to this crate
+            #[inline(always)]
+            pub const fn from_code(code: $repr) -> Self {
+                match code {
+                    $(code if code == $value => Self::$variant,)*
+                    code => Self::$fallback(code),
+                }
+            }
+
+            /// Name of the code in the C headers, or the fallback name for unknown codes
+            #[inline(always)]
+            pub const fn name(self) -> &'static str {
+                match self {
+                    $(Self::$variant => $c_name,)*
+                    Self::$fallback(_) => $fallback_name,
+                }
+            }
+        }
+
+        impl From<$repr> for $name {
+            #[inline(always)]
+            fn from(code: $repr) -> Self {
+                Self::from_code(code)
+            }
+        }
+    };
+}
+
+mod amount;
+mod codes;
 mod control;
+mod currency;
+mod drops;
 mod etxn;
 mod float;
 mod ledger;
+mod meta;
 mod otxn;
+mod param;
 mod slot;
 mod state;
 mod sto;
 mod trace;
 mod util;
+mod xpop;
 
+pub use amount::*;
+pub use codes::*;
 pub use control::*;
+pub use currency::*;
+pub use drops::*;
 pub use etxn::*;
 pub use float::*;
 pub use ledger::*;
+pub use meta::*;
 pub use otxn::*;
+pub use param::*;
 pub use slot::*;
 pub use state::*;
 pub use sto::*;
 pub use trace::*;
 pub use util::*;
+pub use xpop::*;
 
 /// Flags canonical
 pub const TF_CANONICAL: u32 = _c::tfCANONICAL;
 
-/// Account id buffer lenght
+/// Account id buffer length
 pub const ACC_ID_LEN: usize = 20;
-/// Public key buffer lenght
+/// Public key buffer length
 pub const PUB_KEY_LEN: usize = 33;
-/// Currency code buffer lenght
+/// Currency code buffer length
 pub const CURRENCY_CODE_SIZE: usize = 20;
-/// Ledger hash buffer lenght
+/// Ledger hash buffer length
 pub const LEDGER_HASH_LEN: usize = 32;
-/// Keylet buffer lenght
+/// Keylet buffer length
 pub const KEYLET_LEN: usize = 34;
-/// State key buffer lenght
+/// State key buffer length
 pub const STATE_KEY_LEN: usize = 32;
-/// NameSpace buffer lenght
+/// NameSpace buffer length
 pub const NAMESPACE_LEN: usize = 32;
-/// Nonce buffer lenght
+/// Nonce buffer length
 pub const NONCE_LEN: usize = 32;
-/// Hash buffer lenght
+/// Hash buffer length
 pub const HASH_LEN: usize = 32;
-/// Native amount buffer lenght
+/// Native amount buffer length
 pub const NATIVE_AMOUNT_LEN: usize = 8;
-/// IOU amount buffer lenght
+/// IOU amount buffer length
 pub const IOU_AMOUNT_LEN: usize = 48;
-/// Emit details buffer lenght
-pub const EMIT_DETAILS_SIZE: usize = 105;
+/// Emit details buffer length
+pub const EMIT_DETAILS_SIZE: usize = 138;
+/// Hook parameter name max length
+pub const HOOK_PARAM_NAME_MAX_LEN: usize = 32;
+/// Hook parameter value max length
+pub const HOOK_PARAM_VALUE_MAX_LEN: usize = 256;
+
+/// Code not known to this crate
+///
+/// Error of the `TryFrom` conversions of [FieldId] and [TxnType].
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Unknown(pub u32);
 
 /// Buffer of the specified size
 pub type Buffer<const T: usize> = [u8; T];
@@ -76,53 +194,6 @@ pub type EmitDetails = Buffer<EMIT_DETAILS_SIZE>;
 /// Currency code buffer
 pub type CurrencyCode = Buffer<CURRENCY_CODE_SIZE>;
 
-/// Transaction type
-#[allow(missing_docs)]
-#[derive(Clone, Copy)]
-#[repr(u32)]
-pub enum TxnType {
-    Payment = _c::ttPAYMENT,
-    EscrowCreate = 1,
-    EscrowFinish = 2,
-    AccountSet = 3,
-    EscrowCancel = 4,
-    RegularKeySet = 5,
-    // NicknameSet = 6,
-    OfferCreate = 7,
-    OfferCancel = 8,
-    TicketCreate = 10,
-    // SpinalTap = 11,
-    SignerListSet = 12,
-    PaychanCreate = 13,
-    PaychanFund = 14,
-    PaychanClaim = 15,
-    CheckCreate = 16,
-    CheckCash = 17,
-    CheckCancel = 18,
-    DepositPreauth = 19,
-    TrustSet = 20,
-    AccountDelete = 21,
-    HookSet = 22,
-    NFTokenMint = 25,
-    NFTokenBurn = 26,
-    NFTokenCreateOffer = 27,
-    NFTokenCancelOffer = 28,
-    NFTokenAcceptOffer = 29,
-    URITokenMint = 45,
-    URITokenBurn = 46,
-    URITokenBuy = 47,
-    URITokenCreateSellOffer = 48,
-    URITokenCancelSellOffer = 49,
-    Remit = 95,
-    GenesisMint = 96,
-    Import = 97,
-    ClaimReward = 98,
-    Invoke = 99,
-    Amendment = 100,
-    Fee = 101,
-    UnlModify = 102,
-}
-
 /// Account type
 #[allow(missing_docs)]
 #[derive(Clone, Copy)]
@@ -175,6 +246,7 @@ pub enum KeyletType<'a> {
     Offer(&'a AccountId, u32),
     Quality(&'a [u8], u32, u32),
     EmittedDir,
+    Ticket(&'a AccountId, u32),
     Signers(&'a AccountId),
     Check(&'a AccountId, u32),
     DepositPreauth(&'a AccountId, &'a AccountId),
@@ -196,6 +268,8 @@ pub enum KeyletType<'a> {
 pub enum FieldOrXrpAmount {
     /// Field ID
     Field(FieldId),
+    /// Field ID not known to this crate
+    UnknownField(u32),
     /// STI_AMOUNT type contains a native amount
     NativeAmount,
     /// STI_AMOUNT type contains non-native amount
@@ -211,244 +285,138 @@ pub enum SlotTypeFlags {
     NativeAmount,
 }
 
-/// Field type
-#[allow(missing_docs)]
-#[derive(Clone, Copy)]
+impl FieldId {
+    /// Serialized type code, the upper 16 bits of the field ID
+    #[inline(always)]
+    pub const fn type_code(self) -> u32 {
+        self as u32 >> 16
+    }
+
+    /// Field code within the serialized type, the lower 16 bits of the field ID
+    #[inline(always)]
+    pub const fn field_code(self) -> u32 {
+        self as u32 & 0xFFFF
+    }
+
+    /// Serialized type of the field
+    #[inline(always)]
+    pub const fn serialized_type(self) -> SerializedType {
+        SerializedType::from_code(self.type_code())
+    }
+
+    /// Returns `true` if the value is prefixed with its length
+    #[inline(always)]
+    pub const fn is_vl_encoded(self) -> bool {
+        self.serialized_type().is_vl_encoded()
+    }
+}
+
+/// Serialized type of a field
+///
+/// Discriminants are the type codes of the serialization format.
+#[derive(Clone, Copy, PartialEq, Eq)]
 #[repr(u32)]
-pub enum FieldId {
-    CloseResolution = _c::sfCloseResolution,
-    Method = _c::sfMethod,
-    TransactionResult = _c::sfTransactionResult,
-    TickSize = _c::sfTickSize,
-    UNLModifyDisabling = _c::sfUNLModifyDisabling,
-    HookResult = _c::sfHookResult,
-    LedgerEntryType = _c::sfLedgerEntryType,
-    TransactionType = _c::sfTransactionType,
-    SignerWeight = _c::sfSignerWeight,
-    TransferFee = _c::sfTransferFee,
-    Version = _c::sfVersion,
-    HookStateChangeCount = _c::sfHookStateChangeCount,
-    HookEmitCount = _c::sfHookEmitCount,
-    HookExecutionIndex = _c::sfHookExecutionIndex,
-    HookApiVersion = _c::sfHookApiVersion,
-    NetworkID = _c::sfNetworkID,
-    Flags = _c::sfFlags,
-    SourceTag = _c::sfSourceTag,
-    Sequence = _c::sfSequence,
-    PreviousTxnLgrSeq = _c::sfPreviousTxnLgrSeq,
-    LedgerSequence = _c::sfLedgerSequence,
-    CloseTime = _c::sfCloseTime,
-    ParentCloseTime = _c::sfParentCloseTime,
-    SigningTime = _c::sfSigningTime,
-    Expiration = _c::sfExpiration,
-    TransferRate = _c::sfTransferRate,
-    WalletSize = _c::sfWalletSize,
-    OwnerCount = _c::sfOwnerCount,
-    DestinationTag = _c::sfDestinationTag,
-    HighQualityIn = _c::sfHighQualityIn,
-    HighQualityOut = _c::sfHighQualityOut,
-    LowQualityIn = _c::sfLowQualityIn,
-    LowQualityOut = _c::sfLowQualityOut,
-    QualityIn = _c::sfQualityIn,
-    QualityOut = _c::sfQualityOut,
-    StampEscrow = _c::sfStampEscrow,
-    BondAmount = _c::sfBondAmount,
-    LoadFee = _c::sfLoadFee,
-    OfferSequence = _c::sfOfferSequence,
-    FirstLedgerSequence = _c::sfFirstLedgerSequence,
-    LastLedgerSequence = _c::sfLastLedgerSequence,
-    TransactionIndex = _c::sfTransactionIndex,
-    OperationLimit = _c::sfOperationLimit,
-    ReferenceFeeUnits = _c::sfReferenceFeeUnits,
-    ReserveBase = _c::sfReserveBase,
-    ReserveIncrement = _c::sfReserveIncrement,
-    SetFlag = _c::sfSetFlag,
-    ClearFlag = _c::sfClearFlag,
-    SignerQuorum = _c::sfSignerQuorum,
-    CancelAfter = _c::sfCancelAfter,
-    FinishAfter = _c::sfFinishAfter,
-    SignerListID = _c::sfSignerListID,
-    SettleDelay = _c::sfSettleDelay,
-    TicketCount = _c::sfTicketCount,
-    TicketSequence = _c::sfTicketSequence,
-    NFTokenTaxon = _c::sfNFTokenTaxon,
-    MintedNFTokens = _c::sfMintedNFTokens,
-    BurnedNFTokens = _c::sfBurnedNFTokens,
-    HookStateCount = _c::sfHookStateCount,
-    EmitGeneration = _c::sfEmitGeneration,
-    LockCount = _c::sfLockCount,
-    FirstNFTokenSequence = _c::sfFirstNFTokenSequence,
-    XahauActivationLgrSeq = _c::sfXahauActivationLgrSeq,
-    ImportSequence = _c::sfImportSequence,
-    RewardTime = _c::sfRewardTime,
-    RewardLgrFirst = _c::sfRewardLgrFirst,
-    RewardLgrLast = _c::sfRewardLgrLast,
-    IndexNext = _c::sfIndexNext,
-    IndexPrevious = _c::sfIndexPrevious,
-    BookNode = _c::sfBookNode,
-    OwnerNode = _c::sfOwnerNode,
-    BaseFee = _c::sfBaseFee,
-    ExchangeRate = _c::sfExchangeRate,
-    LowNode = _c::sfLowNode,
-    HighNode = _c::sfHighNode,
-    DestinationNode = _c::sfDestinationNode,
-    Cookie = _c::sfCookie,
-    ServerVersion = _c::sfServerVersion,
-    NFTokenOfferNode = _c::sfNFTokenOfferNode,
-    EmitBurden = _c::sfEmitBurden,
-    HookInstructionCount = _c::sfHookInstructionCount,
-    HookReturnCode = _c::sfHookReturnCode,
-    ReferenceCount = _c::sfReferenceCount,
-    AccountIndex = _c::sfAccountIndex,
-    AccountCount = _c::sfAccountCount,
-    RewardAccumulator = _c::sfRewardAccumulator,
-    EmailHash = _c::sfEmailHash,
-    TakerPaysCurrency = _c::sfTakerPaysCurrency,
-    TakerPaysIssuer = _c::sfTakerPaysIssuer,
-    TakerGetsCurrency = _c::sfTakerGetsCurrency,
-    TakerGetsIssuer = _c::sfTakerGetsIssuer,
-    LedgerHash = _c::sfLedgerHash,
-    ParentHash = _c::sfParentHash,
-    TransactionHash = _c::sfTransactionHash,
-    AccountHash = _c::sfAccountHash,
-    PreviousTxnID = _c::sfPreviousTxnID,
-    LedgerIndex = _c::sfLedgerIndex,
-    WalletLocator = _c::sfWalletLocator,
-    RootIndex = _c::sfRootIndex,
-    AccountTxnID = _c::sfAccountTxnID,
-    NFTokenID = _c::sfNFTokenID,
-    EmitParentTxnID = _c::sfEmitParentTxnID,
-    EmitNonce = _c::sfEmitNonce,
-    EmitHookHash = _c::sfEmitHookHash,
-    BookDirectory = _c::sfBookDirectory,
-    InvoiceID = _c::sfInvoiceID,
-    // Nickname = _c::sfNickname,
-    Amendment = _c::sfAmendment,
-    HookOn = _c::sfHookOn,
-    Digest = _c::sfDigest,
-    Channel = _c::sfChannel,
-    ConsensusHash = _c::sfConsensusHash,
-    CheckID = _c::sfCheckID,
-    ValidatedHash = _c::sfValidatedHash,
-    PreviousPageMin = _c::sfPreviousPageMin,
-    NextPageMin = _c::sfNextPageMin,
-    NFTokenBuyOffer = _c::sfNFTokenBuyOffer,
-    NFTokenSellOffer = _c::sfNFTokenSellOffer,
-    HookStateKey = _c::sfHookStateKey,
-    HookHash = _c::sfHookHash,
-    HookNamespace = _c::sfHookNamespace,
-    HookSetTxnID = _c::sfHookSetTxnID,
-    OfferID = _c::sfOfferID,
-    EscrowID = _c::sfEscrowID,
-    URITokenID = _c::sfURITokenID,
-    GovernanceFlags = _c::sfGovernanceFlags,
-    GovernanceMarks = _c::sfGovernanceMarks,
-    EmittedTxnID = _c::sfEmittedTxnID,
-    Amount = _c::sfAmount,
-    Balance = _c::sfBalance,
-    LimitAmount = _c::sfLimitAmount,
-    TakerPays = _c::sfTakerPays,
-    TakerGets = _c::sfTakerGets,
-    LowLimit = _c::sfLowLimit,
-    HighLimit = _c::sfHighLimit,
-    Fee = _c::sfFee,
-    SendMax = _c::sfSendMax,
-    DeliverMin = _c::sfDeliverMin,
-    MinimumOffer = _c::sfMinimumOffer,
-    RippleEscrow = _c::sfRippleEscrow,
-    DeliveredAmount = _c::sfDeliveredAmount,
-    NFTokenBrokerFee = _c::sfNFTokenBrokerFee,
-    LockedBalance = _c::sfLockedBalance,
-    BaseFeeDrops = _c::sfBaseFeeDrops,
-    ReserveBaseDrops = _c::sfReserveBaseDrops,
-    ReserveIncrementDrops = _c::sfReserveIncrementDrops,
-    PublicKey = _c::sfPublicKey,
-    MessageKey = _c::sfMessageKey,
-    SigningPubKey = _c::sfSigningPubKey,
-    TxnSignature = _c::sfTxnSignature,
-    URI = _c::sfURI,
-    Signature = _c::sfSignature,
-    Domain = _c::sfDomain,
-    FundCode = _c::sfFundCode,
-    RemoveCode = _c::sfRemoveCode,
-    ExpireCode = _c::sfExpireCode,
-    CreateCode = _c::sfCreateCode,
-    MemoType = _c::sfMemoType,
-    MemoData = _c::sfMemoData,
-    MemoFormat = _c::sfMemoFormat,
-    Fulfillment = _c::sfFulfillment,
-    Condition = _c::sfCondition,
-    MasterSignature = _c::sfMasterSignature,
-    UNLModifyValidator = _c::sfUNLModifyValidator,
-    ValidatorToDisable = _c::sfValidatorToDisable,
-    ValidatorToReEnable = _c::sfValidatorToReEnable,
-    HookStateData = _c::sfHookStateData,
-    HookReturnString = _c::sfHookReturnString,
-    HookParameterName = _c::sfHookParameterName,
-    HookParameterValue = _c::sfHookParameterValue,
-    Blob = _c::sfBlob,
-    Account = _c::sfAccount,
-    Owner = _c::sfOwner,
-    Destination = _c::sfDestination,
-    Issuer = _c::sfIssuer,
-    Authorize = _c::sfAuthorize,
-    Unauthorize = _c::sfUnauthorize,
-    RegularKey = _c::sfRegularKey,
-    NFTokenMinter = _c::sfNFTokenMinter,
-    EmitCallback = _c::sfEmitCallback,
-    HookAccount = _c::sfHookAccount,
-    Inform = _c::sfInform,
-    Indexes = _c::sfIndexes,
-    Hashes = _c::sfHashes,
-    Amendments = _c::sfAmendments,
-    NFTokenOffers = _c::sfNFTokenOffers,
-    HookNamespaces = _c::sfHookNamespaces,
-    URITokenIDs = _c::sfURITokenIDs,
-    Paths = _c::sfPaths,
-    TransactionMetaData = _c::sfTransactionMetaData,
-    CreatedNode = _c::sfCreatedNode,
-    DeletedNode = _c::sfDeletedNode,
-    ModifiedNode = _c::sfModifiedNode,
-    PreviousFields = _c::sfPreviousFields,
-    FinalFields = _c::sfFinalFields,
-    NewFields = _c::sfNewFields,
-    TemplateEntry = _c::sfTemplateEntry,
-    Memo = _c::sfMemo,
-    SignerEntry = _c::sfSignerEntry,
-    EmitDetails = _c::sfEmitDetails,
-    Hook = _c::sfHook,
-    Signer = _c::sfSigner,
-    Majority = _c::sfMajority,
-    DisabledValidator = _c::sfDisabledValidator,
-    EmittedTxn = _c::sfEmittedTxn,
-    HookExecution = _c::sfHookExecution,
-    HookDefinition = _c::sfHookDefinition,
-    HookParameter = _c::sfHookParameter,
-    HookGrant = _c::sfHookGrant,
-    GenesisMint = _c::sfGenesisMint,
-    ActiveValidator = _c::sfActiveValidator,
-    ImportVLKey = _c::sfImportVLKey,
-    HookEmission = _c::sfHookEmission,
-    MintURIToken = _c::sfMintURIToken,
-    AmountEntry = _c::sfAmountEntry,
-    Signers = _c::sfSigners,
-    SignerEntries = _c::sfSignerEntries,
-    Template = _c::sfTemplate,
-    Necessary = _c::sfNecessary,
-    Sufficient = _c::sfSufficient,
-    AffectedNodes = _c::sfAffectedNodes,
-    Memos = _c::sfMemos,
-    NFTokens = _c::sfNFTokens,
-    Majorities = _c::sfMajorities,
-    DisabledValidators = _c::sfDisabledValidators,
-    HookExecutions = _c::sfHookExecutions,
-    HookParameters = _c::sfHookParameters,
-    HookGrants = _c::sfHookGrants,
-    GenesisMints = _c::sfGenesisMints,
-    ActiveValidators = _c::sfActiveValidators,
-    ImportVLKeys = _c::sfImportVLKeys,
-    HookEmissions = _c::sfHookEmissions,
-    Amounts = _c::sfAmounts,
+pub enum SerializedType {
+    /// Type code not known to this crate
+    Unknown = 0,
+    /// 16-bit unsigned integer
+    UInt16 = 1,
+    /// 32-bit unsigned integer
+    UInt32 = 2,
+    /// 64-bit unsigned integer
+    UInt64 = 3,
+    /// 128-bit hash
+    Hash128 = 4,
+    /// 256-bit hash
+    Hash256 = 5,
+    /// Native or issued currency amount
+    Amount = 6,
+    /// Variable length blob
+    Blob = 7,
+    /// Account ID, length prefixed
+    AccountID = 8,
+    /// Inner object, ended by an end marker
+    STObject = 14,
+    /// Array of inner objects, ended by an end marker
+    STArray = 15,
+    /// 8-bit unsigned integer
+    UInt8 = 16,
+    /// 160-bit hash
+    Hash160 = 17,
+    /// Payment paths
+    PathSet = 18,
+    /// List of 256-bit hashes, length prefixed
+    Vector256 = 19,
+    /// 96-bit unsigned integer
+    UInt96 = 20,
+    /// 192-bit unsigned integer
+    UInt192 = 21,
+    /// 384-bit unsigned integer
+    UInt384 = 22,
+    /// 512-bit unsigned integer
+    UInt512 = 23,
+    /// Currency and issuer
+    Issue = 24,
+    /// Cross-chain bridge
+    XChainBridge = 25,
+    /// Currency code
+    Currency = 26,
+}
+
+impl SerializedType {
+    /// Serialized type of a type code, [SerializedType::Unknown] if not known
+    #[inline(always)]
+    pub const fn from_code(code: u32) -> Self {
+        match code {
+            1 => Self::UInt16,
+            2 => Self::UInt32,
+            3 => Self::UInt64,
+            4 => Self::Hash128,
+            5 => Self::Hash256,
+            6 => Self::Amount,
+            7 => Self::Blob,
+            8 => Self::AccountID,
+            14 => Self::STObject,
+            15 => Self::STArray,
+            16 => Self::UInt8,
+            17 => Self::Hash160,
+            18 => Self::PathSet,
+            19 => Self::Vector256,
+            20 => Self::UInt96,
+            21 => Self::UInt192,
+            22 => Self::UInt384,
+            23 => Self::UInt512,
+            24 => Self::Issue,
+            25 => Self::XChainBridge,
+            26 => Self::Currency,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// Returns `true` if values of this type are prefixed with their length
+    #[inline(always)]
+    pub const fn is_vl_encoded(self) -> bool {
+        matches!(self, Self::Blob | Self::AccountID | Self::Vector256)
+    }
+
+    /// Length of values of this type, `None` if it varies
+    #[inline(always)]
+    pub const fn fixed_len(self) -> Option<usize> {
+        match self {
+            Self::UInt8 => Some(1),
+            Self::UInt16 => Some(2),
+            Self::UInt32 => Some(4),
+            Self::UInt64 => Some(8),
+            Self::UInt96 => Some(12),
+            Self::Hash128 => Some(16),
+            Self::Hash160 | Self::Currency => Some(20),
+            Self::UInt192 => Some(24),
+            Self::Hash256 => Some(32),
+            Self::UInt384 => Some(48),
+            Self::UInt512 => Some(64),
+            _ => None,
+        }
+    }
 }
 
 /// Data representation
@@ -475,6 +443,25 @@ pub enum TxnTypeFlags {
     EmittingTxnIfEmitFailure = 1,
 }
 
+/// Mode for [hook_skip]
+#[derive(Clone, Copy)]
+#[repr(u32)]
+pub enum SkipMode {
+    /// Skip the hook with the given hash in the chain
+    Skip = 0,
+    /// Cancel a previously requested skip
+    CancelSkip = 1,
+}
+
+/// Outcome of [hook_again]
+#[derive(Clone, Copy)]
+pub enum HookAgainOutcome {
+    /// The hook will be executed again weakly after the transaction is applied
+    WillExecuteWeakly,
+    /// The hook is not running strongly, so it cannot be executed again
+    PrerequisiteNotMet,
+}
+
 /// `Result` is a type that represents either success ([`Ok`]) or failure ([`Err`]).
 //
 /// This is simple version of Result type
@@ -571,141 +558,44 @@ impl<T> Result<T> {
     }
 }
 
-/// Possible errors returned by Hook APIs.
-///
-/// Errors are global across all Hook APIs.
-#[derive(Clone, Copy)]
-#[repr(i32)]
-pub enum Error {
-    /// Non-negative return codes refer always to success and usually indicate the number of bytes written or events performed, depending on the specific API.
-    // SUCCESS = _c::SUCCESS,
-    /// A pointer or buffer length provided as a parameter described memory outside of the Hook's allowed memory region.
-    OutOfBounds = _c::OUT_OF_BOUNDS,
-    /// Reserved for internal invariant trips, generally unrelated to inputs.
-    /// These should be reported with an issue.
-    InternalError = _c::INTERNAL_ERROR,
-    /// Attempted to set a parameter or value larger than the allowed space .
-    TooBig = _c::TOO_BIG,
-    /// The API was unable to produce output to the write_ptr because the specified write_len was too small
-    TooSmall = _c::TOO_SMALL,
-    /// The requested object or item wasn't found
-    DoesntExist = _c::DOESNT_EXIST,
-    /// The Hook attempted to allocate an item into a slot, but there were no slots free.
-    /// To avoid ensure re-use of existing slots. The maximum number of slots is 255.
-    NoFreeSlots = _c::NO_FREE_SLOTS,
-    /// One or more of the parameters to the API were invalid according to the individual API's specification.
-    InvalidArgument = _c::INVALID_ARGUMENT,
-    /// Some APIs allow for a once-per-execution parameter to be set.
-    /// A second attempt to set a once-per-execution parameter results in this error.
-    AlreadySet = _c::ALREADY_SET,
-    /// An API required the Hook to do something before the API is allowed to be called.
-    /// Check the API's documentation.
-    PrerequisiteNotMet = _c::PREREQUISITE_NOT_MET,
-    /// During fee calculation if an absurdly large fee is calculated this error is returned.
-    FeeTooLarge = _c::FEE_TOO_LARGE,
-    /// An attempt to emit() a TXN was unsccessful for any of a number of reasons.
-    /// Check the trace log of the rippled to which you are submitting the originating TXN.
-    EmissionFailure = _c::EMISSION_FAILURE,
-    /// A Hook may only use up to 256 calls to nonce() per execution.
-    /// Further calls result in this error code.
-    TooManyNonces = _c::TOO_MANY_NONCES,
-    /// A Hook must declare ahead of time how many TXN it intends to emit().
-    /// If it emits fewer than this many, this is allowed.
-    /// If it emits more than this many this error is returned.
-    TooManyEmittedTxn = _c::TOO_MANY_EMITTED_TXN,
-    /// While Hooks is/was in development an API may return this if some or all of that API is planned but not yet implemented.
-    NotImplemented = _c::NOT_IMPLEMENTED,
-    /// An API which accepts a 20 byte Account ID may return this if, in its opinion, the Account ID was not valid for any reason.
-    InvalidAccount = _c::INVALID_ACCOUNT,
-    /// All loops inside a Hook must declare at the top of the loop, as the first non trivial instruction,
-    /// before any branch instruction, the promised maximum number of iterations of the loop.
-    /// If this promise is violated the hook terminates immediately with this error code.
-    GuardViolation = _c::GUARD_VIOLATION,
-    /// The requested serialized field could not be found in the specified object.
-    InvalidField = _c::INVALID_FIELD,
-    /// While parsing serialized content an error was encountered (typically indicating an invalidly serialized object).
-    ParseError = _c::PARSE_ERROR,
-    /// Used internally to communicate a rollback event.
-    RcRollback = _c::RC_ROLLBACK,
-    /// Used internally to communicate an accept event.
-    RcAccept = _c::RC_ACCEPT,
-    /// Specified keylet could not be found, or keylet is invalid
-    NoSuchKeylet = _c::NO_SUCH_KEYLET,
-    /// API was asked to assume object under analysis is an STArray but it was not.
-    NotAnArray = _c::NOT_AN_ARRAY,
-    /// API was asked to assume object under analysis is an STObject but it was not.
-    NotAnObject = _c::NOT_AN_OBJECT,
-    /// A floating point operation resulted in Not-A-Number or API call attempted to specify an XFL floating point number outside of the expressible range of XFL.
-    InvalidFloat = _c::INVALID_FLOAT,
-    /// API call would result in a division by zero, so API ended early.
-    DivisionByZero = _c::DIVISION_BY_ZERO,
-    /// When attempting to create an XFL the mantissa must be 16 decimal digits.
-    MantissaOversized = _c::MANTISSA_OVERSIZED,
-    /// When attempting to create an XFL the mantissa must be 16 decimal digits.
-    MantissaUndersized = _c::MANTISSA_UNDERSIZED,
-    /// When attempting to create an XFL the exponent must not exceed 80.
-    ExponentOversized = _c::EXPONENT_OVERSIZED,
-    /// When attempting to create an XFL the exponent must not be less than -96.
-    ExponentUndersized = _c::EXPONENT_UNDERSIZED,
-    /// A floating point operation done on an XFL resulted in a value larger than XFL format is able to represent.
-    XflOverflow = _c::XFL_OVERFLOW,
-    /// An API assumed an STAmount was an IOU when in fact it was XRP.
-    NotIouAmount = _c::NOT_IOU_AMOUNT,
-    /// An API assumed an STObject was an STAmount when in fact it was not.
-    NotAnAmount = _c::NOT_AN_AMOUNT,
-    /// An API would have returned a negative integer except that negative integers are reserved for error codes (i.e. what you are reading.)
-    CantReturnNegative = _c::CANT_RETURN_NEGATIVE,
-    /// Hook attempted to set foreign state but was not authorized to do so (grant was missing or invalid.)
-    NotAuthorized = _c::NOT_AUTHORIZED,
-    /// Hook previously received a NOT_AUTHORIZED return code and is not allowed to retry.
-    PreviousFailurePreventsRetry = _c::PREVIOUS_FAILURE_PREVENTS_RETRY,
-    /// Attempted to set a hook parameter for a later hook in the chain, but there are now too many parameters.
-    TooManyParams = _c::TOO_MANY_PARAMS,
-    /// Serialized transaction was not a valid transaction (usually because of a missing required field or data corruption / truncation.)
-    InvalidTxn = _c::INVALID_TXN,
-    /// Setting an additional state object on this account would cause the reserve requirements to exceed the account's balance.
-    ReserveInssuficient = _c::RESERVE_INSUFFICIENT,
-    /// Hook API would be forced to return a complex number, which it cannot do.
-    ComplexNotSupported = _c::COMPLEX_NOT_SUPPORTED,
-    /// Two arguments were required to be of the same type but are not.
-    DoesNotMatch = _c::DOES_NOT_MATCH,
-    /// The provided public key was not valid.
-    InvalidKey = _c::INVALID_KEY,
-    /// The buffer did not contain a nul terminated string.
-    NotAString = _c::NOT_A_STRING,
-    /// The writing pointer points to a buffer that overlaps with the reading pointer.
-    MemOverlap = _c::MEM_OVERLAP,
-    /// More than 5000 modified state entries in the combined hook chains
-    TooManyStateModifications = _c::TOO_MANY_STATE_MODIFICATIONS,
-    /// More than 256 namespaces on this account
-    TooManyNamespaces = _c::TOO_MANY_NAMESPACES,
-}
-
 impl Error {
-    #[inline(always)]
-    fn from_code(code: i32) -> Self {
-        unsafe { core::mem::transmute(code) }
-    }
-
     /// Error code
     #[inline(always)]
-    pub fn code(self) -> i32 {
-        self as _
+    pub const fn code(self) -> i32 {
+        match self {
+            Self::Unknown(code) => code,
+            // SAFETY: the enum has a primitive representation,
+            // so it starts with its discriminant
+            _ => unsafe { *(&self as *const Self as *const i32) },
+        }
     }
 }
 
-type Api1ArgsU32 = unsafe extern "C" fn(u32) -> i64;
-type Api2ArgsU32 = unsafe extern "C" fn(u32, u32) -> i64;
-type Api3ArgsU32 = unsafe extern "C" fn(u32, u32, u32) -> i64;
-type Api4ArgsU32 = unsafe extern "C" fn(u32, u32, u32, u32) -> i64;
-type Api6ArgsU32 = unsafe extern "C" fn(u32, u32, u32, u32, u32, u32) -> i64;
+/// Host function pointer type
+///
+/// `C-unwind` for the native testing host, which unwinds out of host calls.
+#[cfg(not(any(test, feature = "testing")))]
+macro_rules! host_fn {
+    ($($arg:ty),*) => { unsafe extern "C" fn($($arg),*) -> i64 };
+}
+
+/// Host function pointer type
+///
+/// `C-unwind` for the native testing host, which unwinds out of host calls.
+#[cfg(any(test, feature = "testing"))]
+macro_rules! host_fn {
+    ($($arg:ty),*) => { unsafe extern "C-unwind" fn($($arg),*) -> i64 };
+}
+
+type Api1ArgsU32 = host_fn!(u32);
+type Api3ArgsU32 = host_fn!(u32, u32, u32);
 
-type BufWriter = Api2ArgsU32;
-type BufReader = Api2ArgsU32;
-type Buf2Reader = Api4ArgsU32;
-type BufWriterReader = Api4ArgsU32;
-type Buf3Reader = Api6ArgsU32;
-type BufWriter1Arg = Api3ArgsU32;
+type BufWriter = host_fn!(_c::Ptr, u32);
+type BufReader = BufWriter;
+type Buf2Reader = host_fn!(_c::Ptr, u32, _c::Ptr, u32);
+type BufWriterReader = Buf2Reader;
+type Buf3Reader = host_fn!(_c::Ptr, u32, _c::Ptr, u32, _c::Ptr, u32);
+type BufWriter1Arg = host_fn!(_c::Ptr, u32, u32);
 
 #[inline(always)]
 fn api_1arg_call(arg: u32, fun: Api1ArgsU32) -> Result<i64> {
@@ -723,21 +613,21 @@ fn api_3arg_call(arg_1: u32, arg_2: u32, arg_3: u32, fun: Api3ArgsU32) -> Result
 
 #[inline(always)]
 fn buf_write(buf_write: &mut [u8], fun: BufWriter) -> Result<i64> {
-    let res = unsafe { fun(buf_write.as_mut_ptr() as u32, buf_write.len() as u32) };
+    let res = unsafe { fun(buf_write.as_mut_ptr() as _, buf_write.len() as u32) };
 
     result_i64(res)
 }
 
 #[inline(always)]
 fn buf_write_1arg(buf_write: &mut [u8], arg: u32, fun: BufWriter1Arg) -> Result<i64> {
-    let res = unsafe { fun(buf_write.as_mut_ptr() as u32, buf_write.len() as u32, arg) };
+    let res = unsafe { fun(buf_write.as_mut_ptr() as _, buf_write.len() as u32, arg) };
 
     result_i64(res)
 }
 
 #[inline(always)]
 fn buf_read(buf: &[u8], fun: BufReader) -> Result<i64> {
-    let res = unsafe { fun(buf.as_ptr() as u32, buf.len() as u32) };
+    let res = unsafe { fun(buf.as_ptr() as _, buf.len() as u32) };
 
     result_i64(res)
 }
@@ -746,9 +636,9 @@ fn buf_read(buf: &[u8], fun: BufReader) -> Result<i64> {
 fn buf_2read(buf_1: &[u8], buf_2: &[u8], fun: Buf2Reader) -> Result<i64> {
     let res = unsafe {
         fun(
-            buf_1.as_ptr() as u32,
+            buf_1.as_ptr() as _,
             buf_1.len() as u32,
-            buf_2.as_ptr() as u32,
+            buf_2.as_ptr() as _,
             buf_2.len() as u32,
         )
     };
@@ -760,9 +650,9 @@ fn buf_2read(buf_1: &[u8], buf_2: &[u8], fun: Buf2Reader) -> Result<i64> {
 fn buf_write_read(buf_write: &mut [u8], buf_read: &[u8], fun: BufWriterReader) -> Result<i64> {
     let res = unsafe {
         fun(
-            buf_write.as_mut_ptr() as u32,
+            buf_write.as_mut_ptr() as _,
             buf_write.len() as u32,
-            buf_read.as_ptr() as u32,
+            buf_read.as_ptr() as _,
             buf_read.len() as u32,
         )
     };
@@ -779,11 +669,11 @@ fn buf_3_read(
 ) -> Result<i64> {
     let res = unsafe {
         fun(
-            buf_read_1.as_ptr() as u32,
+            buf_read_1.as_ptr() as _,
             buf_read_1.len() as u32,
-            buf_read_2.as_ptr() as u32,
+            buf_read_2.as_ptr() as _,
             buf_read_2.len() as u32,
-            buf_read_3.as_ptr() as u32,
+            buf_read_3.as_ptr() as _,
             buf_read_3.len() as u32,
         )
     };
@@ -794,11 +684,11 @@ fn buf_3_read(
 #[inline(always)]
 fn range_from_location(location: i64) -> core::ops::Range<usize> {
     let offset: i32 = (location >> 32) as _;
-    let lenght: i32 = (location & 0xFFFFFFFF) as _;
+    let length: i32 = (location & 0xFFFFFFFF) as _;
 
     core::ops::Range {
         start: offset as _,
-        end: (offset + lenght) as _,
+        end: (offset + length) as _,
     }
 }
 
@@ -854,7 +744,7 @@ fn buf_read_and_1_arg(
             keylet_type_c,
             buf_read.as_ptr() as _,
             buf_read.len() as _,
-            arg,
+            arg as _,
             0,
             0,
             0,
@@ -879,8 +769,8 @@ fn buf_read_and_2_args(
             keylet_type_c,
             buf_read.as_ptr() as _,
             buf_read.len() as _,
-            arg_1,
-            arg_2,
+            arg_1 as _,
+            arg_2 as _,
             0,
             0,
         )
@@ -928,3 +818,56 @@ fn result_xfl(res: i64) -> Result<XFL> {
         _ => Err(Error::from_code(res as _)),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn field_id_metadata_test() {
+        assert_eq!(FieldId::TransactionType.type_code(), 1);
+        assert_eq!(FieldId::TransactionType.field_code(), 2);
+        assert!(FieldId::TransactionType.serialized_type() == SerializedType::UInt16);
+        assert!(FieldId::Account.serialized_type() == SerializedType::AccountID);
+        assert!(FieldId::Memos.serialized_type() == SerializedType::STArray);
+        assert!(FieldId::Account.is_vl_encoded());
+        assert!(FieldId::Blob.is_vl_encoded());
+        assert!(!FieldId::Amount.is_vl_encoded());
+        assert!(SerializedType::from_code(9) == SerializedType::Unknown);
+    }
+
+    #[test]
+    fn code_lookup_test() {
+        assert!(matches!(
+            FieldId::from_code(_c::sfAccount),
+            Some(FieldId::Account)
+        ));
+        assert!(FieldId::from_code(0xFFFF_FFFF).is_none());
+        assert!(matches!(
+            FieldId::try_from(0xFFFF_FFFF),
+            core::result::Result::Err(Unknown(0xFFFF_FFFF))
+        ));
+        assert!(matches!(
+            TxnType::try_from(99),
+            core::result::Result::Ok(TxnType::Invoke)
+        ));
+        assert!(TxnType::from_code(200).is_none());
+        assert!(matches!(
+            TxnType::from_code(_c::ttUNL_REPORT),
+            Some(TxnType::UnlReport)
+        ));
+
+        assert!(matches!(
+            Error::from_code(_c::GUARD_VIOLATION),
+            Error::GuardViolation
+        ));
+        assert_eq!(Error::GuardViolation.code(), _c::GUARD_VIOLATION);
+        assert!(matches!(Error::from_code(-1000), Error::Unknown(-1000)));
+        assert_eq!(Error::from(-1000).code(), -1000);
+
+        assert_eq!(TxnType::NFTokenMint.name(), "ttNFTOKEN_MINT");
+        assert_eq!(FieldId::Account.name(), "sfAccount");
+        assert_eq!(Error::GuardViolation.name(), "GUARD_VIOLATION");
+        assert_eq!(Error::Unknown(-1000).name(), "UNKNOWN");
+    }
+}
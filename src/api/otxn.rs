@@ -30,6 +30,14 @@ pub fn otxn_type() -> i64 {
     unsafe { _c::otxn_type() }
 }
 
+/// Get the type of the originating transaction as a [TxnType]
+///
+/// Returns `None` for types not known to this crate, [otxn_type] still returns their code.
+#[inline(always)]
+pub fn otxn_txn_type() -> Option<TxnType> {
+    TxnType::from_code(otxn_type() as u32)
+}
+
 /// Load the originating transaction into a slot
 #[inline(always)]
 pub fn otxn_slot(slot_no: u32) -> Result<i64> {
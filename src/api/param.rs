@@ -0,0 +1,131 @@
+use super::*;
+
+/// Retrieve the parameter value for a named hook parameter
+///
+/// Returns the part of `value` written by the host.
+#[inline(always)]
+pub fn hook_param<'a>(value: &'a mut [u8], name: &[u8]) -> Result<&'a [u8]> {
+    match buf_write_read(value, name, _c::hook_param) {
+        Ok(len) => Ok(&value[..len as usize]),
+        Err(e) => Err(e),
+    }
+}
+
+/// Set or delete a hook parameter for a later hook in the chain
+///
+/// An empty `value` deletes the parameter.
+/// Returns [Error::TooManyParams] if the parameter cannot be added
+/// and [Error::TooBig] if the name or the value is too long.
+#[inline(always)]
+pub fn hook_param_set(value: &[u8], name: &[u8], hook_hash: &Hash) -> Result<i64> {
+    buf_3_read(value, name, hook_hash, _c::hook_param_set)
+}
+
+/// Retrieve the parameter value for a named parameter of the originating transaction
+///
+/// Returns the part of `value` written by the host.
+#[inline(always)]
+pub fn otxn_param<'a>(value: &'a mut [u8], name: &[u8]) -> Result<&'a [u8]> {
+    match buf_write_read(value, name, _c::otxn_param) {
+        Ok(len) => Ok(&value[..len as usize]),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{MockLedger, Payment, TxnScenario};
+
+    const OTHER_HOOK: Hash = [0xAB; 32];
+
+    #[test]
+    fn hook_param_test() {
+        let mut ledger = MockLedger::new();
+        ledger
+            .hook_params
+            .insert(b"limit".to_vec(), b"100".to_vec());
+        ledger
+            .hook_param_overrides
+            .insert(([0; 32], b"mode".to_vec()), b"strict".to_vec());
+
+        let outcome = ledger.run(|| {
+            let mut value = [0u8; 8];
+            assert!(matches!(hook_param(&mut value, b"limit"), Ok(b"100")));
+            // set by an earlier hook of the chain for this one
+            assert!(matches!(hook_param(&mut value, b"mode"), Ok(b"strict")));
+            assert!(matches!(
+                hook_param(&mut value, b"missing"),
+                Err(Error::DoesntExist)
+            ));
+            assert!(matches!(hook_param(&mut value, b""), Err(Error::TooSmall)));
+            assert!(matches!(
+                hook_param(&mut value[..2], b"limit"),
+                Err(Error::TooSmall)
+            ));
+            crate::accept(b"", 0)
+        });
+        assert!(outcome.is_accept());
+    }
+
+    #[test]
+    fn hook_param_set_test() {
+        let mut ledger = MockLedger::new();
+
+        let outcome = ledger.run(|| {
+            assert!(matches!(
+                hook_param_set(b"lenient", b"mode", &OTHER_HOOK),
+                Ok(7)
+            ));
+            assert!(matches!(
+                hook_param_set(&[0; HOOK_PARAM_VALUE_MAX_LEN + 1], b"mode", &OTHER_HOOK),
+                Err(Error::TooBig)
+            ));
+            assert!(matches!(
+                hook_param_set(
+                    b"lenient",
+                    &[b'a'; HOOK_PARAM_NAME_MAX_LEN + 1],
+                    &OTHER_HOOK
+                ),
+                Err(Error::TooBig)
+            ));
+            // only visible to the other hook
+            let mut value = [0u8; 8];
+            assert!(matches!(
+                hook_param(&mut value, b"mode"),
+                Err(Error::DoesntExist)
+            ));
+            crate::accept(b"", 0)
+        });
+        assert!(outcome.is_accept());
+        assert_eq!(
+            ledger
+                .hook_param_overrides
+                .get(&(OTHER_HOOK, b"mode".to_vec())),
+            Some(&b"lenient".to_vec())
+        );
+    }
+
+    #[test]
+    fn otxn_param_test() {
+        let mut ledger = MockLedger::new();
+
+        let outcome = Payment::new()
+            .amount_drops(1_000)
+            .hook_param(b"op", b"deposit")
+            .run(&mut ledger, || {
+                let mut value = [0u8; 16];
+                assert!(matches!(otxn_param(&mut value, b"op"), Ok(b"deposit")));
+                assert!(matches!(
+                    otxn_param(&mut value, b"memo"),
+                    Err(Error::DoesntExist)
+                ));
+                assert!(matches!(
+                    otxn_param(&mut value[..4], b"op"),
+                    Err(Error::TooSmall)
+                ));
+                crate::accept(b"", 0)
+            });
+        assert!(outcome.is_accept());
+    }
+}
@@ -21,7 +21,7 @@ pub fn slot_count(slot_no: u32) -> Result<i64> {
 /// Locate an object based on its keylet and place it into a slot
 #[inline(always)]
 pub fn slot_set(keylet: &[u8], slot_no: u32) -> Result<i64> {
-    let res = unsafe { _c::slot_set(keylet.as_ptr() as u32, keylet.len() as u32, slot_no) };
+    let res = unsafe { _c::slot_set(keylet.as_ptr() as _, keylet.len() as u32, slot_no) };
 
     result_i64(res)
 }
@@ -52,9 +52,10 @@ pub fn slot_type(slot_no: u32, flags: SlotTypeFlags) -> Result<FieldOrXrpAmount>
             let res = unsafe { _c::slot_type(slot_no, 0) };
 
             match res {
-                res if res >= 0 => Ok(FieldOrXrpAmount::Field(unsafe {
-                    core::mem::transmute(res as u32)
-                })),
+                res if res >= 0 => match FieldId::from_code(res as u32) {
+                    Some(field_id) => Ok(FieldOrXrpAmount::Field(field_id)),
+                    None => Ok(FieldOrXrpAmount::UnknownField(res as u32)),
+                },
                 _ => Err(Error::from_code(res as _)),
             }
         }
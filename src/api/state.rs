@@ -17,13 +17,13 @@ pub fn state_set(data: &[u8], key: &[u8]) -> Result<i64> {
 pub fn state_foreign(data: &mut [u8], key: &[u8], namespace: &[u8], accid: &[u8]) -> Result<i64> {
     let res = unsafe {
         _c::state_foreign(
-            data.as_mut_ptr() as u32,
+            data.as_mut_ptr() as _,
             data.len() as u32,
-            key.as_ptr() as u32,
+            key.as_ptr() as _,
             key.len() as u32,
-            namespace.as_ptr() as u32,
+            namespace.as_ptr() as _,
             namespace.len() as u32,
-            accid.as_ptr() as u32,
+            accid.as_ptr() as _,
             accid.len() as u32,
         )
     };
@@ -36,13 +36,13 @@ pub fn state_foreign(data: &mut [u8], key: &[u8], namespace: &[u8], accid: &[u8]
 pub fn state_foreign_set(data: &[u8], key: &[u8], namespace: &[u8], accid: &[u8]) -> Result<i64> {
     let res = unsafe {
         _c::state_foreign_set(
-            data.as_ptr() as u32,
+            data.as_ptr() as _,
             data.len() as u32,
-            key.as_ptr() as u32,
+            key.as_ptr() as _,
             key.len() as u32,
-            namespace.as_ptr() as u32,
+            namespace.as_ptr() as _,
             namespace.len() as u32,
-            accid.as_ptr() as u32,
+            accid.as_ptr() as _,
             accid.len() as u32,
         )
     };
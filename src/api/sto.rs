@@ -3,7 +3,7 @@ use super::*;
 /// Index into a xrpld serialized object and return the location and length of a subfield
 #[inline(always)]
 pub fn sto_subfield(sto: &[u8], field_id: FieldId) -> Result<&[u8]> {
-    let res = unsafe { _c::sto_subfield(sto.as_ptr() as u32, sto.len() as u32, field_id as _) };
+    let res = unsafe { _c::sto_subfield(sto.as_ptr() as _, sto.len() as u32, field_id as _) };
 
     let location = match res {
         res if res >= 0 => res,
@@ -16,7 +16,7 @@ pub fn sto_subfield(sto: &[u8], field_id: FieldId) -> Result<&[u8]> {
 /// Index into a xrpld serialized array and return the location and length of an index
 #[inline(always)]
 pub fn sto_subarray(sto: &[u8], array_id: u32) -> Result<&[u8]> {
-    let res = unsafe { _c::sto_subarray(sto.as_ptr() as u32, sto.len() as u32, array_id) };
+    let res = unsafe { _c::sto_subarray(sto.as_ptr() as _, sto.len() as u32, array_id) };
 
     let location = match res {
         res if res >= 0 => res,
@@ -36,11 +36,11 @@ pub fn sto_emplace(
 ) -> Result<i64> {
     let res = unsafe {
         _c::sto_emplace(
-            sto_out.as_mut_ptr() as u32,
+            sto_out.as_mut_ptr() as _,
             sto_out.len() as u32,
-            sto_src.as_ptr() as u32,
+            sto_src.as_ptr() as _,
             sto_src.len() as u32,
-            field.as_ptr() as u32,
+            field.as_ptr() as _,
             field.len() as u32,
             field_id as _,
         )
@@ -54,9 +54,9 @@ pub fn sto_emplace(
 pub fn sto_erase(sto_out: &mut [u8], sto_src: &[u8], field_id: FieldId) -> Result<i64> {
     let res = unsafe {
         _c::sto_erase(
-            sto_out.as_mut_ptr() as u32,
+            sto_out.as_mut_ptr() as _,
             sto_out.len() as u32,
-            sto_src.as_ptr() as u32,
+            sto_src.as_ptr() as _,
             sto_src.len() as u32,
             field_id as _,
         )
@@ -58,8 +58,8 @@ pub fn util_keylet(keylet: &mut [u8], keylet_type: KeyletType) -> Result<i64> {
                         write_ptr,
                         write_len,
                         _c::KEYLET_SKIP,
-                        ledger_index,
-                        num,
+                        ledger_index as _,
+                        num as _,
                         0,
                         0,
                         0,
@@ -105,6 +105,8 @@ pub fn util_keylet(keylet: &mut [u8], keylet_type: KeyletType) -> Result<i64> {
 
         KeyletType::EmittedDir => all_zeroes(keylet, _c::KEYLET_EMITTED_DIR),
 
+        KeyletType::Ticket(accid, seq) => buf_read_and_1_arg(keylet, accid, seq, _c::KEYLET_TICKET),
+
         KeyletType::Signers(accid) => buf_read_and_zeroes(keylet, accid, _c::KEYLET_SIGNERS),
 
         KeyletType::Check(accid, num) => buf_read_and_1_arg(keylet, accid, num, _c::KEYLET_CHECK),
@@ -133,7 +135,7 @@ pub fn util_keylet(keylet: &mut [u8], keylet_type: KeyletType) -> Result<i64> {
                     accid_1.len() as _,
                     accid_2.as_ptr() as _,
                     accid_2.len() as _,
-                    num,
+                    num as _,
                     0,
                 )
             };
@@ -0,0 +1,194 @@
+use super::*;
+
+/// Slots holding the contents of an XPOP (proof of burn)
+#[derive(Clone, Copy)]
+pub struct XpopSlots {
+    /// Slot holding the burn transaction
+    pub tx: u32,
+    /// Slot holding the metadata of the burn transaction
+    pub meta: u32,
+}
+
+/// Load the transaction and metadata of the XPOP carried by the originating Import transaction into slots
+///
+/// Pass 0 for either slot number to allocate a new slot.
+#[inline(always)]
+pub fn xpop_slot(slot_no_tx: u32, slot_no_meta: u32) -> Result<XpopSlots> {
+    let res = unsafe { _c::xpop_slot(slot_no_tx, slot_no_meta) };
+
+    match res {
+        res if res >= 0 => Ok(XpopSlots {
+            tx: ((res >> 16) & 0xFF) as u32,
+            meta: (res & 0xFF) as u32,
+        }),
+        _ => Err(Error::from_code(res as _)),
+    }
+}
+
+impl XpopSlots {
+    /// Serialize and output the burn transaction
+    #[inline(always)]
+    pub fn tx(&self, tx_out: &mut [u8]) -> Result<i64> {
+        slot(tx_out, self.tx)
+    }
+
+    /// Serialize and output the metadata of the burn transaction
+    #[inline(always)]
+    pub fn meta(&self, meta_out: &mut [u8]) -> Result<i64> {
+        slot(meta_out, self.meta)
+    }
+
+    /// Place a field of the burn transaction into a slot
+    #[inline(always)]
+    pub fn tx_field(&self, field_id: FieldId, new_slot: u32) -> Result<i64> {
+        slot_subfield(self.tx, field_id, new_slot)
+    }
+
+    /// Place a field of the burn transaction metadata into a slot
+    #[inline(always)]
+    pub fn meta_field(&self, field_id: FieldId, new_slot: u32) -> Result<i64> {
+        slot_subfield(self.meta, field_id, new_slot)
+    }
+
+    /// Place the [FieldId::TransactionResult] of the burn transaction into a slot
+    #[inline(always)]
+    pub fn transaction_result(&self, new_slot: u32) -> Result<i64> {
+        self.meta_field(FieldId::TransactionResult, new_slot)
+    }
+
+    /// Iterate over the [FieldId::AffectedNodes] of the burn transaction metadata
+    #[inline(always)]
+    pub fn affected_nodes(&self, array_slot: u32, node_slot: u32) -> Result<AffectedNodes> {
+        affected_nodes(self.meta, array_slot, node_slot)
+    }
+}
+
+/// Output the raw XPOP carried in the [FieldId::Blob] of the originating Import transaction
+///
+/// The ledger header and the validation of an XPOP are not serialized objects
+/// and cannot be slotted, so they are only available from this raw blob.
+#[inline(always)]
+pub fn xpop_blob(blob_out: &mut [u8]) -> Result<&[u8]> {
+    match otxn_field(blob_out, FieldId::Blob) {
+        Ok(len) => Ok(&blob_out[..len as usize]),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sto::Writer;
+    use crate::testing::MockLedger;
+    use std::vec::Vec;
+
+    fn serialize(f: impl FnOnce(&mut Writer)) -> Vec<u8> {
+        let mut buf = [0u8; 128];
+        let mut writer = Writer::new(&mut buf);
+        f(&mut writer);
+        match writer.finish() {
+            Ok(data) => data.to_vec(),
+            Err(e) => panic!("{}", e.code()),
+        }
+    }
+
+    fn ledger() -> MockLedger {
+        let tx = serialize(|tx| {
+            tx.u16(FieldId::TransactionType, TxnType::Payment as u16)
+                .account(FieldId::Account, &[0x11; 20]);
+        });
+        let meta = serialize(|meta| {
+            meta.array(FieldId::AffectedNodes, |_| ())
+                .u8(FieldId::TransactionResult, 0);
+        });
+
+        let mut ledger = MockLedger::new();
+        ledger.xpop = Some((tx, meta));
+        ledger
+    }
+
+    #[test]
+    fn xpop_slot_test() {
+        let mut ledger = ledger();
+
+        let outcome = ledger.run(|| {
+            // allocated in turn
+            assert!(matches!(xpop_slot(0, 0), Ok(XpopSlots { tx: 1, meta: 2 })));
+            // both halves of the result, up to the last slot
+            assert!(matches!(
+                xpop_slot(255, 254),
+                Ok(XpopSlots { tx: 255, meta: 254 })
+            ));
+            assert!(matches!(
+                xpop_slot(3, 255),
+                Ok(XpopSlots { tx: 3, meta: 255 })
+            ));
+            assert!(matches!(xpop_slot(256, 0), Err(Error::InvalidArgument)));
+            accept(b"", 0)
+        });
+        assert!(outcome.is_accept());
+    }
+
+    #[test]
+    fn xpop_fields_test() {
+        let mut ledger = ledger();
+
+        let outcome = ledger.run(|| {
+            let xpop = match xpop_slot(0, 0) {
+                Ok(xpop) => xpop,
+                Err(e) => panic!("{}", e.code()),
+            };
+
+            let mut account = [0u8; 20];
+            let slot_no = match xpop.tx_field(FieldId::Account, 0) {
+                Ok(slot_no) => slot_no as u32,
+                Err(e) => panic!("{}", e.code()),
+            };
+            assert!(matches!(slot(&mut account, slot_no), Ok(20)));
+            assert_eq!(account, [0x11; 20]);
+            assert!(matches!(
+                xpop.tx_field(FieldId::Amount, 0),
+                Err(Error::DoesntExist)
+            ));
+
+            let mut result = [0xFFu8; 1];
+            let slot_no = match xpop.transaction_result(0) {
+                Ok(slot_no) => slot_no as u32,
+                Err(e) => panic!("{}", e.code()),
+            };
+            assert!(matches!(slot(&mut result, slot_no), Ok(1)));
+            assert_eq!(result, [0]);
+            assert!(matches!(xpop.meta_field(FieldId::AffectedNodes, 0), Ok(_)));
+            assert!(matches!(
+                xpop.meta_field(FieldId::Account, 0),
+                Err(Error::DoesntExist)
+            ));
+
+            let mut tx = [0u8; 64];
+            assert!(matches!(xpop.tx(&mut tx), Ok(25)));
+            accept(b"", 0)
+        });
+        assert!(outcome.is_accept());
+    }
+
+    #[test]
+    fn no_xpop_test() {
+        let mut ledger = MockLedger::new();
+
+        let outcome = ledger.run(|| {
+            assert!(matches!(xpop_slot(0, 0), Err(Error::PrerequisiteNotMet)));
+            // nothing was slotted
+            let xpop = XpopSlots { tx: 1, meta: 2 };
+            assert!(matches!(
+                xpop.tx_field(FieldId::Account, 0),
+                Err(Error::DoesntExist)
+            ));
+            assert!(matches!(
+                xpop.transaction_result(0),
+                Err(Error::DoesntExist)
+            ));
+            accept(b"", 0)
+        });
+        assert!(outcome.is_accept());
+    }
+}
@@ -24,7 +24,11 @@
 #![doc(test(attr(deny(warnings))))]
 #![doc(html_root_url = "https://docs.rs/xrpl-hooks/0.6.0")]
 
+#[cfg(any(test, feature = "testing"))]
+extern crate std;
+
 mod macros;
+mod sha256;
 
 /// # Low-level unsafe C bindings
 ///
@@ -37,14 +41,111 @@ pub mod api;
 /// A few utilities
 pub mod helpers;
 
+/// Host-independent XFL implementation
+///
+/// Follows the algorithms of the XFL host functions, so results match
+/// the [api] functions bit for bit. All functions are `const fn` and run natively,
+/// which allows XFL constants and native unit tests.
+/// They have no loops, which a hook could only have with guards, so hooks may also
+/// call them at runtime, the host functions cost less wasm though.
+///
+/// # Example
+///
+/// ``` txt
+/// use rshooks_api::{xfl, XFL};
+///
+/// const FEE_RATE: XFL = match xfl::float_set(-3, 25) {
+///     rshooks_api::Ok(rate) => rate,
+///     rshooks_api::Err(_) => panic!(),
+/// };
+/// ```
+pub mod xfl;
+
+/// Serialized object building and reading
+///
+/// [Writer](sto::Writer) serializes an STObject, such as a transaction to emit,
+/// into a caller-supplied buffer without allocations.
+/// [Reader](sto::Reader) walks the fields of a serialized object without host calls.
+///
+/// # Example
+///
+/// ``` txt
+/// use rshooks_api::sto::Writer;
+///
+/// let mut buf = [0u8; 64];
+/// let mut writer = Writer::new(&mut buf);
+/// writer
+///     .u16(FieldId::TransactionType, TxnType::Invoke as u16)
+///     .account(FieldId::Account, &hook_account);
+/// let txn = writer.finish();
+/// ```
+pub mod sto;
+
+/// Account address encoding
+///
+/// Base58check encoding and decoding of r-addresses and X-addresses without host calls,
+/// so it runs natively and on-chain alike. Loops are guarded, their budget is
+/// [MAX_CONVERSIONS](address::MAX_CONVERSIONS) conversions during a hook execution.
+///
+/// # Example
+///
+/// ``` txt
+/// use rshooks_api::address;
+///
+/// if !address::is_valid(&stored_raddr) {
+///     rollback(b"invalid r-address", 1);
+/// }
+/// ```
+pub mod address;
+
+/// Native host for unit testing hooks
+///
+/// Enabled by the `testing` feature. Every host function of [_c] gets a Rust
+/// implementation backed by a thread-local [MockLedger](testing::MockLedger),
+/// so hooks can be run by `cargo test` on the native target.
+///
+/// Enable it for tests only, so it never reaches the wasm build:
+///
+/// ``` txt
+/// [dev-dependencies]
+/// rshooks-api = { version = "0.6.0", features = ["testing"] }
+/// ```
+///
+/// # Example
+///
+/// ``` txt
+/// use rshooks_api::testing::MockLedger;
+///
+/// #[test]
+/// fn accepts_payments() {
+///     let mut ledger = MockLedger::new();
+///     ledger.otxn = payment_bytes();
+///
+///     let outcome = ledger.run(|| hook(0));
+///
+///     assert_eq!(outcome.message(), b"ok");
+///     assert_eq!(ledger.state(b"count"), Some(&[1u8][..]));
+/// }
+/// ```
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
 // Prelude
 pub use {api::*, helpers::*};
 
-#[cfg(not(test))]
+/// Entry point attributes
+pub use rshooks_api_macros::{cbak, hook};
+
+#[doc(hidden)]
+pub use macros::{
+    decode_hex as __decode_hex, decode_raddr as __decode_raddr, guard_id as __guard_id,
+    hex_len as __hex_len,
+};
+
+#[cfg(not(any(test, feature = "testing")))]
 use core::panic::PanicInfo;
 /// You should use rollback() instead of native panic!() macro
-#[cfg(not(test))]
-#[inline(always)]
+#[cfg(not(any(test, feature = "testing")))]
 #[panic_handler]
 fn panic(_: &PanicInfo<'_>) -> ! {
     loop {}
@@ -1,3 +1,7 @@
+use crate::address::ALPHABET;
+use crate::sha256::sha256;
+use crate::AccountId;
+
 /// Assumes uninitialized as initialized
 ///
 /// Rust doesn't allow to use uninitialized values, but we need them to avoid
@@ -15,3 +19,387 @@ macro_rules! uninit_buf {
         unsafe { ::core::mem::MaybeUninit::uninit().assume_init() }
     };
 }
+
+/// Computes a guard ID from a source location
+///
+/// Used by [guard!] and the loop macros, the top bit is always set
+/// to keep the ID away from small hand-picked IDs.
+#[doc(hidden)]
+pub const fn guard_id(file: &str, line: u32, column: u32) -> u32 {
+    // FNV-1a
+    let bytes = file.as_bytes();
+    let mut hash: u32 = 0x811c9dc5;
+    let mut i = 0;
+    while i < bytes.len() {
+        hash ^= bytes[i] as u32;
+        hash = hash.wrapping_mul(0x01000193);
+        i += 1;
+    }
+    hash ^= line;
+    hash = hash.wrapping_mul(0x01000193);
+    hash ^= column;
+    hash = hash.wrapping_mul(0x01000193);
+
+    (1 << 31) | hash
+}
+
+/// Guard call with a unique guard ID
+///
+/// Promises that the enclosing loop runs at most `maxiter` times in total
+/// during the hook execution. The guard ID is derived from the call location,
+/// like the `GUARD` macro of the C hook API.
+/// It must be the first call in the loop condition, `maxiter` must be a constant.
+///
+/// # Example
+///
+/// ``` txt
+/// let mut i = 0;
+/// while {
+///     guard!(MAXITER);
+///     i < MAXITER
+/// } {
+///     // your code
+///     i += 1;
+/// }
+/// ```
+#[macro_export]
+macro_rules! guard {
+    ($maxiter:expr) => {{
+        const GUARD_ID: u32 = $crate::__guard_id(file!(), line!(), column!());
+        const MAXITER: u32 = ($maxiter) as u32 + 1;
+        $crate::_g(GUARD_ID, MAXITER)
+    }};
+}
+
+/// Guarded `while` loop
+///
+/// Runs `body` while `cond` holds, at most `maxiter` times in total
+/// during the hook execution.
+///
+/// # Example
+///
+/// ``` txt
+/// let mut i = 0;
+/// guarded_while!(32, i < len, {
+///     // your code
+///     i += 1;
+/// });
+/// ```
+#[macro_export]
+macro_rules! guarded_while {
+    ($maxiter:expr, $cond:expr, $body:block) => {
+        while {
+            $crate::guard!($maxiter);
+            $cond
+        } $body
+    };
+}
+
+/// Guarded `for` loop over a range
+///
+/// Runs `body` for each value of `range`, at most `maxiter` times in total
+/// during the hook execution.
+///
+/// # Example
+///
+/// ``` txt
+/// guarded_for!(i in 0..len, 32, {
+///     buf[i] = 0;
+/// });
+/// ```
+#[macro_export]
+macro_rules! guarded_for {
+    ($i:ident in $range:expr, $maxiter:expr, $body:block) => {{
+        let range: ::core::ops::Range<_> = $range;
+        let mut next = range.start;
+        while {
+            $crate::guard!($maxiter);
+            next < range.end
+        } {
+            let $i = next;
+            next += 1;
+            $body
+        }
+    }};
+}
+
+/// Tests two buffers for equality
+///
+/// Compares at most `maxiter` bytes in total during the hook execution,
+/// a constant such as the largest expected buffer length.
+///
+/// # Example
+///
+/// ``` txt
+/// let equal = is_buffer_equal!(currency, EXPECTED_CURRENCY, CURRENCY_CODE_SIZE);
+/// ```
+#[macro_export]
+macro_rules! is_buffer_equal {
+    ($buf_1:expr, $buf_2:expr, $maxiter:expr) => {{
+        let buf_1: &[u8] = &$buf_1[..];
+        let buf_2: &[u8] = &$buf_2[..];
+        let len = buf_1.len();
+        let mut equal = len == buf_2.len();
+        let mut i = 0;
+        $crate::guarded_while!($maxiter, equal && i < len, {
+            equal = buf_1[i] == buf_2[i];
+            i += 1;
+        });
+        equal
+    }};
+}
+
+/// Zeroize a buffer
+///
+/// Zeroizes at most `maxiter` bytes in total during the hook execution,
+/// a constant such as the largest expected buffer length.
+///
+/// # Example
+///
+/// ``` txt
+/// buffer_zeroize!(buf, 32);
+/// ```
+#[macro_export]
+macro_rules! buffer_zeroize {
+    ($buf:expr, $maxiter:expr) => {{
+        let buf: &mut [u8] = &mut $buf[..];
+        let len = buf.len();
+        $crate::guarded_for!(i in 0..len, $maxiter, {
+            buf[i] = 0;
+        });
+    }};
+}
+
+/// Length of the buffer decoded from a hex string, used by [hex!]
+#[doc(hidden)]
+pub const fn hex_len(hex: &str) -> usize {
+    hex.len() / 2
+}
+
+/// Decodes a hex string, used by [hex!]
+#[doc(hidden)]
+pub const fn decode_hex<const N: usize>(hex: &str) -> [u8; N] {
+    let hex = hex.as_bytes();
+    if hex.len() != 2 * N {
+        panic!("hex string of odd length");
+    }
+
+    let mut out = [0; N];
+    let mut i = 0;
+    while i < N {
+        out[i] = match (hex_digit(hex[2 * i]), hex_digit(hex[2 * i + 1])) {
+            (Some(high), Some(low)) => (high << 4) | low,
+            _ => panic!("invalid hex digit"),
+        };
+        i += 1;
+    }
+    out
+}
+
+/// Value of a hex digit, in either case
+pub(crate) const fn hex_digit(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Decodes an r-address, used by [raddr!]
+#[doc(hidden)]
+pub const fn decode_raddr(raddr: &str) -> AccountId {
+    let chars = raddr.as_bytes();
+    if chars.len() < 25 || chars.len() > 35 {
+        panic!("invalid r-address length");
+    }
+
+    // Version byte, account ID and checksum as a big-endian base58 number
+    let mut bytes = [0u8; 25];
+    let mut i = 0;
+    while i < chars.len() {
+        let mut carry = base58_digit(chars[i]) as u32;
+        let mut j = bytes.len();
+        while j > 0 {
+            j -= 1;
+            carry += 58 * bytes[j] as u32;
+            bytes[j] = carry as u8;
+            carry >>= 8;
+        }
+        if carry != 0 {
+            panic!("r-address out of range");
+        }
+        i += 1;
+    }
+
+    // Each leading zero byte is encoded as a leading 'r'
+    let mut zero_chars = 0;
+    while zero_chars < chars.len() && chars[zero_chars] == ALPHABET[0] {
+        zero_chars += 1;
+    }
+    let mut zero_bytes = 0;
+    while zero_bytes < bytes.len() && bytes[zero_bytes] == 0 {
+        zero_bytes += 1;
+    }
+    if zero_chars != zero_bytes || bytes[0] != 0 {
+        panic!("not an account r-address");
+    }
+
+    let mut payload = [0u8; 21];
+    let mut account = [0u8; 20];
+    i = 0;
+    while i < 21 {
+        payload[i] = bytes[i];
+        if i > 0 {
+            account[i - 1] = bytes[i];
+        }
+        i += 1;
+    }
+    let checksum = sha256(&sha256(&payload));
+    if checksum[0] != bytes[21]
+        || checksum[1] != bytes[22]
+        || checksum[2] != bytes[23]
+        || checksum[3] != bytes[24]
+    {
+        panic!("invalid r-address checksum");
+    }
+
+    account
+}
+
+const fn base58_digit(c: u8) -> u8 {
+    let mut digit = 0;
+    while digit < ALPHABET.len() {
+        if ALPHABET[digit] == c {
+            return digit as u8;
+        }
+        digit += 1;
+    }
+    panic!("invalid base58 character")
+}
+
+/// Account ID of an r-address, decoded at compile time
+///
+/// Compilation fails if the r-address is malformed or its checksum is wrong.
+///
+/// # Example
+///
+/// ``` txt
+/// const GENESIS: AccountId = raddr!("rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh");
+/// ```
+#[macro_export]
+macro_rules! raddr {
+    ($raddr:literal) => {{
+        const ACCOUNT_ID: $crate::AccountId = $crate::__decode_raddr($raddr);
+        ACCOUNT_ID
+    }};
+}
+
+/// Buffer of a hex string, decoded at compile time
+///
+/// The buffer holds half as many bytes as the string has digits, either case is accepted.
+/// Compilation fails on any other character.
+///
+/// # Example
+///
+/// ``` txt
+/// const USD: CurrencyCode = hex!("0000000000000000000000005553440000000000");
+/// ```
+#[macro_export]
+macro_rules! hex {
+    ($hex:literal) => {{
+        const BUFFER: [u8; $crate::__hex_len($hex)] = $crate::__decode_hex($hex);
+        BUFFER
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_hex, decode_raddr, guard_id};
+    use crate::testing::MockLedger;
+
+    #[test]
+    fn guard_id_test() {
+        let id = guard_id("src/lib.rs", 10, 5);
+        assert_eq!(id, guard_id("src/lib.rs", 10, 5));
+        assert_ne!(id, guard_id("src/lib.rs", 10, 6));
+        assert_ne!(id, guard_id("src/lib.rs", 11, 5));
+        assert_ne!(id, guard_id("src/main.rs", 10, 5));
+        assert_eq!(id >> 31, 1);
+    }
+
+    #[test]
+    fn buffer_guards_test() {
+        let mut ledger = MockLedger::new();
+
+        let outcome = ledger.run(|| {
+            let mut buf = [1u8; 32];
+            crate::buffer_zeroize!(buf, 32);
+            assert!(crate::is_buffer_equal!(buf, [0u8; 32], 32));
+            crate::accept(b"", 0)
+        });
+        assert!(outcome.is_accept(), "{}", ledger.guard_report());
+
+        for guard in ledger.guards.values() {
+            assert_eq!(guard.max_calls, guard.maxiter, "{}", ledger.guard_report());
+        }
+
+        let outcome = ledger.run(|| {
+            assert!(!crate::is_buffer_equal!([0u8; 16], [0u8; 32], 32));
+            assert!(!crate::is_buffer_equal!([0u8; 32], [1u8; 32], 32));
+            crate::accept(b"", 0)
+        });
+        assert!(outcome.is_accept(), "{}", ledger.guard_report());
+
+        // the bound holds whatever the buffer length
+        let outcome = ledger.run(|| {
+            let mut buf = [1u8; 33];
+            crate::buffer_zeroize!(buf, 32);
+            crate::accept(b"", 0)
+        });
+        assert!(outcome.is_guard_violation());
+
+        let outcome = ledger.run(|| {
+            let equal = crate::is_buffer_equal!([0u8; 33], [0u8; 33], 32);
+            crate::accept(b"", equal as _)
+        });
+        assert!(outcome.is_guard_violation());
+    }
+
+    #[test]
+    fn hex_test() {
+        const HASH: crate::Hash =
+            crate::hex!("00112233445566778899aabbccddeeffFFEEDDCCBBAA99887766554433221100");
+        assert_eq!(HASH[..4], [0x00, 0x11, 0x22, 0x33]);
+        assert_eq!(HASH[28..], [0x33, 0x22, 0x11, 0x00]);
+        assert_eq!(crate::hex!(""), [0u8; 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid hex digit")]
+    fn hex_digit_test() {
+        decode_hex::<1>("0g");
+    }
+
+    #[test]
+    fn raddr_test() {
+        const GENESIS: crate::AccountId = crate::raddr!("rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh");
+        assert_eq!(
+            GENESIS,
+            crate::hex!("B5F762798A53D543A014CAF8B297CFF8F2F937E8")
+        );
+        assert_eq!(crate::raddr!("rrrrrrrrrrrrrrrrrrrrrhoLvTp"), [0; 20]);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid r-address checksum")]
+    fn raddr_checksum_test() {
+        decode_raddr("rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTt");
+    }
+
+    #[test]
+    #[should_panic(expected = "not an account r-address")]
+    fn raddr_leading_zeros_test() {
+        decode_raddr("rrHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh");
+    }
+}
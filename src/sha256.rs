@@ -0,0 +1,162 @@
+//! SHA-256 usable in constants
+
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// Initial hash state
+pub(crate) const H: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// Length of a message of `len` bytes once padded, a multiple of the 64 bytes block
+pub(crate) const fn padded_len(len: usize) -> usize {
+    (len + 9).div_ceil(64) * 64
+}
+
+/// Byte `i` of the message padded to `total` bytes with 0x80, zeroes and its bit length
+const fn padded(data: &[u8], total: usize, i: usize) -> u8 {
+    let len = data.len();
+    if i < len {
+        data[i]
+    } else if i == len {
+        0x80
+    } else if i >= total - 8 {
+        ((len as u64 * 8) >> (8 * (total - 1 - i))) as u8
+    } else {
+        0
+    }
+}
+
+/// Word `i` of the message schedule of the block starting at `block`
+///
+/// The earlier words of the block must already be in `w`.
+pub(crate) const fn schedule(
+    data: &[u8],
+    total: usize,
+    block: usize,
+    w: &[u32; 64],
+    i: usize,
+) -> u32 {
+    if i < 16 {
+        let at = block + i * 4;
+        u32::from_be_bytes([
+            padded(data, total, at),
+            padded(data, total, at + 1),
+            padded(data, total, at + 2),
+            padded(data, total, at + 3),
+        ])
+    } else {
+        let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+        let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+        w[i - 16]
+            .wrapping_add(s0)
+            .wrapping_add(w[i - 7])
+            .wrapping_add(s1)
+    }
+}
+
+/// Compression round `i` with the schedule word `w`
+pub(crate) const fn round(state: [u32; 8], i: usize, w: u32) -> [u32; 8] {
+    let [a, b, c, d, e, f, g, h] = state;
+    let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+    let ch = (e & f) ^ (!e & g);
+    let t1 = h
+        .wrapping_add(s1)
+        .wrapping_add(ch)
+        .wrapping_add(K[i])
+        .wrapping_add(w);
+    let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+    let maj = (a & b) ^ (a & c) ^ (b & c);
+    let t2 = s0.wrapping_add(maj);
+
+    [t1.wrapping_add(t2), a, b, c, d.wrapping_add(t1), e, f, g]
+}
+
+/// Adds the state after the last round of a block to the hash state
+pub(crate) const fn add(h: [u32; 8], state: [u32; 8]) -> [u32; 8] {
+    [
+        h[0].wrapping_add(state[0]),
+        h[1].wrapping_add(state[1]),
+        h[2].wrapping_add(state[2]),
+        h[3].wrapping_add(state[3]),
+        h[4].wrapping_add(state[4]),
+        h[5].wrapping_add(state[5]),
+        h[6].wrapping_add(state[6]),
+        h[7].wrapping_add(state[7]),
+    ]
+}
+
+/// Digest of the final hash state
+pub(crate) const fn digest(h: [u32; 8]) -> [u8; 32] {
+    let [a, b, c, d, e, f, g, hh] = [
+        h[0].to_be_bytes(),
+        h[1].to_be_bytes(),
+        h[2].to_be_bytes(),
+        h[3].to_be_bytes(),
+        h[4].to_be_bytes(),
+        h[5].to_be_bytes(),
+        h[6].to_be_bytes(),
+        h[7].to_be_bytes(),
+    ];
+    [
+        a[0], a[1], a[2], a[3], b[0], b[1], b[2], b[3], c[0], c[1], c[2], c[3], d[0], d[1], d[2],
+        d[3], e[0], e[1], e[2], e[3], f[0], f[1], f[2], f[3], g[0], g[1], g[2], g[3], hh[0], hh[1],
+        hh[2], hh[3],
+    ]
+}
+
+/// SHA-256
+///
+/// Loops are not guarded, use it in constants or natively only.
+/// Guarded code runs the same steps, see [address](crate::address).
+pub(crate) const fn sha256(data: &[u8]) -> [u8; 32] {
+    let total = padded_len(data.len());
+    let mut h = H;
+
+    let mut block = 0;
+    while block < total {
+        let mut w = [0u32; 64];
+        let mut state = h;
+        let mut i = 0;
+        while i < 64 {
+            w[i] = schedule(data, total, block, &w, i);
+            state = round(state, i, w[i]);
+            i += 1;
+        }
+        h = add(h, state);
+        block += 64;
+    }
+
+    digest(h)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sha256;
+    use crate::hex;
+
+    #[test]
+    fn sha256_test() {
+        assert_eq!(
+            sha256(b""),
+            hex!("E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855")
+        );
+        assert_eq!(
+            sha256(b"abc"),
+            hex!("BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD")
+        );
+        // Two blocks, the length doesn't fit after the message
+        assert_eq!(
+            sha256(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+            hex!("248D6A61D20638B8E5C026930C3E6039A33CE45964FF2167F6ECEDD419DB06C1")
+        );
+    }
+}
@@ -0,0 +1,1178 @@
+use crate::api::{
+    AccountId, Amount, CurrencyCode, Drops, Err, Error, FieldId, Hash, Ok, Result, SerializedType,
+    XFL,
+};
+use crate::helpers::is_buffer_equal_20;
+
+const OBJECT_END: u32 = ((SerializedType::STObject as u32) << 16) | 1;
+const ARRAY_END: u32 = ((SerializedType::STArray as u32) << 16) | 1;
+
+/// End marker of an STObject
+pub const OBJECT_END_MARKER: u8 = 0xE1;
+/// End marker of an STArray
+pub const ARRAY_END_MARKER: u8 = 0xF1;
+
+/// Longest VL encoded value
+pub const VL_MAX_LEN: usize = 918744;
+
+/// Most bytes of variable length values all writers copy during a hook execution
+///
+/// Longer values fail with [Error::TooBig] before anything is copied.
+pub const WRITER_MAX_COPY: usize = 64 * 1024;
+
+/// Bytes copied by each iteration of the copy loop of a [Writer]
+const WRITER_CHUNK: usize = 32;
+
+/// Most iterations of each reader loop during a hook execution
+///
+/// Bounds the fields all readers yield, and separately the fields
+/// skipped inside nested objects and the steps of path sets.
+pub const READER_MAX_FIELDS: usize = 256;
+
+/// Canonical STObject serializer over a caller-supplied buffer
+///
+/// Fields must be written in canonical order, that is sorted by type code
+/// and then by field code. Errors are sticky: after the first failure further
+/// writes are ignored and [finish](Writer::finish) returns the error.
+///
+/// Fixed size fields are written without loops. Blobs and serialized fields
+/// are copied 32 bytes at a time by a guarded loop whose budget,
+/// [WRITER_MAX_COPY] bytes, is shared by all writers during a hook execution.
+///
+/// # Errors
+///
+/// * [Error::TooSmall] if the buffer is too small
+/// * [Error::InvalidField] if the field type doesn't match the written value
+/// * [Error::InvalidArgument] if a field is out of canonical order or a value is out of range
+/// * [Error::NotAnObject] if an array element is not an object
+/// * [Error::TooBig] if a blob or serialized field is longer than [WRITER_MAX_COPY]
+///
+/// # Example
+///
+/// ``` txt
+/// let mut buf = [0u8; 256];
+/// let mut writer = sto::Writer::new(&mut buf);
+/// writer
+///     .u16(FieldId::TransactionType, TxnType::Payment as u16)
+///     .u32(FieldId::Flags, TF_CANONICAL)
+///     .u32(FieldId::Sequence, 0)
+///     .u32(FieldId::FirstLedgerSequence, seq + 1)
+///     .u32(FieldId::LastLedgerSequence, seq + 5)
+///     .amount_drops(FieldId::Amount, 1_000)
+///     .amount_drops(FieldId::Fee, fee)
+///     .blob(FieldId::SigningPubKey, &[])
+///     .account(FieldId::Account, &hook_account)
+///     .account(FieldId::Destination, &destination)
+///     .serialized(FieldId::EmitDetails, &details);
+///
+/// let txn = match writer.finish() {
+///     Ok(txn) => txn,
+///     Err(e) => rollback(b"serialize failed", e.code() as _),
+/// };
+/// ```
+pub struct Writer<'buf> {
+    buf: &'buf mut [u8],
+    pos: usize,
+    last: u32,
+    in_array: bool,
+    error: Option<Error>,
+}
+
+impl<'buf> Writer<'buf> {
+    /// Create a writer over a buffer
+    #[inline(always)]
+    pub fn new(buf: &'buf mut [u8]) -> Self {
+        Self {
+            buf,
+            pos: 0,
+            last: 0,
+            in_array: false,
+            error: None,
+        }
+    }
+
+    /// Number of bytes written
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns `true` if nothing was written
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.pos == 0
+    }
+
+    /// Serialized object, or the first error
+    #[inline(always)]
+    pub fn finish(self) -> Result<&'buf [u8]> {
+        match self.error {
+            Some(e) => Err(e),
+            None => Ok(&self.buf[..self.pos]),
+        }
+    }
+
+    /// Write a UInt8 field
+    #[inline(always)]
+    pub fn u8(&mut self, field: FieldId, value: u8) -> &mut Self {
+        if self.header(field, SerializedType::UInt8) {
+            self.put_fixed(&[value]);
+        }
+        self
+    }
+
+    /// Write a UInt16 field
+    #[inline(always)]
+    pub fn u16(&mut self, field: FieldId, value: u16) -> &mut Self {
+        if self.header(field, SerializedType::UInt16) {
+            self.put_fixed(&value.to_be_bytes());
+        }
+        self
+    }
+
+    /// Write a UInt32 field
+    #[inline(always)]
+    pub fn u32(&mut self, field: FieldId, value: u32) -> &mut Self {
+        if self.header(field, SerializedType::UInt32) {
+            self.put_fixed(&value.to_be_bytes());
+        }
+        self
+    }
+
+    /// Write a UInt64 field
+    #[inline(always)]
+    pub fn u64(&mut self, field: FieldId, value: u64) -> &mut Self {
+        if self.header(field, SerializedType::UInt64) {
+            self.put_fixed(&value.to_be_bytes());
+        }
+        self
+    }
+
+    /// Write a Hash128 field
+    #[inline(always)]
+    pub fn hash128(&mut self, field: FieldId, value: &[u8; 16]) -> &mut Self {
+        if self.header(field, SerializedType::Hash128) {
+            self.put_fixed(value);
+        }
+        self
+    }
+
+    /// Write a Hash160 field
+    #[inline(always)]
+    pub fn hash160(&mut self, field: FieldId, value: &[u8; 20]) -> &mut Self {
+        if self.header(field, SerializedType::Hash160) {
+            self.put_fixed(value);
+        }
+        self
+    }
+
+    /// Write a Hash256 field
+    #[inline(always)]
+    pub fn hash256(&mut self, field: FieldId, value: &[u8; 32]) -> &mut Self {
+        if self.header(field, SerializedType::Hash256) {
+            self.put_fixed(value);
+        }
+        self
+    }
+
+    /// Write an AccountID field
+    #[inline(always)]
+    pub fn account(&mut self, field: FieldId, account: &AccountId) -> &mut Self {
+        if self.header(field, SerializedType::AccountID) {
+            self.put_fixed(&[20]);
+            self.put_fixed(account);
+        }
+        self
+    }
+
+    /// Write a native Amount field
+    #[inline(always)]
+    pub fn amount_drops(&mut self, field: FieldId, drops: u64) -> &mut Self {
+        if drops > Drops::MAX.get() {
+            self.fail(Error::InvalidArgument);
+        } else if self.header(field, SerializedType::Amount) {
+            self.put_fixed(&(drops | (1 << 62)).to_be_bytes());
+        }
+        self
+    }
+
+    /// Write an issued currency Amount field
+    #[inline(always)]
+    pub fn amount_iou(
+        &mut self,
+        field: FieldId,
+        value: XFL,
+        currency: &CurrencyCode,
+        issuer: &AccountId,
+    ) -> &mut Self {
+        let raw = value.to_raw();
+        if raw < 0 {
+            self.fail(Error::InvalidFloat);
+        } else if self.header(field, SerializedType::Amount) {
+            // XFL shares the layout of an IOU amount, zero has no sign bit
+            self.put_fixed(&(raw as u64 | (1 << 63)).to_be_bytes());
+            self.put_fixed(currency);
+            self.put_fixed(issuer);
+        }
+        self
+    }
+
+    /// Write an Amount field, native or issued
+    #[inline(always)]
+    pub fn amount(&mut self, field: FieldId, amount: &Amount) -> &mut Self {
+        match amount {
+            Amount::Xrp(drops) => self.amount_drops(field, *drops),
+            Amount::Iou {
+                value,
+                currency,
+                issuer,
+            } => self.amount_iou(field, *value, currency.as_code(), issuer),
+        }
+    }
+
+    /// Write a Blob field
+    #[inline(always)]
+    pub fn blob(&mut self, field: FieldId, data: &[u8]) -> &mut Self {
+        if data.len() > VL_MAX_LEN {
+            self.fail(Error::TooBig);
+        } else if self.header(field, SerializedType::Blob) {
+            self.vl(data.len());
+            self.put(data);
+        }
+        self
+    }
+
+    /// Write an already serialized field, header included, such as the output of
+    /// [etxn_details](crate::etxn_details)
+    #[inline(always)]
+    pub fn serialized(&mut self, field: FieldId, serialized: &[u8]) -> &mut Self {
+        let mut header = [0; 3];
+        let len = encode_header(field as u32, &mut header);
+        let matches = serialized.len() >= len
+            && serialized[0] == header[0]
+            && (len < 2 || serialized[1] == header[1])
+            && (len < 3 || serialized[2] == header[2]);
+        if !matches {
+            self.fail(Error::InvalidField);
+        } else if self.order(field) {
+            self.put(serialized);
+        }
+        self
+    }
+
+    /// Write an STObject field, the fields of which `f` writes
+    #[inline(always)]
+    pub fn object(&mut self, field: FieldId, f: impl FnOnce(&mut Self)) -> &mut Self {
+        let in_array = self.in_array;
+        // array elements are objects, in any order
+        let ok = if in_array {
+            self.in_array = false;
+            self.check_type(field, SerializedType::STObject) && self.write_header(field)
+        } else {
+            self.header(field, SerializedType::STObject)
+        };
+        if ok {
+            self.nested(false, f);
+            self.put_fixed(&[OBJECT_END_MARKER]);
+        }
+        self.in_array = in_array;
+        self
+    }
+
+    /// Write an STArray field, the elements of which `f` writes with [object](Writer::object)
+    #[inline(always)]
+    pub fn array(&mut self, field: FieldId, f: impl FnOnce(&mut Self)) -> &mut Self {
+        if self.header(field, SerializedType::STArray) {
+            self.nested(true, f);
+            self.put_fixed(&[ARRAY_END_MARKER]);
+        }
+        self
+    }
+
+    #[inline(always)]
+    fn nested(&mut self, in_array: bool, f: impl FnOnce(&mut Self)) {
+        let (last, outer) = (self.last, self.in_array);
+        self.last = 0;
+        self.in_array = in_array;
+        f(self);
+        self.last = last;
+        self.in_array = outer;
+    }
+
+    #[inline(always)]
+    fn fail(&mut self, error: Error) {
+        if self.error.is_none() {
+            self.error = Some(error);
+        }
+    }
+
+    #[inline(always)]
+    fn check_type(&mut self, field: FieldId, serialized_type: SerializedType) -> bool {
+        if self.error.is_some() {
+            return false;
+        }
+        if field.type_code() != serialized_type as u32 {
+            self.fail(Error::InvalidField);
+            return false;
+        }
+        true
+    }
+
+    /// Checks canonical order, array elements must be objects
+    #[inline(always)]
+    fn order(&mut self, field: FieldId) -> bool {
+        if self.error.is_some() {
+            return false;
+        }
+        if self.in_array {
+            self.fail(Error::NotAnObject);
+            return false;
+        }
+        if (field as u32) <= self.last {
+            self.fail(Error::InvalidArgument);
+            return false;
+        }
+        self.last = field as u32;
+        true
+    }
+
+    /// Checks the field and writes its header
+    #[inline(always)]
+    fn header(&mut self, field: FieldId, serialized_type: SerializedType) -> bool {
+        self.check_type(field, serialized_type) && self.order(field) && self.write_header(field)
+    }
+
+    #[inline(always)]
+    fn write_header(&mut self, field: FieldId) -> bool {
+        let mut header = [0; 3];
+        let len = encode_header(field as u32, &mut header);
+        match len {
+            1 => self.put_fixed(&[header[0]]),
+            2 => self.put_fixed(&[header[0], header[1]]),
+            _ => self.put_fixed(&header),
+        }
+    }
+
+    #[inline(always)]
+    fn vl(&mut self, len: usize) -> bool {
+        match len {
+            0..=192 => self.put_fixed(&[len as u8]),
+            193..=12480 => {
+                let len = len - 193;
+                self.put_fixed(&[193 + (len >> 8) as u8, len as u8])
+            }
+            _ => {
+                let len = len - 12481;
+                self.put_fixed(&[241 + (len >> 16) as u8, (len >> 8) as u8, len as u8])
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn reserve(&mut self, len: usize) -> bool {
+        if self.error.is_some() {
+            return false;
+        }
+        if self.buf.len() - self.pos < len {
+            self.fail(Error::TooSmall);
+            return false;
+        }
+        true
+    }
+
+    /// Writes a fixed size value, lowered to plain stores
+    #[inline(always)]
+    fn put_fixed<const N: usize>(&mut self, bytes: &[u8; N]) -> bool {
+        if !self.reserve(N) {
+            return false;
+        }
+        let out: &mut [u8; N] = crate::helpers::slice_mut(self.buf, self.pos);
+        *out = *bytes;
+        self.pos += N;
+        true
+    }
+
+    /// Writes a variable length value with a guarded loop over fixed size chunks
+    #[inline(always)]
+    fn put(&mut self, bytes: &[u8]) -> bool {
+        let len = bytes.len();
+        if len > WRITER_MAX_COPY {
+            self.fail(Error::TooBig);
+            return false;
+        }
+        if !self.reserve(len) {
+            return false;
+        }
+
+        let chunks = len / WRITER_CHUNK;
+        let mut i = 0;
+        while {
+            crate::guard!(WRITER_MAX_COPY / WRITER_CHUNK);
+            i < chunks
+        } {
+            self.copy::<WRITER_CHUNK>(bytes, i * WRITER_CHUNK);
+            i += 1;
+        }
+
+        // the remainder, at most 31 bytes, by decreasing powers of two
+        let mut offset = chunks * WRITER_CHUNK;
+        if len - offset >= 16 {
+            self.copy::<16>(bytes, offset);
+            offset += 16;
+        }
+        if len - offset >= 8 {
+            self.copy::<8>(bytes, offset);
+            offset += 8;
+        }
+        if len - offset >= 4 {
+            self.copy::<4>(bytes, offset);
+            offset += 4;
+        }
+        if len - offset >= 2 {
+            self.copy::<2>(bytes, offset);
+            offset += 2;
+        }
+        if len - offset >= 1 {
+            self.copy::<1>(bytes, offset);
+        }
+
+        self.pos += len;
+        true
+    }
+
+    /// Copies `N` bytes at `offset` of `bytes` to the same offset past the written bytes,
+    /// lowered to plain loads and stores
+    #[inline(always)]
+    fn copy<const N: usize>(&mut self, bytes: &[u8], offset: usize) {
+        let out: &mut [u8; N] = crate::helpers::slice_mut(self.buf, self.pos + offset);
+        *out = *crate::helpers::slice(bytes, offset);
+    }
+}
+
+/// Serialized field read by a [Reader]
+#[derive(Clone, Copy)]
+pub struct Field<'a> {
+    id: u32,
+    value: &'a [u8],
+}
+
+impl<'a> Field<'a> {
+    /// Field ID, `type << 16 | field` as in [FieldId]
+    #[inline(always)]
+    pub const fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Field ID as a [FieldId], `None` if not known to this crate
+    #[inline(always)]
+    pub const fn field_id(&self) -> Option<FieldId> {
+        FieldId::from_code(self.id)
+    }
+
+    /// Serialized type code of the field
+    #[inline(always)]
+    pub const fn type_code(&self) -> u32 {
+        self.id >> 16
+    }
+
+    /// Serialized type of the field
+    #[inline(always)]
+    pub const fn serialized_type(&self) -> SerializedType {
+        SerializedType::from_code(self.type_code())
+    }
+
+    /// Returns `true` if this is the field `field`
+    #[inline(always)]
+    pub const fn is(&self, field: FieldId) -> bool {
+        self.id == field as u32
+    }
+
+    /// Value as serialized after the header
+    ///
+    /// VL encoded values keep their length prefix,
+    /// objects and arrays don't include their end marker.
+    #[inline(always)]
+    pub const fn value(&self) -> &'a [u8] {
+        self.value
+    }
+
+    #[inline(always)]
+    fn fixed<const N: usize>(&self, serialized_type: SerializedType) -> Result<&'a [u8; N]> {
+        if self.type_code() != serialized_type as u32 {
+            return Err(Error::InvalidField);
+        }
+        Ok(crate::helpers::slice(self.value, 0))
+    }
+
+    /// Value of a UInt8 field
+    #[inline(always)]
+    pub fn u8(&self) -> Result<u8> {
+        match self.fixed::<1>(SerializedType::UInt8) {
+            Ok(bytes) => Ok(bytes[0]),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Value of a UInt16 field
+    #[inline(always)]
+    pub fn u16(&self) -> Result<u16> {
+        match self.fixed(SerializedType::UInt16) {
+            Ok(bytes) => Ok(u16::from_be_bytes(*bytes)),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Value of a UInt32 field
+    #[inline(always)]
+    pub fn u32(&self) -> Result<u32> {
+        match self.fixed(SerializedType::UInt32) {
+            Ok(bytes) => Ok(u32::from_be_bytes(*bytes)),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Value of a UInt64 field
+    #[inline(always)]
+    pub fn u64(&self) -> Result<u64> {
+        match self.fixed(SerializedType::UInt64) {
+            Ok(bytes) => Ok(u64::from_be_bytes(*bytes)),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Value of a Hash128 field
+    #[inline(always)]
+    pub fn hash128(&self) -> Result<&'a [u8; 16]> {
+        self.fixed(SerializedType::Hash128)
+    }
+
+    /// Value of a Hash160 field
+    #[inline(always)]
+    pub fn hash160(&self) -> Result<&'a [u8; 20]> {
+        self.fixed(SerializedType::Hash160)
+    }
+
+    /// Value of a Hash256 field
+    #[inline(always)]
+    pub fn hash256(&self) -> Result<&'a Hash> {
+        self.fixed(SerializedType::Hash256)
+    }
+
+    /// Value of an AccountID field
+    #[inline(always)]
+    pub fn account(&self) -> Result<&'a AccountId> {
+        if self.type_code() != SerializedType::AccountID as u32 || self.value.len() != 21 {
+            return Err(Error::InvalidField);
+        }
+        Ok(crate::helpers::slice(self.value, 1))
+    }
+
+    /// Value of a Blob field, without the length prefix
+    #[inline(always)]
+    pub fn blob(&self) -> Result<&'a [u8]> {
+        if self.type_code() != SerializedType::Blob as u32 {
+            return Err(Error::InvalidField);
+        }
+        match vl_length(self.value) {
+            Ok((_, prefix)) => Ok(&self.value[prefix..]),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Drops of a native Amount field
+    ///
+    /// Returns [Error::NotAnAmount] for other fields and
+    /// [Error::InvalidField] for issued amounts.
+    #[inline(always)]
+    pub fn drops(&self) -> Result<u64> {
+        if self.type_code() != SerializedType::Amount as u32 {
+            return Err(Error::NotAnAmount);
+        }
+        let raw = u64::from_be_bytes(*crate::helpers::slice(self.value, 0));
+        if raw & (1 << 63) != 0 {
+            return Err(Error::InvalidField);
+        }
+        if raw & (1 << 62) == 0 && raw != 0 {
+            return Err(Error::CantReturnNegative);
+        }
+        Ok(raw & ((1 << 62) - 1))
+    }
+
+    /// Value, currency and issuer of an issued currency Amount field
+    ///
+    /// Returns [Error::NotAnAmount] for other fields and
+    /// [Error::NotIouAmount] for native amounts.
+    #[inline(always)]
+    pub fn iou(&self) -> Result<(XFL, &'a CurrencyCode, &'a AccountId)> {
+        if self.type_code() != SerializedType::Amount as u32 {
+            return Err(Error::NotAnAmount);
+        }
+        if self.value.len() != 48 {
+            return Err(Error::NotIouAmount);
+        }
+        let raw = u64::from_be_bytes(*crate::helpers::slice(self.value, 0)) & !(1 << 63);
+        Ok((
+            XFL::from_raw(raw as i64),
+            crate::helpers::slice(self.value, 8),
+            crate::helpers::slice(self.value, 28),
+        ))
+    }
+
+    /// Native or issued currency amount of an Amount field
+    ///
+    /// Returns [Error::NotAnAmount] for other fields.
+    #[inline(always)]
+    pub fn amount(&self) -> Result<Amount> {
+        if self.type_code() != SerializedType::Amount as u32 {
+            return Err(Error::NotAnAmount);
+        }
+        Amount::from_sto(self.value)
+    }
+
+    /// Reader over the fields of an STObject field
+    #[inline(always)]
+    pub fn object(&self) -> Result<Reader<'a>> {
+        if self.type_code() != SerializedType::STObject as u32 {
+            return Err(Error::NotAnObject);
+        }
+        Ok(Reader::new(self.value))
+    }
+
+    /// Reader over the elements of an STArray field
+    ///
+    /// Each element is an STObject field, read its fields with [object](Field::object).
+    #[inline(always)]
+    pub fn array(&self) -> Result<Reader<'a>> {
+        if self.type_code() != SerializedType::STArray as u32 {
+            return Err(Error::NotAnArray);
+        }
+        Ok(Reader::new(self.value))
+    }
+}
+
+/// Iterator over the fields of a serialized object, or the elements of an array
+///
+/// Decodes fields in pure Rust, without host calls, so it runs natively as well.
+/// Every loop of the reader is guarded, the budget of each loop is
+/// [READER_MAX_FIELDS] iterations shared by all readers during a hook execution.
+/// A malformed field ends the iteration with [Error::ParseError].
+///
+/// # Example
+///
+/// ``` txt
+/// let memos = match sto::Reader::new(txn).find(FieldId::Memos) {
+///     Ok(memos) => memos.array().expect(b"memos"),
+///     Err(_) => accept(b"no memos", 0),
+/// };
+///
+/// for memo in memos {
+///     let memo = memo.expect(b"memo").object().expect(b"memo");
+///     let data = memo.find(FieldId::MemoData).expect(b"memo data").blob();
+/// }
+/// ```
+#[derive(Clone, Copy)]
+pub struct Reader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    /// Create a reader over a serialized object
+    #[inline(always)]
+    pub const fn new(buf: &'a [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    /// First field with a given ID
+    ///
+    /// Returns [Error::DoesntExist] if the object doesn't hold it.
+    #[inline(always)]
+    pub fn find(self, field: FieldId) -> Result<Field<'a>> {
+        for found in self {
+            match found {
+                Ok(found) if found.is(field) => return Ok(found),
+                Ok(_) => (),
+                Err(e) => return Err(e),
+            }
+        }
+        Err(Error::DoesntExist)
+    }
+}
+
+impl<'a> Iterator for Reader<'a> {
+    type Item = Result<Field<'a>>;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        crate::guard!(READER_MAX_FIELDS);
+
+        if self.pos >= self.buf.len() {
+            return None;
+        }
+
+        match read_field(self.buf, self.pos) {
+            Ok((field, end)) => {
+                self.pos = end;
+                Some(Ok(field))
+            }
+            Err(e) => {
+                self.pos = self.buf.len();
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// Decodes a field header, returns the field ID and the header length
+#[inline(always)]
+fn header(buf: &[u8], pos: usize) -> Result<(u32, usize)> {
+    let byte = |i: usize| match buf.get(pos + i) {
+        Some(b) => Ok(*b as u32),
+        None => Err(Error::ParseError),
+    };
+
+    let b0 = match byte(0) {
+        Ok(b) => b,
+        Err(e) => return Err(e),
+    };
+    let (type_code, field_code, len) = match (b0 >> 4, b0 & 0xF) {
+        (0, 0) => match (byte(1), byte(2)) {
+            (Ok(type_code), Ok(field_code)) => (type_code, field_code, 3),
+            _ => return Err(Error::ParseError),
+        },
+        (0, field_code) => match byte(1) {
+            Ok(type_code) => (type_code, field_code, 2),
+            Err(e) => return Err(e),
+        },
+        (type_code, 0) => match byte(1) {
+            Ok(field_code) => (type_code, field_code, 2),
+            Err(e) => return Err(e),
+        },
+        (type_code, field_code) => (type_code, field_code, 1),
+    };
+
+    Ok(((type_code << 16) | field_code, len))
+}
+
+/// Decodes a VL prefix, returns the length and the prefix length
+#[inline(always)]
+fn vl_length(buf: &[u8]) -> Result<(usize, usize)> {
+    let byte = |i: usize| buf.get(i).map(|b| *b as usize).unwrap_or(0);
+    let (len, prefix) = match byte(0) {
+        b0 @ 0..=192 => (b0, 1),
+        b0 @ 193..=240 => (193 + (b0 - 193) * 256 + byte(1), 2),
+        b0 @ 241..=254 => (12481 + (b0 - 241) * 65536 + byte(1) * 256 + byte(2), 3),
+        _ => return Err(Error::ParseError),
+    };
+    if buf.len() < prefix + len {
+        return Err(Error::ParseError);
+    }
+
+    Ok((len, prefix))
+}
+
+/// Length of a value that is neither an object nor an array
+#[inline(always)]
+fn value_length(serialized_type: SerializedType, rest: &[u8]) -> Result<usize> {
+    let len = match serialized_type {
+        SerializedType::STObject | SerializedType::STArray | SerializedType::Unknown => {
+            return Err(Error::ParseError)
+        }
+        SerializedType::Amount => match rest.first() {
+            Some(b) if b & 0x80 == 0 => 8,
+            _ => 48,
+        },
+        // an issue of the native currency has no issuer
+        SerializedType::Issue => {
+            if rest.len() >= 20 && is_buffer_equal_20(rest, &[0; 20]) {
+                20
+            } else {
+                40
+            }
+        }
+        vl if vl.is_vl_encoded() => match vl_length(rest) {
+            Ok((len, prefix)) => prefix + len,
+            Err(e) => return Err(e),
+        },
+        SerializedType::PathSet => {
+            let mut i = 0;
+            while {
+                crate::guard!(READER_MAX_FIELDS);
+                match rest.get(i) {
+                    Some(0x00) => false,
+                    Some(0xFF) => {
+                        i += 1;
+                        true
+                    }
+                    Some(kind) => {
+                        i += 1 + 20 * (kind & 0x31).count_ones() as usize;
+                        true
+                    }
+                    None => return Err(Error::ParseError),
+                }
+            } {}
+            i + 1
+        }
+        fixed => match fixed.fixed_len() {
+            Some(len) => len,
+            None => return Err(Error::ParseError),
+        },
+    };
+    if rest.len() < len {
+        return Err(Error::ParseError);
+    }
+
+    Ok(len)
+}
+
+/// Reads the field at `pos`, returns it and the position after it
+#[inline(always)]
+fn read_field(buf: &[u8], pos: usize) -> Result<(Field<'_>, usize)> {
+    let (id, header_len) = match header(buf, pos) {
+        Ok(header) => header,
+        Err(e) => return Err(e),
+    };
+    let start = pos + header_len;
+    let serialized_type = SerializedType::from_code(id >> 16);
+
+    if !matches!(
+        serialized_type,
+        SerializedType::STObject | SerializedType::STArray
+    ) {
+        return match value_length(serialized_type, &buf[start..]) {
+            Ok(len) => Ok((
+                Field {
+                    id,
+                    value: &buf[start..start + len],
+                },
+                start + len,
+            )),
+            Err(e) => Err(e),
+        };
+    }
+
+    // skips nested fields until the matching end marker
+    let mut depth = 1;
+    let mut end = start;
+    while {
+        crate::guard!(READER_MAX_FIELDS);
+        depth > 0
+    } {
+        let (nested, nested_header) = match header(buf, end) {
+            Ok(header) => header,
+            Err(e) => return Err(e),
+        };
+        end += nested_header;
+        match nested {
+            OBJECT_END | ARRAY_END => depth -= 1,
+            nested => match SerializedType::from_code(nested >> 16) {
+                SerializedType::STObject | SerializedType::STArray => depth += 1,
+                nested => match value_length(nested, &buf[end..]) {
+                    Ok(len) => end += len,
+                    Err(e) => return Err(e),
+                },
+            },
+        }
+    }
+
+    Ok((
+        Field {
+            id,
+            value: &buf[start..end - 1],
+        },
+        end,
+    ))
+}
+
+/// Encodes a field header into `out`, returns its length
+#[inline(always)]
+pub(crate) const fn encode_header(field_id: u32, out: &mut [u8; 3]) -> usize {
+    let type_code = (field_id >> 16) as u8;
+    let field_code = (field_id & 0xFFFF) as u8;
+
+    match (type_code < 16, field_code < 16) {
+        (true, true) => {
+            out[0] = (type_code << 4) | field_code;
+            1
+        }
+        (true, false) => {
+            out[0] = type_code << 4;
+            out[1] = field_code;
+            2
+        }
+        (false, true) => {
+            out[0] = field_code;
+            out[1] = type_code;
+            2
+        }
+        (false, false) => {
+            out[0] = 0;
+            out[1] = type_code;
+            out[2] = field_code;
+            3
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{accept, TxnType};
+    use crate::testing::MockLedger;
+
+    const GENESIS: AccountId = [
+        0xB5, 0xF7, 0x62, 0x79, 0x8A, 0x53, 0xD5, 0x43, 0xA0, 0x14, 0xCA, 0xF8, 0xB2, 0x97, 0xCF,
+        0xF8, 0xF2, 0xF9, 0x37, 0xE8,
+    ];
+
+    fn run(f: impl FnOnce()) {
+        let outcome = MockLedger::new().run(|| {
+            f();
+            accept(b"", 0)
+        });
+        assert!(outcome.is_accept());
+    }
+
+    #[test]
+    fn writer_test() {
+        run(|| {
+            let mut buf = [0u8; 64];
+            let mut writer = Writer::new(&mut buf);
+            writer
+                .u16(FieldId::TransactionType, TxnType::Payment as u16)
+                .amount_drops(FieldId::Amount, 1000)
+                .account(FieldId::Account, &GENESIS);
+
+            match writer.finish() {
+                Ok(txn) => {
+                    assert_eq!(
+                        txn[..12],
+                        [0x12, 0, 0, 0x61, 0x40, 0, 0, 0, 0, 0, 0x03, 0xE8]
+                    );
+                    assert_eq!(txn[12..14], [0x81, 0x14]);
+                    assert_eq!(txn[14..], GENESIS);
+                }
+                Err(_) => panic!(),
+            }
+        });
+    }
+
+    #[test]
+    fn writer_nested_test() {
+        run(|| {
+            let mut buf = [0u8; 64];
+            let mut writer = Writer::new(&mut buf);
+            writer.array(FieldId::Memos, |memos| {
+                memos.object(FieldId::Memo, |memo| {
+                    memo.blob(FieldId::MemoType, b"t")
+                        .blob(FieldId::MemoData, b"d");
+                });
+            });
+
+            match writer.finish() {
+                Ok(sto) => assert_eq!(
+                    sto,
+                    [0xF9, 0xEA, 0x7C, 0x01, b't', 0x7D, 0x01, b'd', 0xE1, 0xF1]
+                ),
+                Err(_) => panic!(),
+            }
+        });
+    }
+
+    #[test]
+    fn writer_errors_test() {
+        run(|| {
+            let mut buf = [0u8; 64];
+            let mut writer = Writer::new(&mut buf);
+            writer
+                .account(FieldId::Account, &GENESIS)
+                .u16(FieldId::TransactionType, 0);
+            assert!(matches!(writer.finish(), Err(Error::InvalidArgument)));
+
+            let mut writer = Writer::new(&mut buf);
+            writer.u32(FieldId::TransactionType, 0);
+            assert!(matches!(writer.finish(), Err(Error::InvalidField)));
+
+            let mut writer = Writer::new(&mut buf);
+            writer.array(FieldId::Memos, |memos| {
+                memos.blob(FieldId::MemoData, b"d");
+            });
+            assert!(matches!(writer.finish(), Err(Error::NotAnObject)));
+
+            let mut writer = Writer::new(&mut buf[..2]);
+            writer.account(FieldId::Account, &GENESIS);
+            assert!(matches!(writer.finish(), Err(Error::TooSmall)));
+        });
+    }
+
+    #[test]
+    fn writer_blob_test() {
+        let mut ledger = MockLedger::new();
+        let data: std::vec::Vec<u8> = (0..5000).map(|i| i as u8).collect();
+
+        let outcome = ledger.run(|| {
+            let mut buf = [0u8; 5008];
+            // every remainder of the chunked copy, and a value of several kilobytes
+            for len in (0..=64).chain([4999, 5000]) {
+                let mut writer = Writer::new(&mut buf);
+                writer.blob(FieldId::Blob, &data[..len]);
+                match writer.finish() {
+                    Ok(sto) => match Reader::new(sto).find(FieldId::Blob) {
+                        Ok(field) => {
+                            assert!(matches!(field.blob(), Ok(blob) if blob == &data[..len]))
+                        }
+                        Err(e) => panic!("{len}: {}", e.code()),
+                    },
+                    Err(e) => panic!("{len}: {}", e.code()),
+                }
+            }
+            accept(b"", 0)
+        });
+        assert!(outcome.is_accept(), "{}", ledger.guard_report());
+
+        // too long values fail before the copy loop
+        let outcome = ledger.run(|| {
+            let data = std::vec![0u8; WRITER_MAX_COPY + 1];
+            let mut buf = std::vec![0u8; WRITER_MAX_COPY + 8];
+            let mut writer = Writer::new(&mut buf);
+            writer.blob(FieldId::Blob, &data);
+            assert!(matches!(writer.finish(), Err(Error::TooBig)));
+            accept(b"", 0)
+        });
+        assert!(outcome.is_accept(), "{}", ledger.guard_report());
+        assert!(ledger.guards.values().all(|guard| guard.calls <= 1));
+    }
+
+    #[test]
+    fn header_test() {
+        let mut out = [0; 3];
+        assert_eq!(encode_header((1 << 16) | 2, &mut out), 1);
+        assert_eq!(out[0], 0x12);
+        assert_eq!(encode_header((2 << 16) | 26, &mut out), 2);
+        assert_eq!(out[..2], [0x20, 26]);
+        assert_eq!(encode_header((16 << 16) | 4, &mut out), 2);
+        assert_eq!(out[..2], [0x04, 16]);
+        assert_eq!(encode_header((16 << 16) | 17, &mut out), 3);
+        assert_eq!(out, [0, 16, 17]);
+    }
+
+    fn get(reader: Reader<'_>, field: FieldId) -> Field<'_> {
+        match reader.find(field) {
+            Ok(field) => field,
+            Err(e) => panic!("{}", e.code()),
+        }
+    }
+
+    #[test]
+    fn reader_test() {
+        let mut buf = [0u8; 128];
+        let mut writer = Writer::new(&mut buf);
+        writer
+            .u16(FieldId::TransactionType, TxnType::Invoke as u16)
+            .u32(FieldId::Sequence, 7)
+            .amount_drops(FieldId::Fee, 12)
+            .account(FieldId::Account, &GENESIS)
+            .array(FieldId::Memos, |memos| {
+                memos
+                    .object(FieldId::Memo, |memo| {
+                        memo.blob(FieldId::MemoData, b"first");
+                    })
+                    .object(FieldId::Memo, |memo| {
+                        memo.blob(FieldId::MemoType, b"t")
+                            .blob(FieldId::MemoData, b"second");
+                    });
+            });
+        let txn = match writer.finish() {
+            Ok(txn) => txn,
+            Err(_) => panic!(),
+        };
+
+        let reader = Reader::new(txn);
+        assert_eq!(reader.count(), 5);
+        assert!(matches!(
+            get(reader, FieldId::TransactionType).u16(),
+            Ok(99)
+        ));
+        assert!(matches!(get(reader, FieldId::Sequence).u32(), Ok(7)));
+        assert!(matches!(get(reader, FieldId::Fee).drops(), Ok(12)));
+        assert!(matches!(
+            get(reader, FieldId::Fee).iou(),
+            Err(Error::NotIouAmount)
+        ));
+        match get(reader, FieldId::Account).account() {
+            Ok(account) => assert_eq!(*account, GENESIS),
+            Err(_) => panic!(),
+        }
+        assert!(matches!(
+            get(reader, FieldId::Sequence).u16(),
+            Err(Error::InvalidField)
+        ));
+        assert!(matches!(
+            reader.find(FieldId::Destination),
+            Err(Error::DoesntExist)
+        ));
+
+        let memos = match get(reader, FieldId::Memos).array() {
+            Ok(memos) => memos,
+            Err(_) => panic!(),
+        };
+        let mut data = std::vec::Vec::new();
+        for memo in memos {
+            let memo = match memo {
+                Ok(memo) => memo,
+                Err(_) => panic!(),
+            };
+            let memo = match memo.object() {
+                Ok(memo) => memo,
+                Err(_) => panic!(),
+            };
+            match get(memo, FieldId::MemoData).blob() {
+                Ok(blob) => data.push(blob),
+                Err(_) => panic!(),
+            }
+        }
+        assert_eq!(data, [&b"first"[..], &b"second"[..]]);
+
+        assert!(matches!(
+            Reader::new(&txn[..txn.len() - 1]).last(),
+            Some(Err(Error::ParseError))
+        ));
+    }
+
+    #[test]
+    fn reader_matches_host_test() {
+        use crate::testing::{Payment, TxnScenario};
+
+        let txn = Payment::new()
+            .account(&GENESIS)
+            .destination(&GENESIS)
+            .amount_drops(1000)
+            .memo(b"text", b"hello")
+            .to_bytes();
+
+        run(|| {
+            for field in Reader::new(&txn) {
+                let field = match field {
+                    Ok(field) => field,
+                    Err(_) => panic!(),
+                };
+                let id = match field.field_id() {
+                    Some(id) => id,
+                    None => panic!("{}", field.id()),
+                };
+                assert_eq!(id.serialized_type() as u32, field.type_code());
+                match crate::api::sto_subfield(&txn, id) {
+                    Ok(found) => {
+                        let expected = if field.serialized_type().is_vl_encoded() {
+                            &field.value()[1..]
+                        } else {
+                            field.value()
+                        };
+                        assert_eq!(found, expected);
+                    }
+                    Err(_) => panic!(),
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,1520 @@
+//! Native implementations of the host functions
+//!
+//! All of them are `C-unwind`: `accept`, `rollback` and guard violations unwind to
+//! [MockLedger::run], and a call outside of it panics instead of aborting the test binary.
+
+#![allow(missing_docs, clippy::missing_safety_doc, clippy::too_many_arguments)]
+
+use super::{
+    exit,
+    hash::{sha256, sha512_half},
+    sto::{self, Field},
+    try_with_ledger, with_ledger, HookOutcome, MockLedger, Slot, SLOT_COUNT,
+};
+use crate::_c::{self, Ptr};
+use crate::address::ALPHABET;
+use crate::api::{Error, ExecutionContext, StateKey, XFL};
+use crate::xfl;
+use core::cmp::Ordering;
+use std::{format, string::String, vec::Vec};
+
+/// Field code of a slotted transaction
+const SF_TRANSACTION: u32 = (10001 << 16) | 1;
+/// Field code of a slotted ledger object
+const SF_LEDGER_ENTRY: u32 = (10002 << 16) | 1;
+
+/// Largest hook state entry
+const STATE_MAX_LEN: usize = 256;
+/// Largest number of transactions a hook can reserve
+const ETXN_MAX: u32 = 255;
+/// Largest number of nonces a hook can generate
+const NONCE_MAX: u32 = 255;
+/// Size of sfEmitDetails produced by etxn_details
+const EMIT_DETAILS_LEN: usize = 138;
+
+unsafe fn read<'a>(ptr: Ptr, len: u32) -> &'a [u8] {
+    if len == 0 {
+        &[]
+    } else {
+        core::slice::from_raw_parts(ptr as *const u8, len as usize)
+    }
+}
+
+unsafe fn write(ptr: Ptr, len: u32, data: &[u8]) -> i64 {
+    if (len as usize) < data.len() {
+        return _c::TOO_SMALL as i64;
+    }
+    if !data.is_empty() {
+        core::ptr::copy(data.as_ptr(), ptr as *mut u8, data.len());
+    }
+
+    data.len() as i64
+}
+
+/// Packs a location inside a buffer as returned by the sto functions
+fn location(range: core::ops::Range<usize>) -> i64 {
+    ((range.start as i64) << 32) | (range.end - range.start) as i64
+}
+
+/// Returns up to 8 bytes as a big-endian integer, as for a zero length write
+fn as_int(data: &[u8]) -> i64 {
+    if data.len() > 8 {
+        return _c::TOO_BIG as i64;
+    }
+
+    data.iter().fold(0, |acc, b| (acc << 8) | *b as i64)
+}
+
+fn result(res: crate::api::Result<XFL>) -> i64 {
+    match res {
+        crate::api::Ok(float) => float.to_raw(),
+        crate::api::Err(e) => e.code() as i64,
+    }
+}
+
+/// Left-pads a hook state key to 32 bytes
+pub(crate) fn state_key(key: &[u8]) -> Option<StateKey> {
+    if key.is_empty() || key.len() > 32 {
+        return None;
+    }
+    let mut padded = [0; 32];
+    padded[32 - key.len()..].copy_from_slice(key);
+
+    Some(padded)
+}
+
+fn txn_id(txn: &[u8]) -> [u8; 32] {
+    let mut data = b"TXN\0".to_vec();
+    data.extend_from_slice(txn);
+
+    sha512_half(&data)
+}
+
+fn xfl_to_f64(float: i64) -> f64 {
+    let float = XFL::from_raw(float);
+    let value = xfl::mantissa(float) as f64 * 10f64.powi(xfl::exponent(float));
+
+    if xfl::is_negative(float) {
+        -value
+    } else {
+        value
+    }
+}
+
+fn xfl_from_f64(value: f64) -> i64 {
+    if value == 0.0 {
+        return 0;
+    }
+    let exponent = value.abs().log10().floor() as i32 - 15;
+    let mantissa = (value.abs() / 10f64.powi(exponent)).round() as i64;
+
+    match xfl::float_set(exponent, mantissa) {
+        crate::api::Ok(float) if value < 0.0 => result(xfl::float_negate(float)),
+        res => result(res),
+    }
+}
+
+/// Converts a serialized amount value into an XFL
+fn amount_to_xfl(amount: &[u8]) -> i64 {
+    if amount.len() < 8 {
+        return _c::NOT_AN_AMOUNT as i64;
+    }
+    let mut bytes = [0; 8];
+    bytes.copy_from_slice(&amount[..8]);
+    let raw = u64::from_be_bytes(bytes);
+
+    if raw >> 63 == 1 {
+        // IOU amounts share the XFL layout below the top bit
+        return (raw & !(1 << 63)) as i64;
+    }
+
+    let drops = (raw & ((1 << 62) - 1)) as i64;
+    match xfl::float_set(0, drops) {
+        crate::api::Ok(float) if raw >> 62 == 0 => result(xfl::float_negate(float)),
+        res => result(res),
+    }
+}
+
+fn find(buf: &[u8], field_id: u32) -> Result<Field, i64> {
+    match sto::find(buf, field_id) {
+        Ok(Some(field)) => Ok(field),
+        Ok(None) => Err(_c::DOESNT_EXIST as i64),
+        Err(_) => Err(_c::PARSE_ERROR as i64),
+    }
+}
+
+fn find_param(params: &[u8], name: &[u8]) -> Option<Vec<u8>> {
+    let elements = sto::fields(params).ok()?;
+
+    elements.into_iter().find_map(|element| {
+        let object = &params[element.value];
+        let param_name = sto::find(object, _c::sfHookParameterName).ok()??;
+        if &object[param_name.payload] != name {
+            return None;
+        }
+        match sto::find(object, _c::sfHookParameterValue).ok()? {
+            Some(value) => Some(object[value.payload].to_vec()),
+            None => Some(Vec::new()),
+        }
+    })
+}
+
+impl MockLedger {
+    fn otxn_id(&self) -> [u8; 32] {
+        txn_id(&self.otxn)
+    }
+
+    fn slot(&self, slot_no: u32) -> Result<&Slot, i64> {
+        match self.slots.get((slot_no as usize).wrapping_sub(1)) {
+            Some(Some(slot)) => Ok(slot),
+            _ => Err(_c::DOESNT_EXIST as i64),
+        }
+    }
+
+    fn slot_into(&mut self, slot_no: u32, slot: Slot) -> i64 {
+        let index = if slot_no == 0 {
+            match self.slots.iter().position(Option::is_none) {
+                Some(index) => index,
+                None => return _c::NO_FREE_SLOTS as i64,
+            }
+        } else if slot_no as usize > SLOT_COUNT {
+            return _c::INVALID_ARGUMENT as i64;
+        } else {
+            slot_no as usize - 1
+        };
+        self.slots[index] = Some(slot);
+
+        index as i64 + 1
+    }
+
+    fn nonce(&self, prefix: &[u8], counter: u32) -> [u8; 32] {
+        let mut data = prefix.to_vec();
+        data.extend_from_slice(&self.otxn_id());
+        data.extend_from_slice(&self.hook_hash);
+        data.extend_from_slice(&counter.to_be_bytes());
+
+        sha512_half(&data)
+    }
+
+    fn hook_param(&self, name: &[u8]) -> Option<Vec<u8>> {
+        if let Some(value) = self
+            .hook_param_overrides
+            .get(&(self.hook_hash, name.to_vec()))
+        {
+            return Some(value.clone());
+        }
+
+        self.hook_params.get(name).cloned()
+    }
+}
+
+fn check_param_name(name: &[u8]) -> Result<(), i64> {
+    match name.len() {
+        0 => Err(_c::TOO_SMALL as i64),
+        len if len > crate::api::HOOK_PARAM_NAME_MAX_LEN => Err(_c::TOO_BIG as i64),
+        _ => Ok(()),
+    }
+}
+
+macro_rules! try_host {
+    ($e:expr) => {
+        match $e {
+            Ok(value) => value,
+            Err(code) => return code,
+        }
+    };
+}
+
+// Control
+
+pub unsafe extern "C-unwind" fn _g(guard_id: u32, maxiter: u32) -> i32 {
+    // outside of a run guards are not counted, so guarded code also runs in plain tests
+    let violation = try_with_ledger(|ledger| {
+        let guard = ledger.guards.entry(guard_id).or_default();
+        guard.maxiter = maxiter;
+        guard.calls += 1;
+        guard.max_calls = guard.max_calls.max(guard.calls);
+        guard.calls > maxiter
+    });
+
+    if violation == Some(true) {
+        exit(HookOutcome::Rollback {
+            message: format!("guard violation: {guard_id:#010x}").into_bytes(),
+            code: Error::GuardViolation.code() as i64,
+        })
+    }
+
+    1
+}
+
+pub unsafe extern "C-unwind" fn accept(read_ptr: Ptr, read_len: u32, error_code: i64) -> i64 {
+    exit(HookOutcome::Accept {
+        message: read(read_ptr, read_len).to_vec(),
+        code: error_code,
+    })
+}
+
+pub unsafe extern "C-unwind" fn rollback(read_ptr: Ptr, read_len: u32, error_code: i64) -> i64 {
+    exit(HookOutcome::Rollback {
+        message: read(read_ptr, read_len).to_vec(),
+        code: error_code,
+    })
+}
+
+// Utilities
+
+pub unsafe extern "C-unwind" fn util_raddr(
+    write_ptr: Ptr,
+    write_len: u32,
+    read_ptr: Ptr,
+    read_len: u32,
+) -> i64 {
+    let accid = read(read_ptr, read_len);
+    if accid.len() != 20 {
+        return _c::INVALID_ARGUMENT as i64;
+    }
+
+    let mut payload = std::vec![0u8];
+    payload.extend_from_slice(accid);
+    let checksum = sha256(&sha256(&payload));
+    payload.extend_from_slice(&checksum[..4]);
+
+    let mut digits: Vec<u8> = Vec::new();
+    for byte in &payload {
+        let mut carry = *byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+    let zeros = payload.iter().take_while(|b| **b == 0).count();
+    let raddr: Vec<u8> = core::iter::repeat_n(ALPHABET[0], zeros)
+        .chain(digits.iter().rev().map(|d| ALPHABET[*d as usize]))
+        .collect();
+
+    write(write_ptr, write_len, &raddr)
+}
+
+pub unsafe extern "C-unwind" fn util_accid(
+    write_ptr: Ptr,
+    write_len: u32,
+    read_ptr: Ptr,
+    read_len: u32,
+) -> i64 {
+    if write_len < 20 {
+        return _c::TOO_SMALL as i64;
+    }
+    let raddr = read(read_ptr, read_len);
+    if raddr.len() > 49 {
+        return _c::TOO_BIG as i64;
+    }
+
+    let mut bytes: Vec<u8> = Vec::new();
+    for c in raddr {
+        let mut carry = match ALPHABET.iter().position(|a| a == c) {
+            Some(value) => value as u32,
+            None => return _c::INVALID_ARGUMENT as i64,
+        };
+        for byte in bytes.iter_mut() {
+            carry += *byte as u32 * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+    let zeros = raddr.iter().take_while(|c| **c == ALPHABET[0]).count();
+    let decoded: Vec<u8> = core::iter::repeat_n(0, zeros)
+        .chain(bytes.iter().rev().copied())
+        .collect();
+
+    if decoded.len() != 25 || decoded[0] != 0 {
+        return _c::INVALID_ARGUMENT as i64;
+    }
+    if sha256(&sha256(&decoded[..21]))[..4] != decoded[21..] {
+        return _c::INVALID_ARGUMENT as i64;
+    }
+
+    write(write_ptr, write_len, &decoded[1..21])
+}
+
+pub unsafe extern "C-unwind" fn util_verify(
+    dread_ptr: Ptr,
+    dread_len: u32,
+    sread_ptr: Ptr,
+    sread_len: u32,
+    kread_ptr: Ptr,
+    kread_len: u32,
+) -> i64 {
+    let payload = read(dread_ptr, dread_len);
+    let signature = read(sread_ptr, sread_len);
+    let key = read(kread_ptr, kread_len);
+    if key.len() != 33 {
+        return _c::INVALID_KEY as i64;
+    }
+
+    with_ledger(|ledger| {
+        ledger
+            .valid_signatures
+            .iter()
+            .any(|(p, s, k)| p == payload && s == signature && k == key) as i64
+    })
+}
+
+pub unsafe extern "C-unwind" fn util_sha512h(
+    write_ptr: Ptr,
+    write_len: u32,
+    read_ptr: Ptr,
+    read_len: u32,
+) -> i64 {
+    if write_len < 32 {
+        return _c::TOO_SMALL as i64;
+    }
+
+    write(write_ptr, write_len, &sha512_half(read(read_ptr, read_len)))
+}
+
+fn index(namespace: u8, parts: &[&[u8]]) -> [u8; 32] {
+    let mut data = std::vec![0, namespace];
+    for part in parts {
+        data.extend_from_slice(part);
+    }
+
+    sha512_half(&data)
+}
+
+fn keylet(entry_type: u16, key: [u8; 32]) -> [u8; 34] {
+    let mut keylet = [0; 34];
+    keylet[..2].copy_from_slice(&entry_type.to_be_bytes());
+    keylet[2..].copy_from_slice(&key);
+
+    keylet
+}
+
+pub unsafe extern "C-unwind" fn util_keylet(
+    write_ptr: Ptr,
+    write_len: u32,
+    keylet_type: u32,
+    a: Ptr,
+    b: Ptr,
+    c: Ptr,
+    d: Ptr,
+    e: Ptr,
+    f: Ptr,
+) -> i64 {
+    if write_len < 34 {
+        return _c::TOO_SMALL as i64;
+    }
+
+    let buf = |ptr: Ptr, len: Ptr, expected: usize| {
+        let buf = read(ptr, len as u32);
+        if buf.len() == expected {
+            Ok(buf)
+        } else {
+            Err(_c::INVALID_ARGUMENT as i64)
+        }
+    };
+    let seq = |seq: Ptr| (seq as u32).to_be_bytes();
+
+    let keylet = match keylet_type {
+        _c::KEYLET_HOOK => keylet(0x48, index(b'H', &[try_host!(buf(a, b, 20))])),
+        _c::KEYLET_HOOK_STATE => keylet(
+            0x76,
+            index(
+                b'v',
+                &[
+                    try_host!(buf(a, b, 20)),
+                    try_host!(buf(c, d, 32)),
+                    try_host!(buf(e, f, 32)),
+                ],
+            ),
+        ),
+        _c::KEYLET_ACCOUNT => keylet(0x61, index(b'a', &[try_host!(buf(a, b, 20))])),
+        _c::KEYLET_AMENDMENTS => keylet(0x66, index(b'f', &[])),
+        _c::KEYLET_CHILD => {
+            let mut key = [0; 32];
+            key.copy_from_slice(try_host!(buf(a, b, 32)));
+            keylet(0x1CD2, key)
+        }
+        _c::KEYLET_SKIP if a == 0 && b == 0 => keylet(0x68, index(b's', &[])),
+        _c::KEYLET_SKIP => keylet(0x68, index(b's', &[&seq(a >> 16)])),
+        _c::KEYLET_FEES => keylet(0x73, index(b'e', &[])),
+        _c::KEYLET_NEGATIVE_UNL => keylet(0x4E, index(b'N', &[])),
+        _c::KEYLET_LINE => {
+            let high = try_host!(buf(a, b, 20));
+            let low = try_host!(buf(c, d, 20));
+            let currency = try_host!(buf(e, f, 20));
+            let (first, second) = if high < low { (high, low) } else { (low, high) };
+            keylet(0x72, index(b'r', &[first, second, currency]))
+        }
+        _c::KEYLET_OFFER => keylet(0x6F, index(b'o', &[try_host!(buf(a, b, 20)), &seq(c)])),
+        _c::KEYLET_QUALITY => {
+            let base = try_host!(buf(a, b, 34));
+            if base[..2] != [0, 0x64] {
+                return _c::INVALID_ARGUMENT as i64;
+            }
+            let mut key = [0; 32];
+            key.copy_from_slice(&base[2..]);
+            key[24..].copy_from_slice(&(((c as u64) << 32) | d as u32 as u64).to_be_bytes());
+            keylet(0x64, key)
+        }
+        _c::KEYLET_EMITTED_DIR => keylet(0x64, index(b'F', &[])),
+        _c::KEYLET_TICKET => keylet(0x54, index(b'T', &[try_host!(buf(a, b, 20)), &seq(c)])),
+        _c::KEYLET_SIGNERS => keylet(0x53, index(b'S', &[try_host!(buf(a, b, 20)), &seq(0)])),
+        _c::KEYLET_CHECK => keylet(0x43, index(b'C', &[try_host!(buf(a, b, 20)), &seq(c)])),
+        _c::KEYLET_DEPOSIT_PREAUTH => keylet(
+            0x70,
+            index(b'p', &[try_host!(buf(a, b, 20)), try_host!(buf(c, d, 20))]),
+        ),
+        _c::KEYLET_UNCHECKED => {
+            let mut key = [0; 32];
+            key.copy_from_slice(try_host!(buf(a, b, 32)));
+            keylet(0, key)
+        }
+        _c::KEYLET_OWNER_DIR => keylet(0x64, index(b'O', &[try_host!(buf(a, b, 20))])),
+        _c::KEYLET_PAGE => {
+            let mut root = [0; 32];
+            root.copy_from_slice(try_host!(buf(a, b, 32)));
+            match ((c as u64) << 32) | d as u32 as u64 {
+                0 => keylet(0x64, root),
+                page => keylet(0x64, index(b'd', &[&root, &page.to_be_bytes()])),
+            }
+        }
+        _c::KEYLET_ESCROW => keylet(0x75, index(b'u', &[try_host!(buf(a, b, 20)), &seq(c)])),
+        _c::KEYLET_PAYCHAN => keylet(
+            0x78,
+            index(
+                b'x',
+                &[try_host!(buf(a, b, 20)), try_host!(buf(c, d, 20)), &seq(e)],
+            ),
+        ),
+        _c::KEYLET_EMITTED => keylet(0x45, index(b'E', &[try_host!(buf(a, b, 32))])),
+        _c::KEYLET_NFT_OFFER => keylet(0x37, index(b'q', &[try_host!(buf(a, b, 20)), &seq(c)])),
+        _c::KEYLET_HOOK_DEFINITION => keylet(0x44, index(b'D', &[try_host!(buf(a, b, 32))])),
+        _c::KEYLET_HOOK_STATE_DIR => keylet(
+            0x64,
+            index(b'J', &[try_host!(buf(a, b, 20)), try_host!(buf(c, d, 32))]),
+        ),
+        _ => return _c::NO_SUCH_KEYLET as i64,
+    };
+
+    write(write_ptr, write_len, &keylet)
+}
+
+// Serialized objects
+
+pub unsafe extern "C-unwind" fn sto_validate(tread_ptr: Ptr, tread_len: u32) -> i64 {
+    let sto = read(tread_ptr, tread_len);
+    if sto.is_empty() {
+        return _c::TOO_SMALL as i64;
+    }
+
+    sto::fields(sto).is_ok() as i64
+}
+
+pub unsafe extern "C-unwind" fn sto_subfield(read_ptr: Ptr, read_len: u32, field_id: u32) -> i64 {
+    let field = try_host!(find(read(read_ptr, read_len), field_id));
+
+    location(field.payload)
+}
+
+pub unsafe extern "C-unwind" fn sto_subarray(read_ptr: Ptr, read_len: u32, array_id: u32) -> i64 {
+    let elements = match sto::fields(read(read_ptr, read_len)) {
+        Ok(elements) => elements,
+        Err(_) => return _c::PARSE_ERROR as i64,
+    };
+
+    match elements.get(array_id as usize) {
+        Some(element) => location(element.whole.clone()),
+        None => _c::DOESNT_EXIST as i64,
+    }
+}
+
+unsafe fn sto_replace(
+    write_ptr: Ptr,
+    write_len: u32,
+    sto_src: &[u8],
+    field: Option<&[u8]>,
+    field_id: u32,
+) -> i64 {
+    let fields = match sto::fields(sto_src) {
+        Ok(fields) => fields,
+        Err(_) => return _c::PARSE_ERROR as i64,
+    };
+    if field.is_none() && !fields.iter().any(|f| f.id == field_id) {
+        return _c::DOESNT_EXIST as i64;
+    }
+
+    let mut out = Vec::with_capacity(sto_src.len() + field.map_or(0, <[u8]>::len));
+    let mut inserted = false;
+    for f in &fields {
+        if !inserted && f.id >= field_id {
+            out.extend_from_slice(field.unwrap_or_default());
+            inserted = true;
+        }
+        if f.id != field_id {
+            out.extend_from_slice(&sto_src[f.whole.clone()]);
+        }
+    }
+    if !inserted {
+        out.extend_from_slice(field.unwrap_or_default());
+    }
+
+    write(write_ptr, write_len, &out)
+}
+
+pub unsafe extern "C-unwind" fn sto_emplace(
+    write_ptr: Ptr,
+    write_len: u32,
+    sread_ptr: Ptr,
+    sread_len: u32,
+    fread_ptr: Ptr,
+    fread_len: u32,
+    field_id: u32,
+) -> i64 {
+    let sto_src = read(sread_ptr, sread_len);
+    let field = read(fread_ptr, fread_len);
+    if sto_src.len() > 16 * 1024 || field.len() > 4 * 1024 {
+        return _c::TOO_BIG as i64;
+    }
+    if field.is_empty() {
+        return sto_replace(write_ptr, write_len, sto_src, None, field_id);
+    }
+
+    match sto::field_at(field, 0) {
+        Ok(parsed) if parsed.id == field_id && parsed.whole.end == field.len() => {
+            sto_replace(write_ptr, write_len, sto_src, Some(field), field_id)
+        }
+        _ => _c::PARSE_ERROR as i64,
+    }
+}
+
+pub unsafe extern "C-unwind" fn sto_erase(
+    write_ptr: Ptr,
+    write_len: u32,
+    read_ptr: Ptr,
+    read_len: u32,
+    field_id: u32,
+) -> i64 {
+    sto_replace(
+        write_ptr,
+        write_len,
+        read(read_ptr, read_len),
+        None,
+        field_id,
+    )
+}
+
+// Emitted transactions
+
+pub unsafe extern "C-unwind" fn etxn_burden() -> i64 {
+    with_ledger(|ledger| match ledger.etxn_reserved {
+        Some(count) => ledger.otxn_burden.saturating_mul(count as i64),
+        None => _c::PREREQUISITE_NOT_MET as i64,
+    })
+}
+
+pub unsafe extern "C-unwind" fn etxn_details(write_ptr: Ptr, write_len: u32) -> i64 {
+    if (write_len as usize) < EMIT_DETAILS_LEN {
+        return _c::TOO_SMALL as i64;
+    }
+
+    let details = with_ledger(|ledger| {
+        let count = match ledger.etxn_reserved {
+            Some(count) => count,
+            None => return Err(_c::PREREQUISITE_NOT_MET as i64),
+        };
+        if ledger.etxn_nonces >= NONCE_MAX {
+            return Err(_c::TOO_MANY_NONCES as i64);
+        }
+        ledger.etxn_nonces += 1;
+        let nonce = ledger.nonce(b"ETXN", ledger.etxn_nonces);
+
+        let mut details = sto::encode_header(_c::sfEmitDetails);
+        let mut field = |field_id: u32, value: &[u8]| {
+            details.extend(sto::encode_header(field_id));
+            details.extend_from_slice(value);
+        };
+        field(
+            _c::sfEmitGeneration,
+            &(ledger.otxn_generation as u32 + 1).to_be_bytes(),
+        );
+        field(
+            _c::sfEmitBurden,
+            &((ledger.otxn_burden as u64).saturating_mul(count as u64)).to_be_bytes(),
+        );
+        field(_c::sfEmitParentTxnID, &ledger.otxn_id());
+        field(_c::sfEmitNonce, &nonce);
+        field(_c::sfEmitHookHash, &ledger.hook_hash);
+        let mut callback = std::vec![20];
+        callback.extend_from_slice(&ledger.hook_account);
+        field(_c::sfEmitCallback, &callback);
+        details.push(0xE1);
+
+        Ok(details)
+    });
+
+    write(write_ptr, write_len, &try_host!(details))
+}
+
+pub unsafe extern "C-unwind" fn etxn_fee_base(read_ptr: Ptr, read_len: u32) -> i64 {
+    let tx = read(read_ptr, read_len);
+
+    with_ledger(|ledger| {
+        if ledger.etxn_reserved.is_none() {
+            return _c::PREREQUISITE_NOT_MET as i64;
+        }
+        match sto::fields(tx) {
+            Ok(_) => ledger.fee_base,
+            Err(_) => _c::INVALID_TXN as i64,
+        }
+    })
+}
+
+pub unsafe extern "C-unwind" fn etxn_reserve(count: u32) -> i64 {
+    with_ledger(|ledger| match count {
+        _ if ledger.etxn_reserved.is_some() => _c::ALREADY_SET as i64,
+        0 => _c::TOO_SMALL as i64,
+        count if count > ETXN_MAX => _c::TOO_BIG as i64,
+        count => {
+            ledger.etxn_reserved = Some(count);
+            count as i64
+        }
+    })
+}
+
+pub unsafe extern "C-unwind" fn etxn_generation() -> i64 {
+    with_ledger(|ledger| ledger.otxn_generation + 1)
+}
+
+pub unsafe extern "C-unwind" fn etxn_nonce(write_ptr: Ptr, write_len: u32) -> i64 {
+    if write_len < 32 {
+        return _c::TOO_SMALL as i64;
+    }
+
+    let nonce = with_ledger(|ledger| {
+        if ledger.etxn_nonces >= NONCE_MAX {
+            return Err(_c::TOO_MANY_NONCES as i64);
+        }
+        ledger.etxn_nonces += 1;
+
+        Ok(ledger.nonce(b"ETXN", ledger.etxn_nonces))
+    });
+
+    write(write_ptr, write_len, &try_host!(nonce))
+}
+
+pub unsafe extern "C-unwind" fn emit(
+    write_ptr: Ptr,
+    write_len: u32,
+    read_ptr: Ptr,
+    read_len: u32,
+) -> i64 {
+    if write_len < 32 {
+        return _c::TOO_SMALL as i64;
+    }
+    let tx = read(read_ptr, read_len);
+
+    let hash = with_ledger(|ledger| {
+        let emitted = ledger.emitted.len() as u32;
+        match ledger.etxn_reserved {
+            None => return Err(_c::PREREQUISITE_NOT_MET as i64),
+            Some(count) if emitted >= count => return Err(_c::TOO_MANY_EMITTED_TXN as i64),
+            _ => (),
+        }
+        if sto::fields(tx).is_err() || sto::find(tx, _c::sfEmitDetails).ok().flatten().is_none() {
+            return Err(_c::EMISSION_FAILURE as i64);
+        }
+        ledger.emitted.push(tx.to_vec());
+
+        Ok(txn_id(tx))
+    });
+
+    write(write_ptr, write_len, &try_host!(hash))
+}
+
+// Floats
+
+pub unsafe extern "C-unwind" fn float_set(exponent: i32, mantissa: i64) -> i64 {
+    result(xfl::float_set(exponent, mantissa))
+}
+
+pub unsafe extern "C-unwind" fn float_multiply(float1: i64, float2: i64) -> i64 {
+    result(xfl::float_multiply(
+        XFL::from_raw(float1),
+        XFL::from_raw(float2),
+    ))
+}
+
+pub unsafe extern "C-unwind" fn float_mulratio(
+    float1: i64,
+    round_up: u32,
+    numerator: u32,
+    denominator: u32,
+) -> i64 {
+    result(xfl::float_mulratio(
+        XFL::from_raw(float1),
+        round_up != 0,
+        numerator,
+        denominator,
+    ))
+}
+
+pub unsafe extern "C-unwind" fn float_negate(float1: i64) -> i64 {
+    result(xfl::float_negate(XFL::from_raw(float1)))
+}
+
+pub unsafe extern "C-unwind" fn float_compare(float1: i64, float2: i64, mode: u32) -> i64 {
+    let less = mode & _c::COMPARE_LESS != 0;
+    let equal = mode & _c::COMPARE_EQUAL != 0;
+    let greater = mode & _c::COMPARE_GREATER != 0;
+    if mode == 0 || mode > 7 || (less && equal && greater) {
+        return _c::INVALID_ARGUMENT as i64;
+    }
+
+    match xfl::float_cmp(XFL::from_raw(float1), XFL::from_raw(float2)) {
+        crate::api::Ok(Ordering::Less) => less as i64,
+        crate::api::Ok(Ordering::Equal) => equal as i64,
+        crate::api::Ok(Ordering::Greater) => greater as i64,
+        crate::api::Err(e) => e.code() as i64,
+    }
+}
+
+pub unsafe extern "C-unwind" fn float_sum(float1: i64, float2: i64) -> i64 {
+    result(xfl::float_sum(XFL::from_raw(float1), XFL::from_raw(float2)))
+}
+
+pub unsafe extern "C-unwind" fn float_sto(
+    write_ptr: Ptr,
+    write_len: u32,
+    cread_ptr: Ptr,
+    cread_len: u32,
+    iread_ptr: Ptr,
+    iread_len: u32,
+    float1: i64,
+    field_code: u32,
+) -> i64 {
+    let float = XFL::from_raw(float1);
+    let mut out = Vec::new();
+
+    if field_code == 0 {
+        let drops = match xfl::float_int(float, 0, true) {
+            crate::api::Ok(drops) => drops as u64,
+            crate::api::Err(e) => return e.code() as i64,
+        };
+        let sign = if xfl::is_negative(float) { 0 } else { 1 << 62 };
+        out.extend(sto::encode_header(_c::sfAmount));
+        out.extend_from_slice(&(drops | sign).to_be_bytes());
+    } else {
+        let value = match float1 {
+            0 => 1 << 63,
+            raw => raw as u64 | (1 << 63),
+        };
+        if field_code != u32::MAX {
+            let currency = read(cread_ptr, cread_len);
+            let issuer = read(iread_ptr, iread_len);
+            if currency.len() != 20 || issuer.len() != 20 {
+                return _c::INVALID_ARGUMENT as i64;
+            }
+            out.extend(sto::encode_header(field_code));
+            out.extend_from_slice(&value.to_be_bytes());
+            out.extend_from_slice(currency);
+            out.extend_from_slice(issuer);
+        } else {
+            out.extend_from_slice(&value.to_be_bytes());
+        }
+    }
+
+    write(write_ptr, write_len, &out)
+}
+
+pub unsafe extern "C-unwind" fn float_sto_set(read_ptr: Ptr, read_len: u32) -> i64 {
+    let sto = read(read_ptr, read_len);
+    if sto.len() < 8 {
+        return _c::NOT_AN_OBJECT as i64;
+    }
+    if sto.len() == 8 {
+        return amount_to_xfl(sto);
+    }
+
+    match sto::header(sto) {
+        Ok((id, len)) if id >> 16 == sto::STI_AMOUNT => amount_to_xfl(&sto[len..]),
+        _ => amount_to_xfl(sto),
+    }
+}
+
+pub unsafe extern "C-unwind" fn float_invert(float1: i64) -> i64 {
+    result(xfl::float_invert(XFL::from_raw(float1)))
+}
+
+pub unsafe extern "C-unwind" fn float_divide(float1: i64, float2: i64) -> i64 {
+    result(xfl::float_divide(
+        XFL::from_raw(float1),
+        XFL::from_raw(float2),
+    ))
+}
+
+pub unsafe extern "C-unwind" fn float_one() -> i64 {
+    xfl::float_one().to_raw()
+}
+
+pub unsafe extern "C-unwind" fn float_mantissa(float1: i64) -> i64 {
+    match xfl::float_mantissa(XFL::from_raw(float1)) {
+        crate::api::Ok(mantissa) => mantissa,
+        crate::api::Err(e) => e.code() as i64,
+    }
+}
+
+pub unsafe extern "C-unwind" fn float_sign(float1: i64) -> i64 {
+    match xfl::float_sign(XFL::from_raw(float1)) {
+        crate::api::Ok(negative) => negative as i64,
+        crate::api::Err(e) => e.code() as i64,
+    }
+}
+
+pub unsafe extern "C-unwind" fn float_int(float1: i64, decimal_places: u32, abs: u32) -> i64 {
+    match xfl::float_int(XFL::from_raw(float1), decimal_places, abs != 0) {
+        crate::api::Ok(value) => value,
+        crate::api::Err(e) => e.code() as i64,
+    }
+}
+
+/// Computed in `f64`, so the last digits may differ from the host
+pub unsafe extern "C-unwind" fn float_log(float1: i64) -> i64 {
+    let value = xfl_to_f64(float1);
+    if value < 0.0 {
+        return _c::COMPLEX_NOT_SUPPORTED as i64;
+    }
+    if value == 0.0 {
+        return _c::INVALID_ARGUMENT as i64;
+    }
+
+    xfl_from_f64(value.log10())
+}
+
+/// Computed in `f64`, so the last digits may differ from the host
+pub unsafe extern "C-unwind" fn float_root(float1: i64, n: u32) -> i64 {
+    let value = xfl_to_f64(float1);
+    if n == 0 {
+        return _c::INVALID_ARGUMENT as i64;
+    }
+    if value < 0.0 {
+        return _c::COMPLEX_NOT_SUPPORTED as i64;
+    }
+
+    xfl_from_f64(value.powf(1.0 / n as f64))
+}
+
+// Ledger
+
+pub unsafe extern "C-unwind" fn fee_base() -> i64 {
+    with_ledger(|ledger| ledger.fee_base)
+}
+
+pub unsafe extern "C-unwind" fn ledger_seq() -> i64 {
+    with_ledger(|ledger| ledger.ledger_seq as i64)
+}
+
+pub unsafe extern "C-unwind" fn ledger_last_time() -> i64 {
+    with_ledger(|ledger| ledger.ledger_last_time)
+}
+
+pub unsafe extern "C-unwind" fn ledger_last_hash(write_ptr: Ptr, write_len: u32) -> i64 {
+    let hash = with_ledger(|ledger| ledger.ledger_last_hash);
+
+    write(write_ptr, write_len, &hash)
+}
+
+pub unsafe extern "C-unwind" fn ledger_nonce(write_ptr: Ptr, write_len: u32) -> i64 {
+    if write_len < 32 {
+        return _c::TOO_SMALL as i64;
+    }
+
+    let nonce = with_ledger(|ledger| {
+        if ledger.ledger_nonces >= NONCE_MAX {
+            return Err(_c::TOO_MANY_NONCES as i64);
+        }
+        ledger.ledger_nonces += 1;
+        let mut data = ledger.ledger_last_hash.to_vec();
+        data.extend_from_slice(&ledger.ledger_nonces.to_be_bytes());
+
+        Ok(ledger.nonce(&data, ledger.ledger_nonces))
+    });
+
+    write(write_ptr, write_len, &try_host!(nonce))
+}
+
+pub unsafe extern "C-unwind" fn ledger_keylet(
+    write_ptr: Ptr,
+    write_len: u32,
+    lread_ptr: Ptr,
+    lread_len: u32,
+    hread_ptr: Ptr,
+    hread_len: u32,
+) -> i64 {
+    if write_len < 34 {
+        return _c::TOO_SMALL as i64;
+    }
+    let low = read(lread_ptr, lread_len);
+    let high = read(hread_ptr, hread_len);
+    if low.len() != 34 || high.len() != 34 {
+        return _c::INVALID_ARGUMENT as i64;
+    }
+
+    let found = with_ledger(|ledger| {
+        ledger
+            .objects
+            .range(low.to_vec()..=high.to_vec())
+            .map(|(keylet, _)| keylet.clone())
+            .find(|keylet| keylet.len() == 34)
+    });
+
+    match found {
+        Some(keylet) => write(write_ptr, write_len, &keylet),
+        None => _c::DOESNT_EXIST as i64,
+    }
+}
+
+// Hook
+
+pub unsafe extern "C-unwind" fn hook_account(write_ptr: Ptr, write_len: u32) -> i64 {
+    let account = with_ledger(|ledger| ledger.hook_account);
+
+    write(write_ptr, write_len, &account)
+}
+
+pub unsafe extern "C-unwind" fn hook_hash(write_ptr: Ptr, write_len: u32, hook_no: i32) -> i64 {
+    if write_len < 32 {
+        return _c::TOO_SMALL as i64;
+    }
+
+    let hash = with_ledger(|ledger| match hook_no {
+        -1 => Ok(ledger.hook_hash),
+        hook_no if hook_no as i64 == ledger.hook_pos => Ok(ledger.hook_hash),
+        hook_no => match ledger.hook_chain.get(hook_no as usize) {
+            Some(hash) if hook_no >= 0 => Ok(*hash),
+            _ => Err(_c::DOESNT_EXIST as i64),
+        },
+    });
+
+    write(write_ptr, write_len, &try_host!(hash))
+}
+
+pub unsafe extern "C-unwind" fn hook_param_set(
+    read_ptr: Ptr,
+    read_len: u32,
+    kread_ptr: Ptr,
+    kread_len: u32,
+    hread_ptr: Ptr,
+    hread_len: u32,
+) -> i64 {
+    let value = read(read_ptr, read_len);
+    let name = read(kread_ptr, kread_len);
+    let hash = read(hread_ptr, hread_len);
+    if hash.len() != 32 {
+        return _c::INVALID_ARGUMENT as i64;
+    }
+    try_host!(check_param_name(name));
+    if value.len() > crate::api::HOOK_PARAM_VALUE_MAX_LEN {
+        return _c::TOO_BIG as i64;
+    }
+
+    let mut hook_hash = [0; 32];
+    hook_hash.copy_from_slice(hash);
+    with_ledger(|ledger| {
+        ledger
+            .hook_param_overrides
+            .insert((hook_hash, name.to_vec()), value.to_vec())
+    });
+
+    value.len() as i64
+}
+
+pub unsafe extern "C-unwind" fn hook_param(
+    write_ptr: Ptr,
+    write_len: u32,
+    read_ptr: Ptr,
+    read_len: u32,
+) -> i64 {
+    let name = read(read_ptr, read_len);
+    try_host!(check_param_name(name));
+
+    match with_ledger(|ledger| ledger.hook_param(name)) {
+        Some(value) => write(write_ptr, write_len, &value),
+        None => _c::DOESNT_EXIST as i64,
+    }
+}
+
+pub unsafe extern "C-unwind" fn hook_again() -> i64 {
+    with_ledger(|ledger| {
+        if ledger.execution != ExecutionContext::Strong {
+            return _c::PREREQUISITE_NOT_MET as i64;
+        }
+        if ledger.hook_again {
+            return _c::ALREADY_SET as i64;
+        }
+        ledger.hook_again = true;
+
+        1
+    })
+}
+
+pub unsafe extern "C-unwind" fn hook_skip(read_ptr: Ptr, read_len: u32, flags: u32) -> i64 {
+    let hash = read(read_ptr, read_len);
+    if hash.len() != 32 || flags > 1 {
+        return _c::INVALID_ARGUMENT as i64;
+    }
+
+    let mut hook_hash = [0; 32];
+    hook_hash.copy_from_slice(hash);
+    with_ledger(|ledger| match flags {
+        0 => {
+            ledger.hook_skips.insert(hook_hash);
+            1
+        }
+        _ if ledger.hook_skips.remove(&hook_hash) => 1,
+        _ => _c::DOESNT_EXIST as i64,
+    })
+}
+
+pub unsafe extern "C-unwind" fn hook_pos() -> i64 {
+    with_ledger(|ledger| ledger.hook_pos)
+}
+
+// Slots
+
+/// Serialized value of a slot, account IDs without their VL prefix
+fn slot_bytes(slot: &Slot) -> &[u8] {
+    if slot.field >> 16 == sto::STI_ACCOUNT && !slot.data.is_empty() {
+        &slot.data[1..]
+    } else {
+        &slot.data
+    }
+}
+
+pub unsafe extern "C-unwind" fn slot(write_ptr: Ptr, write_len: u32, slot: u32) -> i64 {
+    let data = try_host!(with_ledger(|ledger| ledger
+        .slot(slot)
+        .map(|slot| slot_bytes(slot).to_vec())));
+
+    if write_ptr == 0 && write_len == 0 {
+        return as_int(&data);
+    }
+
+    write(write_ptr, write_len, &data)
+}
+
+pub unsafe extern "C-unwind" fn slot_clear(slot: u32) -> i64 {
+    with_ledger(
+        |ledger| match ledger.slots.get_mut((slot as usize).wrapping_sub(1)) {
+            Some(slot @ Some(_)) => {
+                *slot = None;
+                1
+            }
+            _ => _c::DOESNT_EXIST as i64,
+        },
+    )
+}
+
+pub unsafe extern "C-unwind" fn slot_count(slot: u32) -> i64 {
+    with_ledger(|ledger| {
+        let slot = try_host!(ledger.slot(slot));
+        if slot.field >> 16 != sto::STI_ARRAY {
+            return _c::NOT_AN_ARRAY as i64;
+        }
+
+        match sto::fields(&slot.data) {
+            Ok(elements) => elements.len() as i64,
+            Err(_) => _c::INTERNAL_ERROR as i64,
+        }
+    })
+}
+
+pub unsafe extern "C-unwind" fn slot_set(read_ptr: Ptr, read_len: u32, slot: u32) -> i64 {
+    let key = read(read_ptr, read_len);
+    let field = match key.len() {
+        32 => SF_TRANSACTION,
+        34 => SF_LEDGER_ENTRY,
+        _ => return _c::INVALID_ARGUMENT as i64,
+    };
+
+    with_ledger(|ledger| match ledger.objects.get(key) {
+        Some(data) => {
+            let data = data.clone();
+            ledger.slot_into(slot, Slot { field, data })
+        }
+        None => _c::DOESNT_EXIST as i64,
+    })
+}
+
+pub unsafe extern "C-unwind" fn slot_size(slot: u32) -> i64 {
+    with_ledger(|ledger| match ledger.slot(slot) {
+        Ok(slot) => slot_bytes(slot).len() as i64,
+        Err(code) => code,
+    })
+}
+
+pub unsafe extern "C-unwind" fn slot_subarray(
+    parent_slot: u32,
+    array_id: u32,
+    new_slot: u32,
+) -> i64 {
+    with_ledger(|ledger| {
+        let parent = try_host!(ledger.slot(parent_slot));
+        if parent.field >> 16 != sto::STI_ARRAY {
+            return _c::NOT_AN_ARRAY as i64;
+        }
+        let elements = match sto::fields(&parent.data) {
+            Ok(elements) => elements,
+            Err(_) => return _c::INTERNAL_ERROR as i64,
+        };
+        let element = match elements.get(array_id as usize) {
+            Some(element) => element,
+            None => return _c::DOESNT_EXIST as i64,
+        };
+        let slot = Slot {
+            field: element.id,
+            data: parent.data[element.value.clone()].to_vec(),
+        };
+
+        ledger.slot_into(new_slot, slot)
+    })
+}
+
+pub unsafe extern "C-unwind" fn slot_subfield(
+    parent_slot: u32,
+    field_id: u32,
+    new_slot: u32,
+) -> i64 {
+    with_ledger(|ledger| {
+        let parent = try_host!(ledger.slot(parent_slot));
+        if parent.field >> 16 != sto::STI_OBJECT
+            && parent.field != SF_TRANSACTION
+            && parent.field != SF_LEDGER_ENTRY
+        {
+            return _c::NOT_AN_OBJECT as i64;
+        }
+        let field = try_host!(find(&parent.data, field_id));
+        let slot = Slot {
+            field: field.id,
+            data: parent.data[field.value].to_vec(),
+        };
+
+        ledger.slot_into(new_slot, slot)
+    })
+}
+
+pub unsafe extern "C-unwind" fn slot_type(slot_no: u32, flags: u32) -> i64 {
+    with_ledger(|ledger| {
+        let slot = try_host!(ledger.slot(slot_no));
+        match flags {
+            0 => slot.field as i64,
+            1 if slot.field >> 16 != sto::STI_AMOUNT => _c::NOT_AN_AMOUNT as i64,
+            1 => slot.data.first().map_or(0, |b| (b & 0x80 == 0) as i64),
+            _ => _c::INVALID_ARGUMENT as i64,
+        }
+    })
+}
+
+pub unsafe extern "C-unwind" fn slot_float(slot_no: u32) -> i64 {
+    with_ledger(|ledger| {
+        let slot = try_host!(ledger.slot(slot_no));
+        if slot.field >> 16 != sto::STI_AMOUNT {
+            return _c::NOT_AN_AMOUNT as i64;
+        }
+
+        amount_to_xfl(&slot.data)
+    })
+}
+
+// State
+
+/// Resolves the account and namespace of a foreign state call
+unsafe fn foreign(
+    nread_ptr: Ptr,
+    nread_len: u32,
+    aread_ptr: Ptr,
+    aread_len: u32,
+    ledger: &MockLedger,
+) -> Result<([u8; 20], [u8; 32]), i64> {
+    let namespace = read(nread_ptr, nread_len);
+    let account = read(aread_ptr, aread_len);
+
+    let namespace = match namespace.len() {
+        0 => ledger.hook_namespace,
+        32 => {
+            let mut ns = [0; 32];
+            ns.copy_from_slice(namespace);
+            ns
+        }
+        _ => return Err(_c::INVALID_ARGUMENT as i64),
+    };
+    let account = match account.len() {
+        0 => ledger.hook_account,
+        20 => {
+            let mut accid = [0; 20];
+            accid.copy_from_slice(account);
+            accid
+        }
+        _ => return Err(_c::INVALID_ARGUMENT as i64),
+    };
+
+    Ok((account, namespace))
+}
+
+fn checked_state_key(key: &[u8]) -> Result<StateKey, i64> {
+    match key.len() {
+        0 => Err(_c::TOO_SMALL as i64),
+        len if len > 32 => Err(_c::TOO_BIG as i64),
+        _ => Ok(state_key(key).unwrap_or_default()),
+    }
+}
+
+pub unsafe extern "C-unwind" fn state_set(
+    read_ptr: Ptr,
+    read_len: u32,
+    kread_ptr: Ptr,
+    kread_len: u32,
+) -> i64 {
+    state_foreign_set(read_ptr, read_len, kread_ptr, kread_len, 0, 0, 0, 0)
+}
+
+pub unsafe extern "C-unwind" fn state_foreign_set(
+    read_ptr: Ptr,
+    read_len: u32,
+    kread_ptr: Ptr,
+    kread_len: u32,
+    nread_ptr: Ptr,
+    nread_len: u32,
+    aread_ptr: Ptr,
+    aread_len: u32,
+) -> i64 {
+    let data = read(read_ptr, read_len);
+    let key = try_host!(checked_state_key(read(kread_ptr, kread_len)));
+    if data.len() > STATE_MAX_LEN {
+        return _c::TOO_BIG as i64;
+    }
+
+    with_ledger(|ledger| {
+        let (account, namespace) =
+            try_host!(foreign(nread_ptr, nread_len, aread_ptr, aread_len, ledger));
+        if account != ledger.hook_account {
+            return _c::NOT_AUTHORIZED as i64;
+        }
+
+        if data.is_empty() {
+            ledger.state.remove(&(account, namespace, key));
+        } else {
+            ledger
+                .state
+                .insert((account, namespace, key), data.to_vec());
+        }
+
+        data.len() as i64
+    })
+}
+
+pub unsafe extern "C-unwind" fn state(
+    write_ptr: Ptr,
+    write_len: u32,
+    kread_ptr: Ptr,
+    kread_len: u32,
+) -> i64 {
+    state_foreign(write_ptr, write_len, kread_ptr, kread_len, 0, 0, 0, 0)
+}
+
+pub unsafe extern "C-unwind" fn state_foreign(
+    write_ptr: Ptr,
+    write_len: u32,
+    kread_ptr: Ptr,
+    kread_len: u32,
+    nread_ptr: Ptr,
+    nread_len: u32,
+    aread_ptr: Ptr,
+    aread_len: u32,
+) -> i64 {
+    let key = try_host!(checked_state_key(read(kread_ptr, kread_len)));
+
+    let data = with_ledger(|ledger| {
+        let (account, namespace) = foreign(nread_ptr, nread_len, aread_ptr, aread_len, ledger)?;
+
+        match ledger.state.get(&(account, namespace, key)) {
+            Some(data) => Ok(data.clone()),
+            None => Err(_c::DOESNT_EXIST as i64),
+        }
+    });
+    let data = try_host!(data);
+
+    if write_ptr == 0 && write_len == 0 {
+        return as_int(&data);
+    }
+
+    write(write_ptr, write_len, &data)
+}
+
+// Trace
+
+fn log(line: String) -> i64 {
+    with_ledger(|ledger| ledger.trace.push(line));
+
+    0
+}
+
+pub unsafe extern "C-unwind" fn trace(
+    mread_ptr: Ptr,
+    mread_len: u32,
+    dread_ptr: Ptr,
+    dread_len: u32,
+    as_hex: u32,
+) -> i64 {
+    let msg = String::from_utf8_lossy(read(mread_ptr, mread_len));
+    let data = read(dread_ptr, dread_len);
+    let data = if as_hex != 0 {
+        data.iter().map(|b| format!("{b:02X}")).collect()
+    } else {
+        String::from_utf8_lossy(data).into_owned()
+    };
+
+    log(format!("{msg} {data}"))
+}
+
+pub unsafe extern "C-unwind" fn trace_num(read_ptr: Ptr, read_len: u32, number: i64) -> i64 {
+    let msg = String::from_utf8_lossy(read(read_ptr, read_len));
+
+    log(format!("{msg} {number}"))
+}
+
+pub unsafe extern "C-unwind" fn trace_float(read_ptr: Ptr, read_len: u32, float1: i64) -> i64 {
+    let msg = String::from_utf8_lossy(read(read_ptr, read_len));
+    let float = XFL::from_raw(float1);
+    let sign = if xfl::is_negative(float) { "-" } else { "" };
+
+    log(format!(
+        "{msg} Float {sign}{}*10^({})",
+        xfl::mantissa(float),
+        xfl::exponent(float)
+    ))
+}
+
+// Originating transaction
+
+pub unsafe extern "C-unwind" fn otxn_burden() -> i64 {
+    with_ledger(|ledger| ledger.otxn_burden)
+}
+
+pub unsafe extern "C-unwind" fn otxn_field(write_ptr: Ptr, write_len: u32, field_id: u32) -> i64 {
+    let data = with_ledger(|ledger| {
+        let field = find(&ledger.otxn, field_id)?;
+        let range = if field.type_code() == sto::STI_ACCOUNT {
+            field.payload
+        } else {
+            field.value
+        };
+
+        Ok(ledger.otxn[range].to_vec())
+    });
+
+    write(write_ptr, write_len, &try_host!(data))
+}
+
+pub unsafe extern "C-unwind" fn otxn_generation() -> i64 {
+    with_ledger(|ledger| ledger.otxn_generation)
+}
+
+pub unsafe extern "C-unwind" fn otxn_id(write_ptr: Ptr, write_len: u32, _flags: u32) -> i64 {
+    if write_len < 32 {
+        return _c::TOO_SMALL as i64;
+    }
+
+    write(
+        write_ptr,
+        write_len,
+        &with_ledger(|ledger| ledger.otxn_id()),
+    )
+}
+
+pub unsafe extern "C-unwind" fn otxn_type() -> i64 {
+    with_ledger(|ledger| match find(&ledger.otxn, _c::sfTransactionType) {
+        Ok(field) => as_int(&ledger.otxn[field.payload]),
+        Err(code) => code,
+    })
+}
+
+pub unsafe extern "C-unwind" fn otxn_slot(slot_no: u32) -> i64 {
+    with_ledger(|ledger| {
+        let slot = Slot {
+            field: SF_TRANSACTION,
+            data: ledger.otxn.clone(),
+        };
+
+        ledger.slot_into(slot_no, slot)
+    })
+}
+
+pub unsafe extern "C-unwind" fn otxn_param(
+    write_ptr: Ptr,
+    write_len: u32,
+    read_ptr: Ptr,
+    read_len: u32,
+) -> i64 {
+    let name = read(read_ptr, read_len);
+    try_host!(check_param_name(name));
+
+    let value = with_ledger(|ledger| {
+        let params = find(&ledger.otxn, _c::sfHookParameters)?;
+        find_param(&ledger.otxn[params.value], name).ok_or(_c::DOESNT_EXIST as i64)
+    });
+
+    write(write_ptr, write_len, &try_host!(value))
+}
+
+pub unsafe extern "C-unwind" fn meta_slot(slot_no: u32) -> i64 {
+    with_ledger(|ledger| match ledger.meta.clone() {
+        Some(data) => ledger.slot_into(
+            slot_no,
+            Slot {
+                field: _c::sfTransactionMetaData,
+                data,
+            },
+        ),
+        None => _c::PREREQUISITE_NOT_MET as i64,
+    })
+}
+
+pub unsafe extern "C-unwind" fn xpop_slot(arg1: u32, arg2: u32) -> i64 {
+    with_ledger(|ledger| {
+        let (tx, meta) = match ledger.xpop.clone() {
+            Some(xpop) => xpop,
+            None => return _c::PREREQUISITE_NOT_MET as i64,
+        };
+        let tx_slot = ledger.slot_into(
+            arg1,
+            Slot {
+                field: SF_TRANSACTION,
+                data: tx,
+            },
+        );
+        if tx_slot < 0 {
+            return tx_slot;
+        }
+        let meta_slot = ledger.slot_into(
+            arg2,
+            Slot {
+                field: _c::sfTransactionMetaData,
+                data: meta,
+            },
+        );
+        if meta_slot < 0 {
+            return meta_slot;
+        }
+
+        (tx_slot << 16) | meta_slot
+    })
+}
@@ -0,0 +1,519 @@
+use crate::api::{AccountId, Error, ExecutionContext, Hash, NameSpace, StateKey};
+use core::cell::RefCell;
+use core::mem;
+use std::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    panic::{self, AssertUnwindSafe},
+    string::String,
+    vec::Vec,
+};
+
+mod hash;
+pub(crate) mod host;
+mod scenario;
+pub(crate) mod sto;
+
+pub use self::scenario::*;
+
+/// Number of slots available to a hook
+const SLOT_COUNT: usize = 255;
+
+std::thread_local! {
+    static LEDGER: RefCell<Option<MockLedger>> = const { RefCell::new(None) };
+}
+
+/// How a hook execution ended
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HookOutcome {
+    /// The hook called [accept](crate::accept)
+    Accept {
+        /// Message passed to `accept`
+        message: Vec<u8>,
+        /// Error code passed to `accept`
+        code: i64,
+    },
+    /// The hook called [rollback](crate::rollback), or returned without calling either
+    Rollback {
+        /// Message passed to `rollback`
+        message: Vec<u8>,
+        /// Error code passed to `rollback`, or the value the hook returned
+        code: i64,
+    },
+}
+
+impl HookOutcome {
+    /// Returns `true` if the hook accepted
+    pub fn is_accept(&self) -> bool {
+        matches!(self, Self::Accept { .. })
+    }
+
+    /// Returns `true` if the hook rolled back
+    pub fn is_rollback(&self) -> bool {
+        !self.is_accept()
+    }
+
+    /// Message passed to `accept` or `rollback`
+    pub fn message(&self) -> &[u8] {
+        match self {
+            Self::Accept { message, .. } | Self::Rollback { message, .. } => message,
+        }
+    }
+
+    /// Error code passed to `accept` or `rollback`
+    pub fn code(&self) -> i64 {
+        match self {
+            Self::Accept { code, .. } | Self::Rollback { code, .. } => *code,
+        }
+    }
+
+    /// Returns `true` if a loop exceeded its guard
+    pub fn is_guard_violation(&self) -> bool {
+        self.is_rollback() && self.code() == Error::GuardViolation.code() as i64
+    }
+}
+
+/// Calls of a guard ID
+///
+/// A loop guarded with `maxiter` calls [_g](crate::_g) once per evaluation of its condition,
+/// so a loop running `n` times makes `n + 1` calls.
+/// Guards are only counted during [run](MockLedger::run), outside of it guarded code
+/// such as [sto::Reader](crate::sto::Reader) runs unchecked.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GuardCounter {
+    /// Bound passed to the last call
+    pub maxiter: u32,
+    /// Calls during the last run
+    pub calls: u32,
+    /// Most calls during a single run, over all runs
+    pub max_calls: u32,
+}
+
+/// A loaded slot
+#[derive(Clone)]
+pub(crate) struct Slot {
+    /// Field ID of the slotted object
+    pub(crate) field: u32,
+    /// Serialized value, without the field header
+    pub(crate) data: Vec<u8>,
+}
+
+/// In-memory ledger backing the native host functions
+///
+/// Fill in the public fields, then call [run](MockLedger::run) with the hook entry point.
+/// Changes the hook makes are visible in the public fields afterwards.
+pub struct MockLedger {
+    /// Account the hook is installed on
+    pub hook_account: AccountId,
+    /// Hash of the executing hook
+    pub hook_hash: Hash,
+    /// Namespace of the executing hook
+    pub hook_namespace: NameSpace,
+    /// Position of the executing hook in the hook chain
+    pub hook_pos: i64,
+    /// Hashes of the hooks in the hook chain, by position
+    pub hook_chain: Vec<Hash>,
+    /// Install-time parameters of the executing hook
+    pub hook_params: BTreeMap<Vec<u8>, Vec<u8>>,
+    /// Parameters set for other hooks by [hook_param_set](crate::hook_param_set), by hook hash
+    pub hook_param_overrides: BTreeMap<(Hash, Vec<u8>), Vec<u8>>,
+    /// Whether the hook executes strongly or weakly
+    pub execution: ExecutionContext,
+    /// Set once the hook requested [hook_again](crate::hook_again)
+    pub hook_again: bool,
+    /// Hooks of the chain skipped by [hook_skip](crate::hook_skip)
+    pub hook_skips: BTreeSet<Hash>,
+
+    /// Serialized originating transaction
+    pub otxn: Vec<u8>,
+    /// Burden of the originating transaction
+    pub otxn_burden: i64,
+    /// Generation of the originating transaction
+    pub otxn_generation: i64,
+    /// Serialized metadata of the originating transaction, available to weak executions
+    pub meta: Option<Vec<u8>>,
+    /// Serialized transaction and metadata proven by the XPOP of an `Import` transaction
+    pub xpop: Option<(Vec<u8>, Vec<u8>)>,
+
+    /// Serialized ledger objects by 34 byte keylet and transactions by 32 byte ID
+    pub objects: BTreeMap<Vec<u8>, Vec<u8>>,
+    /// Hook state by account, namespace and key
+    pub state: BTreeMap<(AccountId, NameSpace, StateKey), Vec<u8>>,
+    /// Current ledger sequence
+    pub ledger_seq: u32,
+    /// Close time of the last ledger
+    pub ledger_last_time: i64,
+    /// Hash of the last ledger
+    pub ledger_last_hash: Hash,
+    /// Fee base of the current ledger, in drops
+    pub fee_base: i64,
+    /// Signatures [util_verify](crate::util_verify) accepts, as (payload, signature, public key)
+    pub valid_signatures: Vec<(Vec<u8>, Vec<u8>, Vec<u8>)>,
+
+    /// Transactions emitted by the hook
+    pub emitted: Vec<Vec<u8>>,
+    /// Lines written by the trace functions
+    pub trace: Vec<String>,
+    /// Counters of each guard ID, see [guard_report](MockLedger::guard_report)
+    pub guards: BTreeMap<u32, GuardCounter>,
+
+    slots: Vec<Option<Slot>>,
+    etxn_reserved: Option<u32>,
+    etxn_nonces: u32,
+    ledger_nonces: u32,
+}
+
+impl Default for MockLedger {
+    fn default() -> Self {
+        Self {
+            hook_account: [0; 20],
+            hook_hash: [0; 32],
+            hook_namespace: [0; 32],
+            hook_pos: 0,
+            hook_chain: Vec::new(),
+            hook_params: BTreeMap::new(),
+            hook_param_overrides: BTreeMap::new(),
+            execution: ExecutionContext::Strong,
+            hook_again: false,
+            hook_skips: BTreeSet::new(),
+            otxn: Vec::new(),
+            otxn_burden: 1,
+            otxn_generation: 0,
+            meta: None,
+            xpop: None,
+            objects: BTreeMap::new(),
+            state: BTreeMap::new(),
+            ledger_seq: 1,
+            ledger_last_time: 0,
+            ledger_last_hash: [0; 32],
+            fee_base: 10,
+            valid_signatures: Vec::new(),
+            emitted: Vec::new(),
+            trace: Vec::new(),
+            guards: BTreeMap::new(),
+            slots: std::vec![None; SLOT_COUNT],
+            etxn_reserved: None,
+            etxn_nonces: 0,
+            ledger_nonces: 0,
+        }
+    }
+}
+
+impl MockLedger {
+    /// Create an empty ledger
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hook state of the executing hook for a key
+    pub fn state(&self, key: &[u8]) -> Option<&[u8]> {
+        let key = host::state_key(key)?;
+
+        self.state
+            .get(&(self.hook_account, self.hook_namespace, key))
+            .map(Vec::as_slice)
+    }
+
+    /// Set the hook state of the executing hook for a key
+    pub fn set_state(&mut self, key: &[u8], data: &[u8]) {
+        let key = host::state_key(key).expect("state key must be 1 to 32 bytes");
+
+        self.state
+            .insert((self.hook_account, self.hook_namespace, key), data.to_vec());
+    }
+
+    /// Most calls seen for each guard ID against its bound, one line per guard
+    ///
+    /// Use it to set tight `maxiter` bounds: a guard is tight when its most calls equal its bound.
+    pub fn guard_report(&self) -> String {
+        self.guards
+            .iter()
+            .map(|(id, guard)| {
+                std::format!(
+                    "guard {id:#010x}: {} of {}\n",
+                    guard.max_calls,
+                    guard.maxiter
+                )
+            })
+            .collect()
+    }
+
+    /// Run a hook entry point against this ledger
+    ///
+    /// Slots, reservations and guard calls start fresh on each run.
+    /// A loop exceeding its guard rolls back with [Error::GuardViolation], as on the ledger.
+    /// On rollback, state changes and emitted transactions are discarded, as on the ledger.
+    ///
+    /// The entry point must be able to unwind: the [hook](crate::hook) and
+    /// [cbak](crate::cbak) attributes export `extern "C-unwind"` functions for this.
+    pub fn run<F: FnOnce() -> i64>(&mut self, entry: F) -> HookOutcome {
+        let state = self.state.clone();
+        let emitted = self.emitted.len();
+
+        self.slots = std::vec![None; SLOT_COUNT];
+        self.etxn_reserved = None;
+        self.etxn_nonces = 0;
+        self.ledger_nonces = 0;
+        for guard in self.guards.values_mut() {
+            guard.calls = 0;
+        }
+
+        LEDGER.with(|ledger| *ledger.borrow_mut() = Some(mem::take(self)));
+        let result = panic::catch_unwind(AssertUnwindSafe(entry));
+        *self = LEDGER
+            .with(|ledger| ledger.borrow_mut().take())
+            .expect("ledger is installed while the hook runs");
+
+        let outcome = match result {
+            Ok(code) => HookOutcome::Rollback {
+                message: Vec::new(),
+                code,
+            },
+            Err(payload) => match payload.downcast::<HookOutcome>() {
+                Ok(outcome) => *outcome,
+                Err(payload) => panic::resume_unwind(payload),
+            },
+        };
+
+        if outcome.is_rollback() {
+            self.state = state;
+            self.emitted.truncate(emitted);
+        }
+
+        outcome
+    }
+}
+
+/// Runs `f` with the ledger of the running hook
+pub(crate) fn with_ledger<R>(f: impl FnOnce(&mut MockLedger) -> R) -> R {
+    LEDGER.with(|ledger| {
+        f(ledger
+            .borrow_mut()
+            .as_mut()
+            .expect("host function called outside of MockLedger::run"))
+    })
+}
+
+/// Runs `f` with the ledger of the running hook, if any
+pub(crate) fn try_with_ledger<R>(f: impl FnOnce(&mut MockLedger) -> R) -> Option<R> {
+    LEDGER.with(|ledger| ledger.borrow_mut().as_mut().map(f))
+}
+
+/// Ends the running hook
+pub(crate) fn exit(outcome: HookOutcome) -> ! {
+    panic::resume_unwind(Box::new(outcome))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{self, FieldId};
+
+    // TransactionType Payment, Account rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh, Amount 1000 drops
+    const OTXN: &[u8] = &[
+        0x12, 0x00, 0x00, 0x61, 0x40, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0xE8, 0x81, 0x14, 0xB5,
+        0xF7, 0x62, 0x79, 0x8A, 0x53, 0xD5, 0x43, 0xA0, 0x14, 0xCA, 0xF8, 0xB2, 0x97, 0xCF, 0xF8,
+        0xF2, 0xF9, 0x37, 0xE8,
+    ];
+    const GENESIS: &[u8] = b"rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh";
+
+    fn ledger() -> MockLedger {
+        let mut ledger = MockLedger::new();
+        ledger.otxn = OTXN.to_vec();
+        ledger
+    }
+
+    #[test]
+    fn accept_keeps_state() {
+        let mut ledger = ledger();
+
+        let outcome = ledger.run(|| {
+            let _ = api::state_set(b"value", b"key");
+            api::accept(b"done", 7)
+        });
+
+        assert_eq!(
+            outcome,
+            HookOutcome::Accept {
+                message: b"done".to_vec(),
+                code: 7
+            }
+        );
+        assert_eq!(ledger.state(b"key"), Some(&b"value"[..]));
+    }
+
+    #[test]
+    fn rollback_discards_state() {
+        let mut ledger = ledger();
+        ledger.set_state(b"key", b"old");
+
+        let outcome = ledger.run(|| {
+            let _ = api::state_set(b"new", b"key");
+            api::rollback(b"no", 1)
+        });
+
+        assert!(outcome.is_rollback());
+        assert_eq!(outcome.message(), b"no");
+        assert_eq!(ledger.state(b"key"), Some(&b"old"[..]));
+    }
+
+    #[test]
+    fn return_without_exit_rolls_back() {
+        let outcome = ledger().run(|| 3);
+
+        assert!(outcome.is_rollback());
+        assert_eq!(outcome.code(), 3);
+    }
+
+    #[test]
+    fn otxn_fields() {
+        let mut ledger = ledger();
+
+        ledger.run(|| {
+            let mut account = [0; 20];
+            assert!(matches!(
+                api::otxn_field(&mut account, FieldId::Account),
+                api::Ok(20)
+            ));
+            assert_eq!(account[..], OTXN[14..]);
+            assert_eq!(api::otxn_type(), 0);
+
+            let mut raddr = [0; 40];
+            match api::util_raddr(&mut raddr, &account) {
+                api::Ok(len) => assert_eq!(&raddr[..len as usize], GENESIS),
+                api::Err(e) => panic!("{}", e.code()),
+            }
+
+            let mut dest = [0; 20];
+            assert!(matches!(
+                api::otxn_field(&mut dest, FieldId::Destination),
+                api::Err(api::Error::DoesntExist)
+            ));
+
+            api::accept(b"", 0)
+        });
+    }
+
+    #[test]
+    fn slots() {
+        let mut ledger = ledger();
+
+        ledger.run(|| {
+            let otxn = match api::otxn_slot(0) {
+                api::Ok(slot) => slot as u32,
+                api::Err(e) => panic!("{}", e.code()),
+            };
+            let amount = match api::slot_subfield(otxn, FieldId::Amount, 0) {
+                api::Ok(slot) => slot as u32,
+                api::Err(e) => panic!("{}", e.code()),
+            };
+            assert!(matches!(
+                api::slot_type(amount, api::SlotTypeFlags::NativeAmount),
+                api::Ok(api::FieldOrXrpAmount::NativeAmount)
+            ));
+            match api::slot_float(amount) {
+                api::Ok(float) => assert!(matches!(api::float_int(float, 0, false), api::Ok(1000))),
+                api::Err(e) => panic!("{}", e.code()),
+            }
+            assert!(matches!(api::slot_clear(amount), api::Ok(1)));
+            assert!(matches!(
+                api::slot_size(amount),
+                api::Err(api::Error::DoesntExist)
+            ));
+
+            api::accept(b"", 0)
+        });
+    }
+
+    #[test]
+    fn emit_needs_reserve() {
+        let mut ledger = ledger();
+
+        let outcome = ledger.run(|| {
+            let mut hash = [0; 32];
+            assert!(matches!(
+                api::emit(&mut hash, OTXN),
+                api::Err(api::Error::PrerequisiteNotMet)
+            ));
+            assert!(matches!(api::etxn_reserve(1), api::Ok(1)));
+
+            let mut details = [0; 138];
+            assert!(matches!(api::etxn_details(&mut details), api::Ok(138)));
+            let mut txn = OTXN.to_vec();
+            txn.extend_from_slice(&details);
+            assert!(matches!(api::emit(&mut hash, &txn), api::Ok(32)));
+
+            api::accept(b"", 0)
+        });
+
+        assert!(outcome.is_accept());
+        assert_eq!(ledger.emitted.len(), 1);
+    }
+
+    #[test]
+    fn guard_violation() {
+        let mut ledger = ledger();
+        let run = |ledger: &mut MockLedger, n: u32| {
+            ledger.run(|| {
+                let mut i = 0;
+                while {
+                    api::_g(1, 5);
+                    i < n
+                } {
+                    i += 1;
+                }
+                api::accept(b"", 0)
+            })
+        };
+
+        assert!(run(&mut ledger, 4).is_accept());
+        assert_eq!(ledger.guard_report(), "guard 0x00000001: 5 of 5\n");
+
+        assert!(run(&mut ledger, 5).is_guard_violation());
+        assert!(run(&mut ledger, 2).is_accept());
+        assert_eq!(ledger.guards[&1].calls, 3);
+        assert_eq!(ledger.guards[&1].max_calls, 6);
+    }
+
+    #[test]
+    fn traces() {
+        let mut ledger = ledger();
+
+        let outcome = ledger.run(|| {
+            let _ = api::trace(b"hex", b"\x01\xAB", api::DataRepr::AsHex);
+            let _ = api::trace_num(b"num", -42);
+            let _ = api::trace_float(b"float", api::XFL::from_raw(6089866696204910592));
+            api::accept(b"", 0)
+        });
+
+        assert!(outcome.is_accept());
+        assert_eq!(
+            ledger.trace,
+            [
+                "hex 01AB",
+                "num -42",
+                "float Float 1000000000000000*10^(-15)"
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "host function called outside of MockLedger::run")]
+    fn host_call_outside_run() {
+        let _ = api::ledger_seq();
+    }
+
+    #[test]
+    fn hashes() {
+        assert_eq!(
+            hash::sha256(b"abc")[..4],
+            [0xba, 0x78, 0x16, 0xbf],
+            "sha256"
+        );
+        assert_eq!(
+            hash::sha512_half(b"abc")[28..],
+            [0x4b, 0x55, 0xd3, 0x9a],
+            "sha512 half"
+        );
+    }
+}
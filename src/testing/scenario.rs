@@ -0,0 +1,430 @@
+use super::{sto, HookOutcome, MockLedger};
+use crate::api::{AccountId, CurrencyCode, FieldId, Hash, TxnType, XFL};
+use std::{collections::BTreeMap, vec::Vec};
+
+/// Fields of a scenario transaction
+///
+/// Kept by field ID, so serialization is always in canonical order.
+#[derive(Clone, Debug)]
+pub struct TxnFields {
+    fields: BTreeMap<u32, Vec<u8>>,
+    memos: Vec<Vec<u8>>,
+    params: Vec<Vec<u8>>,
+}
+
+impl TxnFields {
+    /// Create the fields of a transaction of a given type
+    ///
+    /// Starts with a zero `Account`, a 10 drops `Fee`, `Sequence` 1 and an empty `SigningPubKey`.
+    pub fn new(txn_type: TxnType) -> Self {
+        let mut fields = Self {
+            fields: BTreeMap::new(),
+            memos: Vec::new(),
+            params: Vec::new(),
+        };
+        fields.set(FieldId::TransactionType, &(txn_type as u16).to_be_bytes());
+        fields.set(FieldId::Account, &vl(&[0; 20]));
+        fields.set(FieldId::Fee, &drops(10));
+        fields.set(FieldId::Sequence, &1u32.to_be_bytes());
+        fields.set(FieldId::SigningPubKey, &vl(&[]));
+
+        fields
+    }
+
+    fn set(&mut self, field: FieldId, value: &[u8]) {
+        self.fields.insert(field as u32, value.to_vec());
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut fields = self.fields.clone();
+        if !self.memos.is_empty() {
+            fields.insert(FieldId::Memos as u32, array(&self.memos));
+        }
+        if !self.params.is_empty() {
+            fields.insert(FieldId::HookParameters as u32, array(&self.params));
+        }
+
+        let mut bytes = Vec::new();
+        for (field_id, value) in fields {
+            bytes.extend(sto::encode_header(field_id));
+            bytes.extend(value);
+        }
+
+        bytes
+    }
+}
+
+fn vl(data: &[u8]) -> Vec<u8> {
+    let mut value = sto::encode_vl(data.len());
+    value.extend_from_slice(data);
+
+    value
+}
+
+fn drops(drops: u64) -> [u8; 8] {
+    ((drops & ((1 << 62) - 1)) | (1 << 62)).to_be_bytes()
+}
+
+fn iou(value: XFL, currency: &CurrencyCode, issuer: &AccountId) -> Vec<u8> {
+    let raw = match value.to_raw() {
+        0 => 1 << 63,
+        raw => raw as u64 | (1 << 63),
+    };
+    let mut amount = raw.to_be_bytes().to_vec();
+    amount.extend_from_slice(currency);
+    amount.extend_from_slice(issuer);
+
+    amount
+}
+
+/// Serialized inner object with an end marker
+fn object(fields: &[(FieldId, Vec<u8>)]) -> Vec<u8> {
+    let mut fields = fields.to_vec();
+    fields.sort_by_key(|(field, _)| *field as u32);
+
+    let mut bytes = Vec::new();
+    for (field, value) in fields {
+        bytes.extend(sto::encode_header(field as u32));
+        bytes.extend(value);
+    }
+    bytes.push(0xE1);
+
+    bytes
+}
+
+fn array(elements: &[Vec<u8>]) -> Vec<u8> {
+    let mut bytes: Vec<u8> = elements.concat();
+    bytes.push(0xF1);
+
+    bytes
+}
+
+/// Originating transaction of a test
+///
+/// Builds the canonical serialization of a transaction, which [run](TxnScenario::run)
+/// installs as the originating transaction of a [MockLedger]. It then backs
+/// [otxn_field](crate::otxn_field), [otxn_slot](crate::otxn_slot),
+/// [otxn_type](crate::otxn_type) and [otxn_id](crate::otxn_id).
+///
+/// # Example
+///
+/// ``` txt
+/// let mut ledger = MockLedger::new();
+/// ledger.hook_account = HOOK;
+///
+/// let outcome = Payment::new()
+///     .account(&ALICE)
+///     .destination(&HOOK)
+///     .amount_drops(1_000_000)
+///     .run(&mut ledger, || hook(0));
+///
+/// assert!(outcome.is_accept());
+/// assert_eq!(ledger.emitted.len(), 1);
+/// ```
+pub trait TxnScenario: Sized {
+    /// Fields of the transaction
+    fn fields(&self) -> &TxnFields;
+
+    /// Mutable fields of the transaction
+    fn fields_mut(&mut self) -> &mut TxnFields;
+
+    /// Set a field to an already serialized value, without the field header
+    fn field(mut self, field: FieldId, value: &[u8]) -> Self {
+        self.fields_mut().set(field, value);
+        self
+    }
+
+    /// Set a UInt8 field
+    fn field_u8(self, field: FieldId, value: u8) -> Self {
+        self.field(field, &[value])
+    }
+
+    /// Set a UInt16 field
+    fn field_u16(self, field: FieldId, value: u16) -> Self {
+        self.field(field, &value.to_be_bytes())
+    }
+
+    /// Set a UInt32 field
+    fn field_u32(self, field: FieldId, value: u32) -> Self {
+        self.field(field, &value.to_be_bytes())
+    }
+
+    /// Set a UInt64 field
+    fn field_u64(self, field: FieldId, value: u64) -> Self {
+        self.field(field, &value.to_be_bytes())
+    }
+
+    /// Set a Hash256 field
+    fn field_hash(self, field: FieldId, value: &Hash) -> Self {
+        self.field(field, value)
+    }
+
+    /// Set an AccountID field
+    fn field_account(self, field: FieldId, account: &AccountId) -> Self {
+        self.field(field, &vl(account))
+    }
+
+    /// Set a Blob field
+    fn field_blob(self, field: FieldId, data: &[u8]) -> Self {
+        self.field(field, &vl(data))
+    }
+
+    /// Set an Amount field to a native amount
+    fn field_drops(self, field: FieldId, value: u64) -> Self {
+        self.field(field, &drops(value))
+    }
+
+    /// Set an Amount field to an issued currency amount
+    fn field_iou(
+        self,
+        field: FieldId,
+        value: XFL,
+        currency: &CurrencyCode,
+        issuer: &AccountId,
+    ) -> Self {
+        self.field(field, &iou(value, currency, issuer))
+    }
+
+    /// Set the sending account
+    fn account(self, account: &AccountId) -> Self {
+        self.field_account(FieldId::Account, account)
+    }
+
+    /// Set the fee, in drops
+    fn fee_drops(self, value: u64) -> Self {
+        self.field_drops(FieldId::Fee, value)
+    }
+
+    /// Set the sequence
+    fn sequence(self, sequence: u32) -> Self {
+        self.field_u32(FieldId::Sequence, sequence)
+    }
+
+    /// Set the flags
+    fn flags(self, flags: u32) -> Self {
+        self.field_u32(FieldId::Flags, flags)
+    }
+
+    /// Set the source tag
+    fn source_tag(self, tag: u32) -> Self {
+        self.field_u32(FieldId::SourceTag, tag)
+    }
+
+    /// Add a memo
+    fn memo(mut self, memo_type: &[u8], data: &[u8]) -> Self {
+        let mut memo = sto::encode_header(FieldId::Memo as u32);
+        memo.extend(object(&[
+            (FieldId::MemoType, vl(memo_type)),
+            (FieldId::MemoData, vl(data)),
+        ]));
+        self.fields_mut().memos.push(memo);
+        self
+    }
+
+    /// Add a hook parameter, read by [otxn_param](crate::otxn_param)
+    fn hook_param(mut self, name: &[u8], value: &[u8]) -> Self {
+        let mut param = sto::encode_header(FieldId::HookParameter as u32);
+        param.extend(object(&[
+            (FieldId::HookParameterName, vl(name)),
+            (FieldId::HookParameterValue, vl(value)),
+        ]));
+        self.fields_mut().params.push(param);
+        self
+    }
+
+    /// Canonical serialization of the transaction
+    fn to_bytes(&self) -> Vec<u8> {
+        self.fields().to_bytes()
+    }
+
+    /// Run a hook entry point with this transaction as the originating transaction
+    fn run<F: FnOnce() -> i64>(&self, ledger: &mut MockLedger, entry: F) -> HookOutcome {
+        ledger.otxn = self.to_bytes();
+        ledger.run(entry)
+    }
+}
+
+macro_rules! scenario {
+    ($(#[$doc:meta])* $name:ident) => {
+        $(#[$doc])*
+        #[derive(Clone, Debug)]
+        pub struct $name(TxnFields);
+
+        impl $name {
+            /// Create the transaction with default fields
+            pub fn new() -> Self {
+                Self(TxnFields::new(TxnType::$name))
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl TxnScenario for $name {
+            fn fields(&self) -> &TxnFields {
+                &self.0
+            }
+
+            fn fields_mut(&mut self) -> &mut TxnFields {
+                &mut self.0
+            }
+        }
+    };
+}
+
+scenario!(
+    /// Payment scenario
+    Payment
+);
+
+impl Payment {
+    /// Set the destination account
+    pub fn destination(self, account: &AccountId) -> Self {
+        self.field_account(FieldId::Destination, account)
+    }
+
+    /// Set the destination tag
+    pub fn destination_tag(self, tag: u32) -> Self {
+        self.field_u32(FieldId::DestinationTag, tag)
+    }
+
+    /// Set the amount to a native amount
+    pub fn amount_drops(self, value: u64) -> Self {
+        self.field_drops(FieldId::Amount, value)
+    }
+
+    /// Set the amount to an issued currency amount
+    pub fn amount(self, value: XFL, currency: &CurrencyCode, issuer: &AccountId) -> Self {
+        self.field_iou(FieldId::Amount, value, currency, issuer)
+    }
+}
+
+scenario!(
+    /// Invoke scenario
+    Invoke
+);
+
+impl Invoke {
+    /// Set the destination account
+    pub fn destination(self, account: &AccountId) -> Self {
+        self.field_account(FieldId::Destination, account)
+    }
+
+    /// Set the blob
+    pub fn blob(self, data: &[u8]) -> Self {
+        self.field_blob(FieldId::Blob, data)
+    }
+}
+
+scenario!(
+    /// TrustSet scenario
+    TrustSet
+);
+
+impl TrustSet {
+    /// Set the trust line limit
+    pub fn limit_amount(self, value: XFL, currency: &CurrencyCode, issuer: &AccountId) -> Self {
+        self.field_iou(FieldId::LimitAmount, value, currency, issuer)
+    }
+}
+
+/// Any other transaction type, fields set through the [TxnScenario] methods
+#[derive(Clone, Debug)]
+pub struct Transaction(TxnFields);
+
+impl Transaction {
+    /// Create a transaction of a given type with default fields
+    pub fn new(txn_type: TxnType) -> Self {
+        Self(TxnFields::new(txn_type))
+    }
+}
+
+impl TxnScenario for Transaction {
+    fn fields(&self) -> &TxnFields {
+        &self.0
+    }
+
+    fn fields_mut(&mut self) -> &mut TxnFields {
+        &mut self.0
+    }
+}
+
+/// Find a field in a serialized transaction, such as an emitted one
+///
+/// Returns the value without the field header and without the VL prefix,
+/// like [sto_subfield](crate::sto_subfield).
+pub fn txn_field(txn: &[u8], field: FieldId) -> Option<&[u8]> {
+    match sto::find(txn, field as u32) {
+        Ok(Some(found)) => Some(&txn[found.payload]),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api;
+
+    const ALICE: AccountId = [0xA1; 20];
+    const HOOK: AccountId = [0xB2; 20];
+
+    #[test]
+    fn canonical_order() {
+        let txn = Payment::new()
+            .amount_drops(1000)
+            .destination(&HOOK)
+            .account(&ALICE)
+            .to_bytes();
+
+        let ids: Vec<u32> = sto::fields(&txn)
+            .unwrap()
+            .iter()
+            .map(|field| field.id)
+            .collect();
+        let mut sorted = ids.clone();
+        sorted.sort();
+        assert_eq!(ids, sorted);
+        assert_eq!(txn_field(&txn, FieldId::Destination), Some(&HOOK[..]));
+        assert_eq!(txn_field(&txn, FieldId::DestinationTag), None);
+    }
+
+    #[test]
+    fn feeds_otxn() {
+        let mut ledger = MockLedger::new();
+        ledger.hook_account = HOOK;
+
+        let outcome = Payment::new()
+            .account(&ALICE)
+            .destination(&HOOK)
+            .amount_drops(1000)
+            .hook_param(b"op", b"A")
+            .memo(b"text", b"hello")
+            .run(&mut ledger, || {
+                assert_eq!(api::otxn_type(), TxnType::Payment as i64);
+                assert!(matches!(api::otxn_txn_type(), Some(TxnType::Payment)));
+
+                let mut account = [0; 20];
+                assert!(matches!(
+                    api::otxn_field(&mut account, FieldId::Account),
+                    api::Ok(20)
+                ));
+                assert_eq!(account, ALICE);
+
+                let mut op = [0; 8];
+                match api::otxn_param(&mut op, b"op") {
+                    api::Ok(value) => assert_eq!(value, b"A"),
+                    api::Err(e) => panic!("{}", e.code()),
+                }
+
+                let _ = api::state_set(b"value", b"key");
+                api::accept(b"paid", 3)
+            });
+
+        assert_eq!(outcome.message(), b"paid");
+        assert_eq!(outcome.code(), 3);
+        assert_eq!(ledger.state(b"key"), Some(&b"value"[..]));
+    }
+}
@@ -0,0 +1,215 @@
+//! Minimal STObject parsing for the mock host
+
+use core::ops::Range;
+use std::vec::Vec;
+
+pub(crate) const STI_UINT16: u32 = 1;
+pub(crate) const STI_UINT32: u32 = 2;
+pub(crate) const STI_UINT64: u32 = 3;
+pub(crate) const STI_HASH128: u32 = 4;
+pub(crate) const STI_HASH256: u32 = 5;
+pub(crate) const STI_AMOUNT: u32 = 6;
+pub(crate) const STI_VL: u32 = 7;
+pub(crate) const STI_ACCOUNT: u32 = 8;
+pub(crate) const STI_OBJECT: u32 = 14;
+pub(crate) const STI_ARRAY: u32 = 15;
+pub(crate) const STI_UINT8: u32 = 16;
+pub(crate) const STI_HASH160: u32 = 17;
+pub(crate) const STI_PATHSET: u32 = 18;
+pub(crate) const STI_VECTOR256: u32 = 19;
+pub(crate) const STI_UINT96: u32 = 20;
+pub(crate) const STI_UINT192: u32 = 21;
+pub(crate) const STI_UINT384: u32 = 22;
+pub(crate) const STI_UINT512: u32 = 23;
+pub(crate) const STI_ISSUE: u32 = 24;
+pub(crate) const STI_CURRENCY: u32 = 26;
+
+const OBJECT_END: u32 = (STI_OBJECT << 16) | 1;
+const ARRAY_END: u32 = (STI_ARRAY << 16) | 1;
+
+/// Parse failure
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Malformed;
+
+/// A field located inside a buffer
+#[derive(Clone, Debug)]
+pub(crate) struct Field {
+    /// Field ID, `type << 16 | field`
+    pub(crate) id: u32,
+    /// Header and value
+    pub(crate) whole: Range<usize>,
+    /// Value as serialized after the header, without the end marker of objects and arrays
+    pub(crate) value: Range<usize>,
+    /// Value without the VL prefix
+    pub(crate) payload: Range<usize>,
+}
+
+impl Field {
+    pub(crate) fn type_code(&self) -> u32 {
+        self.id >> 16
+    }
+}
+
+/// Decodes a field header, returns the field ID and the header length
+pub(crate) fn header(buf: &[u8]) -> Result<(u32, usize), Malformed> {
+    let b0 = *buf.first().ok_or(Malformed)? as u32;
+    let (type_code, field_code, len) = match (b0 >> 4, b0 & 0xF) {
+        (0, 0) => (byte(buf, 1)?, byte(buf, 2)?, 3),
+        (0, field_code) => (byte(buf, 1)?, field_code, 2),
+        (type_code, 0) => (type_code, byte(buf, 1)?, 2),
+        (type_code, field_code) => (type_code, field_code, 1),
+    };
+
+    Ok(((type_code << 16) | field_code, len))
+}
+
+/// Encodes a field header
+pub(crate) fn encode_header(field_id: u32) -> Vec<u8> {
+    let mut header = [0; 3];
+    let len = crate::sto::encode_header(field_id, &mut header);
+
+    header[..len].to_vec()
+}
+
+/// Decodes a VL prefix, returns the length and the prefix length
+pub(crate) fn vl_length(buf: &[u8]) -> Result<(usize, usize), Malformed> {
+    let b0 = byte(buf, 0)? as usize;
+
+    match b0 {
+        0..=192 => Ok((b0, 1)),
+        193..=240 => Ok((193 + (b0 - 193) * 256 + byte(buf, 1)? as usize, 2)),
+        241..=254 => Ok((
+            12481 + (b0 - 241) * 65536 + byte(buf, 1)? as usize * 256 + byte(buf, 2)? as usize,
+            3,
+        )),
+        _ => Err(Malformed),
+    }
+}
+
+/// Encodes a VL prefix
+pub(crate) fn encode_vl(len: usize) -> Vec<u8> {
+    match len {
+        0..=192 => std::vec![len as u8],
+        193..=12480 => {
+            let len = len - 193;
+            std::vec![193 + (len >> 8) as u8, len as u8]
+        }
+        _ => {
+            let len = len - 12481;
+            std::vec![241 + (len >> 16) as u8, (len >> 8) as u8, len as u8]
+        }
+    }
+}
+
+fn byte(buf: &[u8], i: usize) -> Result<u32, Malformed> {
+    buf.get(i).map(|b| *b as u32).ok_or(Malformed)
+}
+
+/// Reads the field starting at `start`
+pub(crate) fn field_at(buf: &[u8], start: usize) -> Result<Field, Malformed> {
+    let (id, header_len) = header(&buf[start..])?;
+    let value_start = start + header_len;
+    let rest = &buf[value_start..];
+
+    let fixed = |len: usize| {
+        if rest.len() < len {
+            Err(Malformed)
+        } else {
+            Ok((
+                value_start..value_start + len,
+                value_start..value_start + len,
+                len,
+            ))
+        }
+    };
+
+    let (value, payload, skip) = match id >> 16 {
+        STI_UINT8 => fixed(1)?,
+        STI_UINT16 => fixed(2)?,
+        STI_UINT32 => fixed(4)?,
+        STI_UINT64 => fixed(8)?,
+        STI_UINT96 => fixed(12)?,
+        STI_HASH128 => fixed(16)?,
+        STI_HASH160 | STI_CURRENCY => fixed(20)?,
+        STI_UINT192 => fixed(24)?,
+        STI_HASH256 => fixed(32)?,
+        STI_UINT384 => fixed(48)?,
+        STI_UINT512 => fixed(64)?,
+        STI_AMOUNT => fixed(if byte(rest, 0)? & 0x80 == 0 { 8 } else { 48 })?,
+        STI_ISSUE => fixed(if rest.iter().take(20).all(|b| *b == 0) {
+            20
+        } else {
+            40
+        })?,
+        STI_VL | STI_ACCOUNT | STI_VECTOR256 => {
+            let (len, prefix) = vl_length(rest)?;
+            if rest.len() < prefix + len {
+                return Err(Malformed);
+            }
+            (
+                value_start..value_start + prefix + len,
+                value_start + prefix..value_start + prefix + len,
+                prefix + len,
+            )
+        }
+        STI_PATHSET => {
+            let mut i = 0;
+            loop {
+                match byte(rest, i)? {
+                    0x00 => break,
+                    0xFF => i += 1,
+                    kind => i += 1 + 20 * (kind & 0x31).count_ones() as usize,
+                }
+            }
+            if rest.len() < i {
+                return Err(Malformed);
+            }
+            (
+                value_start..value_start + i + 1,
+                value_start..value_start + i + 1,
+                i + 1,
+            )
+        }
+        STI_OBJECT | STI_ARRAY => {
+            let end_marker = if id >> 16 == STI_OBJECT {
+                OBJECT_END
+            } else {
+                ARRAY_END
+            };
+            let mut pos = value_start;
+            loop {
+                if header(&buf[pos..])?.0 == end_marker {
+                    break;
+                }
+                pos = field_at(buf, pos)?.whole.end;
+            }
+            (value_start..pos, value_start..pos, pos + 1 - value_start)
+        }
+        _ => return Err(Malformed),
+    };
+
+    Ok(Field {
+        id,
+        whole: start..value_start + skip,
+        value,
+        payload,
+    })
+}
+
+/// Reads all fields of an object or all elements of an array
+pub(crate) fn fields(buf: &[u8]) -> Result<Vec<Field>, Malformed> {
+    let mut fields = Vec::new();
+    let mut pos = 0;
+    while pos < buf.len() {
+        let field = field_at(buf, pos)?;
+        pos = field.whole.end;
+        fields.push(field);
+    }
+
+    Ok(fields)
+}
+
+/// Finds a field in an object
+pub(crate) fn find(buf: &[u8], field_id: u32) -> Result<Option<Field>, Malformed> {
+    Ok(fields(buf)?.into_iter().find(|field| field.id == field_id))
+}
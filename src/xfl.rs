@@ -0,0 +1,968 @@
+use crate::api::{Err, Error, Ok, Result, XFLCompareMode, XFL};
+use core::cmp::Ordering;
+
+pub(crate) const MANTISSA_MASK: i64 = (1 << 54) - 1;
+pub(crate) const EXPONENT_MASK: i64 = 0xFF << 54;
+pub(crate) const EXPONENT_BIAS: i32 = 97;
+pub(crate) const MIN_MANTISSA: i64 = 1_000_000_000_000_000;
+pub(crate) const MAX_MANTISSA: i64 = 9_999_999_999_999_999;
+pub(crate) const MIN_EXPONENT: i32 = -96;
+pub(crate) const MAX_EXPONENT: i32 = 80;
+
+const SIGN_BIT: i64 = 1 << 62;
+const INT64_LOG10_FLOOR: i32 = 18;
+
+/// Zero represented as XFL
+pub const ZERO: XFL = XFL::from_raw(0);
+
+/// One represented as XFL
+pub const ONE: XFL = XFL::from_raw(6089866696204910592);
+
+const POW10: [u128; 30] = {
+    let mut table = [1u128; 30];
+    let mut i = 1;
+    while i < 30 {
+        table[i] = table[i - 1] * 10;
+        i += 1;
+    }
+    table
+};
+
+// Smallest integers for which the host's `(int32_t)log10((double)n)` reaches 1, 2, ..., 18.
+// Above 10^14 the conversion to double rounds up to the next power of ten.
+const LOG10_FLOOR_THRESHOLDS: [u64; 18] = [
+    10,
+    100,
+    1_000,
+    10_000,
+    100_000,
+    1_000_000,
+    10_000_000,
+    100_000_000,
+    1_000_000_000,
+    10_000_000_000,
+    100_000_000_000,
+    1_000_000_000_000,
+    10_000_000_000_000,
+    100_000_000_000_000,
+    999_999_999_999_998,
+    9_999_999_999_999_979,
+    99_999_999_999_999_593,
+    999_999_999_999_995_840,
+];
+
+/// Low 32 bits of a `u64`
+const LOW: u64 = 0xFFFF_FFFF;
+
+/// Quotient and remainder of `a * b / d`, `None` if `d` is zero or the quotient
+/// doesn't fit in 64 bits
+///
+/// Works on 32-bit halves, like `divlu` of Hacker's Delight, so that the hook has
+/// neither 128-bit arithmetic nor loops, which would need guards.
+#[inline(always)]
+pub(crate) const fn mul_div(a: u64, b: u64, d: u64) -> Option<(u64, u64)> {
+    // 128-bit product, as its high and low 64 bits
+    let (a1, a0) = (a >> 32, a & LOW);
+    let (b1, b0) = (b >> 32, b & LOW);
+    let (p11, p10, p01, p00) = (a1 * b1, a1 * b0, a0 * b1, a0 * b0);
+    let middle = (p00 >> 32) + (p10 & LOW) + (p01 & LOW);
+    let high = p11 + (p10 >> 32) + (p01 >> 32) + (middle >> 32);
+    let low = (middle << 32) | (p00 & LOW);
+    if high >= d {
+        return None;
+    }
+
+    Some(div_wide(high, low, d))
+}
+
+/// Quotient and remainder of `n / d`, for a non-zero `d`
+///
+/// Divides the high half first, then the rest like [mul_div]: the 128-bit division
+/// of `compiler_builtins` loops.
+#[inline(always)]
+const fn div_rem(n: u128, d: u64) -> (u128, u64) {
+    let high = (n >> 64) as u64;
+    let (quotient, remainder) = div_wide(high % d, n as u64, d);
+    (((high / d) as u128) << 64 | quotient as u128, remainder)
+}
+
+/// Quotient and remainder of `(high << 64 | low) / d`, for `high < d`
+#[inline(always)]
+const fn div_wide(high: u64, low: u64, d: u64) -> (u64, u64) {
+    // Shift the divisor so that its top bit is set, for the quotient digit estimates
+    let shift = d.leading_zeros();
+    let d = d << shift;
+    let high = match shift {
+        0 => high,
+        shift => (high << shift) | (low >> (64 - shift)),
+    };
+    let low = low << shift;
+
+    let (q1, remainder) = div_digit(high, low >> 32, d);
+    let (q0, remainder) = div_digit(remainder, low & LOW, d);
+    ((q1 << 32) | q0, remainder >> shift)
+}
+
+/// Quotient digit and remainder of `(n << 32 | digit) / d`, for `n < d` and `d`
+/// with its top bit set
+///
+/// The digit estimated from the high half of `d` is at most 2 too large.
+#[inline(always)]
+const fn div_digit(n: u64, digit: u64, d: u64) -> (u64, u64) {
+    // The top bit is already set, setting it again shows that `d1` isn't zero,
+    // so that the divisions don't check it
+    let (d1, d0) = (d >> 32 | 1 << 31, d & LOW);
+    let mut q = n / d1;
+    let mut r = n % d1;
+    if q > LOW || q * d0 > (r << 32 | digit) {
+        q -= 1;
+        r += d1;
+        if r <= LOW && (q > LOW || q * d0 > (r << 32 | digit)) {
+            q -= 1;
+        }
+    }
+
+    // The remainder is less than `d`, the wrapped high bits cancel out
+    let remainder = (n << 32 | digit).wrapping_sub(q.wrapping_mul(d));
+    (q, remainder)
+}
+
+// Runs `body` for each index. A `const fn` can't call `_g`, so its loops would be
+// rejected in a hook, these are unrolled instead.
+macro_rules! unroll {
+    ($i:ident in [$($n:literal)*] $body:block) => {
+        $({
+            let $i: usize = $n;
+            $body
+        })*
+    };
+}
+
+#[inline(always)]
+const fn pow10(n: i32) -> i64 {
+    POW10[n as usize] as i64
+}
+
+#[inline(always)]
+const fn log10_floor(n: u64) -> i32 {
+    let mut order = 0;
+    unroll!(i in [0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17] {
+        if n >= LOG10_FLOOR_THRESHOLDS[i] {
+            order += 1;
+        }
+    });
+    order
+}
+
+#[inline(always)]
+const fn log10_ceil(n: u128) -> i32 {
+    let mut order = 0;
+    unroll!(i in [0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26 27 28] {
+        if POW10[i] < n {
+            order += 1;
+        }
+    });
+    order
+}
+
+// Number of digits after the first one, exactly unlike `log10_floor`
+#[inline(always)]
+const fn digits_after_first(n: u64) -> i32 {
+    log10_ceil(n as u128 + 1) - 1
+}
+
+#[inline(always)]
+pub(crate) const fn mantissa(float: XFL) -> i64 {
+    float.to_raw() & MANTISSA_MASK
+}
+
+#[inline(always)]
+pub(crate) const fn exponent(float: XFL) -> i32 {
+    if float.to_raw() == 0 {
+        return 0;
+    }
+    (((float.to_raw() & EXPONENT_MASK) >> 54) as i32) - EXPONENT_BIAS
+}
+
+#[inline(always)]
+pub(crate) const fn is_negative(float: XFL) -> bool {
+    float.to_raw() & SIGN_BIT == 0
+}
+
+#[inline(always)]
+pub(crate) const fn is_valid(float: XFL) -> bool {
+    if float.to_raw() < 0 {
+        return false;
+    }
+
+    if float.to_raw() == 0 {
+        return true;
+    }
+
+    let mantissa = mantissa(float);
+    let exponent = exponent(float);
+
+    mantissa >= MIN_MANTISSA
+        && mantissa <= MAX_MANTISSA
+        && exponent >= MIN_EXPONENT
+        && exponent <= MAX_EXPONENT
+}
+
+#[inline(always)]
+const fn make_float(mantissa: i64, exponent: i32, negative: bool) -> Result<XFL> {
+    if mantissa == 0 {
+        return Ok(ZERO);
+    }
+
+    if mantissa > MAX_MANTISSA {
+        return Err(Error::MantissaOversized);
+    }
+
+    if mantissa < MIN_MANTISSA {
+        return Err(Error::MantissaUndersized);
+    }
+
+    if exponent > MAX_EXPONENT {
+        return Err(Error::ExponentOversized);
+    }
+
+    if exponent < MIN_EXPONENT {
+        return Err(Error::ExponentUndersized);
+    }
+
+    let sign = if negative { 0 } else { SIGN_BIT };
+
+    Ok(XFL::from_raw(
+        sign | (((exponent + EXPONENT_BIAS) as i64) << 54) | mantissa,
+    ))
+}
+
+const fn normalize(mut mantissa: i64, mut exponent: i32, mut negative: bool) -> Result<XFL> {
+    if mantissa == 0 {
+        return Ok(ZERO);
+    }
+
+    if mantissa == i64::MIN {
+        mantissa += 1;
+    }
+
+    if mantissa < 0 {
+        mantissa = -mantissa;
+        negative = true;
+    }
+
+    let adjust = 15 - log10_floor(mantissa as u64);
+
+    if adjust > 0 {
+        mantissa *= pow10(adjust);
+        exponent = exponent.saturating_sub(adjust);
+    } else if adjust < 0 {
+        mantissa /= pow10(-adjust);
+        exponent = exponent.saturating_sub(adjust);
+    }
+
+    // even after adjustment the mantissa can be outside the range by one place
+    if mantissa < MIN_MANTISSA {
+        mantissa *= 10;
+        exponent = exponent.saturating_sub(1);
+    }
+
+    if mantissa > MAX_MANTISSA {
+        mantissa /= 10;
+        exponent = exponent.saturating_add(1);
+    }
+
+    if exponent < MIN_EXPONENT {
+        return Ok(ZERO);
+    }
+
+    if exponent > MAX_EXPONENT {
+        return Err(Error::XflOverflow);
+    }
+
+    make_float(mantissa, exponent, negative)
+}
+
+// Normalization of an IOU amount, used by the host for sums, comparisons and ratios.
+// Returns a zero mantissa for amounts too small to be represented.
+const fn normalize_iou(mut mantissa: i64, mut exponent: i32) -> Result<(i64, i32)> {
+    if mantissa == 0 {
+        return Ok((0, 0));
+    }
+
+    let negative = mantissa < 0;
+    if negative {
+        mantissa = -mantissa;
+    }
+
+    // scale up by the missing digits, as far as the exponent allows
+    let missing = 15 - digits_after_first(mantissa as u64);
+    if missing > 0 && exponent > MIN_EXPONENT {
+        let shift = if missing < exponent - MIN_EXPONENT {
+            missing
+        } else {
+            exponent - MIN_EXPONENT
+        };
+        mantissa *= pow10(shift);
+        exponent -= shift;
+    }
+
+    // scale down by the extra digits, overflowing if the exponent reaches the maximum
+    // before the last one is dropped
+    let extra = digits_after_first(mantissa as u64) - 15;
+    if extra > 0 {
+        if exponent + extra > MAX_EXPONENT {
+            return Err(Error::XflOverflow);
+        }
+        mantissa /= pow10(extra);
+        exponent += extra;
+    }
+
+    if exponent < MIN_EXPONENT || mantissa < MIN_MANTISSA {
+        return Ok((0, 0));
+    }
+
+    if exponent > MAX_EXPONENT {
+        return Err(Error::XflOverflow);
+    }
+
+    if negative {
+        mantissa = -mantissa;
+    }
+
+    Ok((mantissa, exponent))
+}
+
+#[inline(always)]
+const fn from_iou(mantissa: i64, exponent: i32) -> Result<XFL> {
+    if mantissa == 0 {
+        return Ok(ZERO);
+    }
+
+    make_float(mantissa.abs(), exponent, mantissa < 0)
+}
+
+// Drops the last `digits` digits of a mantissa of at most 17 digits
+#[inline(always)]
+const fn shift_right(mantissa: i64, digits: i32) -> i64 {
+    if digits > 17 {
+        0
+    } else {
+        mantissa / pow10(digits)
+    }
+}
+
+#[inline(always)]
+const fn signed_mantissa(float: XFL) -> i64 {
+    if is_negative(float) {
+        -mantissa(float)
+    } else {
+        mantissa(float)
+    }
+}
+
+/// Create a float from an exponent and mantissa
+///
+/// Returns [Error::ExponentUndersized] if the number is too small to be represented.
+pub const fn float_set(exponent: i32, mantissa: i64) -> Result<XFL> {
+    if mantissa == 0 {
+        return Ok(ZERO);
+    }
+
+    match normalize(mantissa, exponent, false) {
+        Ok(float) if float.is_zero() => Err(Error::ExponentUndersized),
+        res => res,
+    }
+}
+
+/// Return the number 1 represented in an XFL enclosing number
+#[inline(always)]
+pub const fn float_one() -> XFL {
+    ONE
+}
+
+/// Get the mantissa of an XFL enclosing number
+pub const fn float_mantissa(float: XFL) -> Result<i64> {
+    if !is_valid(float) {
+        return Err(Error::InvalidFloat);
+    }
+
+    Ok(mantissa(float))
+}
+
+/// Get the sign of an XFL enclosing number
+pub const fn float_sign(float: XFL) -> Result<bool> {
+    if !is_valid(float) {
+        return Err(Error::InvalidFloat);
+    }
+
+    Ok(!float.is_zero() && is_negative(float))
+}
+
+/// Negate an XFL floating point number
+pub const fn float_negate(float: XFL) -> Result<XFL> {
+    if float.is_zero() {
+        return Ok(ZERO);
+    }
+
+    if !is_valid(float) {
+        return Err(Error::InvalidFloat);
+    }
+
+    Ok(XFL::from_raw(float.to_raw() ^ SIGN_BIT))
+}
+
+/// Multiply two XFL numbers together
+pub const fn float_multiply(float1: XFL, float2: XFL) -> Result<XFL> {
+    if !is_valid(float1) || !is_valid(float2) {
+        return Err(Error::InvalidFloat);
+    }
+
+    if float1.is_zero() || float2.is_zero() {
+        return Ok(ZERO);
+    }
+
+    let product = mantissa(float1) as u128 * mantissa(float2) as u128;
+    let (product, _) = div_rem(product, POW10[15] as u64);
+    let exponent = exponent(float1) + exponent(float2) + 15;
+    let negative = is_negative(float1) != is_negative(float2);
+
+    match normalize(product as i64, exponent, negative) {
+        Err(Error::ExponentUndersized) => Ok(ZERO),
+        Err(Error::ExponentOversized) => Err(Error::XflOverflow),
+        res => res,
+    }
+}
+
+/// Divide an XFL by another XFL floating point number
+// the remainder of the last digit isn't needed
+#[allow(unused_assignments)]
+pub const fn float_divide(float1: XFL, float2: XFL) -> Result<XFL> {
+    if !is_valid(float1) || !is_valid(float2) {
+        return Err(Error::InvalidFloat);
+    }
+
+    if float2.is_zero() {
+        return Err(Error::DivisionByZero);
+    }
+
+    if float1.is_zero() {
+        return Ok(ZERO);
+    }
+
+    if float2.to_raw() == ONE.to_raw() {
+        return Ok(float1);
+    }
+
+    let mut mantissa1 = mantissa(float1);
+    let exponent1 = exponent(float1);
+    let mut mantissa2 = mantissa(float2);
+    let mut exponent2 = exponent(float2);
+
+    // both mantissas have 16 digits, so aligning them takes one step at most
+    if mantissa2 > mantissa1 {
+        mantissa2 /= 10;
+        exponent2 += 1;
+    }
+
+    if mantissa2 < mantissa1 && mantissa2 * 10 <= mantissa1 {
+        mantissa2 *= 10;
+        exponent2 -= 1;
+    }
+
+    // long division by the divisor truncated by one more digit for each digit
+    // of the quotient, until nothing is left of its 16 digits
+    let mut mantissa3: i64 = 0;
+    let exponent3 = exponent1 - exponent2 - digits_after_first(mantissa2 as u64);
+
+    unroll!(i in [0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15] {
+        let divisor = mantissa2 / pow10(i as i32);
+        if divisor > 0 {
+            mantissa3 = mantissa3 * 10 + mantissa1 / divisor;
+            mantissa1 %= divisor;
+        }
+    });
+
+    normalize(
+        mantissa3,
+        exponent3,
+        is_negative(float1) != is_negative(float2),
+    )
+}
+
+/// Divide one by an XFL floating point number
+pub const fn float_invert(float: XFL) -> Result<XFL> {
+    if float.is_zero() {
+        return Err(Error::DivisionByZero);
+    }
+
+    if float.to_raw() == ONE.to_raw() {
+        return Ok(ONE);
+    }
+
+    float_divide(ONE, float)
+}
+
+/// Add two XFL numbers together
+pub const fn float_sum(float1: XFL, float2: XFL) -> Result<XFL> {
+    if !is_valid(float1) || !is_valid(float2) {
+        return Err(Error::InvalidFloat);
+    }
+
+    if float1.is_zero() {
+        return Ok(float2);
+    }
+
+    if float2.is_zero() {
+        return Ok(float1);
+    }
+
+    let mut mantissa1 = signed_mantissa(float1);
+    let mut exponent1 = exponent(float1);
+    let mut mantissa2 = signed_mantissa(float2);
+    let exponent2 = exponent(float2);
+
+    // the mantissa of the smaller exponent loses its last digits
+    if exponent1 < exponent2 {
+        mantissa1 = shift_right(mantissa1, exponent2 - exponent1);
+        exponent1 = exponent2;
+    }
+
+    if exponent2 < exponent1 {
+        mantissa2 = shift_right(mantissa2, exponent1 - exponent2);
+    }
+
+    let sum = mantissa1 + mantissa2;
+    if sum >= -10 && sum <= 10 {
+        return Ok(ZERO);
+    }
+
+    match normalize_iou(sum, exponent1) {
+        Ok((mantissa, exponent)) => from_iou(mantissa, exponent),
+        Err(e) => Err(e),
+    }
+}
+
+/// Compare two XFL floating point numbers
+pub const fn float_cmp(float1: XFL, float2: XFL) -> Result<Ordering> {
+    if !is_valid(float1) || !is_valid(float2) {
+        return Err(Error::InvalidFloat);
+    }
+
+    let negative1 = !float1.is_zero() && is_negative(float1);
+    let negative2 = !float2.is_zero() && is_negative(float2);
+
+    if negative1 != negative2 {
+        return Ok(if negative1 {
+            Ordering::Less
+        } else {
+            Ordering::Greater
+        });
+    }
+
+    if float1.is_zero() || float2.is_zero() {
+        return Ok(if float1.is_zero() && float2.is_zero() {
+            Ordering::Equal
+        } else if float1.is_zero() {
+            Ordering::Less
+        } else {
+            Ordering::Greater
+        });
+    }
+
+    let exponent1 = exponent(float1);
+    let exponent2 = exponent(float2);
+    let mantissa1 = mantissa(float1);
+    let mantissa2 = mantissa(float2);
+
+    let magnitude = if exponent1 != exponent2 {
+        if exponent1 < exponent2 {
+            Ordering::Less
+        } else {
+            Ordering::Greater
+        }
+    } else if mantissa1 != mantissa2 {
+        if mantissa1 < mantissa2 {
+            Ordering::Less
+        } else {
+            Ordering::Greater
+        }
+    } else {
+        Ordering::Equal
+    };
+
+    Ok(if negative1 {
+        magnitude.reverse()
+    } else {
+        magnitude
+    })
+}
+
+/// Perform a comparison on two XFL floating point numbers
+pub const fn float_compare(float1: XFL, float2: XFL, mode: XFLCompareMode) -> Result<bool> {
+    let ordering = match float_cmp(float1, float2) {
+        Ok(ordering) => ordering,
+        Err(e) => return Err(e),
+    };
+
+    Ok(match mode {
+        XFLCompareMode::Less => ordering.is_lt(),
+        XFLCompareMode::Equal => ordering.is_eq(),
+        XFLCompareMode::Greater => ordering.is_gt(),
+        XFLCompareMode::NotEqual => ordering.is_ne(),
+        XFLCompareMode::LessOrEqual => ordering.is_le(),
+        XFLCompareMode::GreaterOrEqual => ordering.is_ge(),
+    })
+}
+
+/// Convert an XFL floating point into an integer (floor)
+pub const fn float_int(float: XFL, decimal_places: u32, absolute: bool) -> Result<i64> {
+    if !is_valid(float) {
+        return Err(Error::InvalidFloat);
+    }
+
+    if float.is_zero() {
+        return Ok(0);
+    }
+
+    if decimal_places > 15 {
+        return Err(Error::InvalidArgument);
+    }
+
+    if is_negative(float) && !absolute {
+        return Err(Error::CantReturnNegative);
+    }
+
+    let shift = -(exponent(float) + decimal_places as i32);
+
+    if shift > 15 {
+        return Ok(0);
+    }
+
+    if shift < 0 {
+        return Err(Error::TooBig);
+    }
+
+    Ok(mantissa(float) / pow10(shift))
+}
+
+/// Multiply an XFL floating point by a non-XFL numerator and denominator
+pub const fn float_mulratio(
+    float: XFL,
+    round_up: bool,
+    numerator: u32,
+    denominator: u32,
+) -> Result<XFL> {
+    if !is_valid(float) {
+        return Err(Error::InvalidFloat);
+    }
+
+    if float.is_zero() {
+        return Ok(ZERO);
+    }
+
+    if denominator == 0 {
+        return Err(Error::DivisionByZero);
+    }
+
+    let denominator = denominator as u64;
+    let product = mantissa(float) as u128 * numerator as u128;
+    let mut exponent = exponent(float);
+
+    let (mut low, remainder) = div_rem(product, denominator);
+    let mut remainder = remainder as u128;
+
+    if remainder != 0 {
+        let room_to_grow = INT64_LOG10_FLOOR - log10_ceil(low);
+        if room_to_grow > 0 {
+            exponent -= room_to_grow;
+            low *= POW10[room_to_grow as usize];
+            remainder *= POW10[room_to_grow as usize];
+        }
+        let (add, rest) = div_rem(remainder, denominator);
+        low += add;
+        remainder = rest as u128;
+    }
+
+    let mut has_remainder = remainder != 0;
+    let must_shrink = log10_ceil(low) - INT64_LOG10_FLOOR;
+    if must_shrink > 0 {
+        exponent += must_shrink;
+        let (shrunk, dropped) = div_rem(low, POW10[must_shrink as usize] as u64);
+        low = shrunk;
+        has_remainder = has_remainder || dropped != 0;
+    }
+
+    let (mut mantissa, mut exponent) = match normalize_iou(low as i64, exponent) {
+        Ok(res) => res,
+        Err(e) => return Err(e),
+    };
+
+    if has_remainder && round_up {
+        if mantissa == 0 {
+            mantissa = MIN_MANTISSA;
+            exponent = MIN_EXPONENT;
+        } else {
+            match normalize_iou(mantissa + 1, exponent) {
+                Ok((m, e)) => {
+                    mantissa = m;
+                    exponent = e;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    if mantissa == 0 {
+        return Ok(ZERO);
+    }
+
+    make_float(mantissa, exponent, is_negative(float))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const fn xfl(exponent: i32, mantissa: i64) -> XFL {
+        match float_set(exponent, mantissa) {
+            Ok(float) => float,
+            Err(_) => panic!(),
+        }
+    }
+
+    fn parts(float: XFL) -> (bool, i64, i32) {
+        (is_negative(float), mantissa(float), exponent(float))
+    }
+
+    #[test]
+    fn float_set_test() {
+        const TWO: XFL = xfl(0, 2);
+        assert_eq!(xfl(-15, 1_000_000_000_000_000).to_raw(), ONE.to_raw());
+        assert_eq!(xfl(0, 1).to_raw(), ONE.to_raw());
+        assert_eq!(parts(TWO), (false, 2_000_000_000_000_000, -15));
+        assert_eq!(parts(xfl(-2, -12345)), (true, 1_234_500_000_000_000, -13));
+        assert_eq!(
+            parts(xfl(0, 99_999_999_999_999_999)),
+            (false, 9_999_999_999_999_990, 1)
+        );
+        assert!(float_set(0, 0).is_ok());
+        assert!(matches!(float_set(-200, 1), Err(Error::ExponentUndersized)));
+        assert!(matches!(float_set(200, 1), Err(Error::XflOverflow)));
+    }
+
+    #[test]
+    fn float_negate_test() {
+        let minus_one = float_negate(ONE);
+        match minus_one {
+            Ok(float) => assert_eq!(parts(float), (true, MIN_MANTISSA, -15)),
+            Err(_) => panic!(),
+        }
+        assert!(matches!(float_negate(ZERO), Ok(float) if float.is_zero()));
+        assert!(matches!(float_sign(xfl(0, -1)), Ok(true)));
+        assert!(matches!(float_sign(ZERO), Ok(false)));
+        assert!(float_negate(XFL::from_raw(-1)).is_err());
+    }
+
+    #[test]
+    fn float_multiply_test() {
+        match float_multiply(xfl(0, 2), xfl(0, -3)) {
+            Ok(float) => assert_eq!(float.to_raw(), xfl(0, -6).to_raw()),
+            Err(_) => panic!(),
+        }
+        match float_multiply(ONE, ONE) {
+            Ok(float) => assert_eq!(float.to_raw(), ONE.to_raw()),
+            Err(_) => panic!(),
+        }
+        assert!(matches!(float_multiply(ZERO, ONE), Ok(float) if float.is_zero()));
+        assert!(matches!(
+            float_multiply(xfl(80, 1), xfl(80, 1)),
+            Err(Error::XflOverflow)
+        ));
+        assert!(matches!(float_multiply(xfl(-80, 1), xfl(-80, 1)), Ok(float) if float.is_zero()));
+    }
+
+    #[test]
+    fn float_divide_test() {
+        match float_divide(xfl(0, 10), xfl(0, 4)) {
+            Ok(float) => assert_eq!(parts(float), (false, 2_500_000_000_000_000, -15)),
+            Err(_) => panic!(),
+        }
+        match float_divide(xfl(0, -1), xfl(0, 3)) {
+            Ok(float) => assert_eq!(parts(float), (true, 3_333_333_333_333_330, -16)),
+            Err(_) => panic!(),
+        }
+        match float_invert(xfl(0, 8)) {
+            Ok(float) => assert_eq!(parts(float), (false, 1_250_000_000_000_000, -16)),
+            Err(_) => panic!(),
+        }
+        assert!(matches!(
+            float_divide(ONE, ZERO),
+            Err(Error::DivisionByZero)
+        ));
+        assert!(matches!(float_invert(ZERO), Err(Error::DivisionByZero)));
+    }
+
+    #[test]
+    fn float_sum_test() {
+        match float_sum(xfl(0, 1), xfl(-1, 5)) {
+            Ok(float) => assert_eq!(parts(float), (false, 1_500_000_000_000_000, -15)),
+            Err(_) => panic!(),
+        }
+        match float_sum(xfl(0, 1), xfl(0, -3)) {
+            Ok(float) => assert_eq!(float.to_raw(), xfl(0, -2).to_raw()),
+            Err(_) => panic!(),
+        }
+        assert!(matches!(float_sum(ONE, xfl(0, -1)), Ok(float) if float.is_zero()));
+        assert!(matches!(float_sum(ZERO, ONE), Ok(float) if float.to_raw() == ONE.to_raw()));
+        assert!(matches!(
+            float_sum(xfl(95, 9), xfl(95, 9)),
+            Err(Error::XflOverflow)
+        ));
+    }
+
+    #[test]
+    fn float_compare_test() {
+        let minus_two = xfl(0, -2);
+        assert!(matches!(float_cmp(ONE, ONE), Ok(Ordering::Equal)));
+        assert!(matches!(float_cmp(minus_two, ONE), Ok(Ordering::Less)));
+        assert!(matches!(
+            float_cmp(xfl(0, -1), minus_two),
+            Ok(Ordering::Greater)
+        ));
+        assert!(matches!(float_cmp(ZERO, minus_two), Ok(Ordering::Greater)));
+        assert!(matches!(float_cmp(ZERO, ONE), Ok(Ordering::Less)));
+        assert!(matches!(
+            float_cmp(xfl(1, 1), xfl(0, 9)),
+            Ok(Ordering::Greater)
+        ));
+        assert!(matches!(
+            float_compare(ONE, minus_two, XFLCompareMode::GreaterOrEqual),
+            Ok(true)
+        ));
+        assert!(matches!(
+            float_compare(ONE, ONE, XFLCompareMode::NotEqual),
+            Ok(false)
+        ));
+    }
+
+    #[test]
+    fn float_int_test() {
+        assert!(matches!(float_int(xfl(-2, 12345), 0, false), Ok(123)));
+        assert!(matches!(float_int(xfl(-2, 12345), 2, false), Ok(12345)));
+        assert!(matches!(float_int(xfl(-20, 1), 0, false), Ok(0)));
+        assert!(matches!(
+            float_int(xfl(0, -1), 0, false),
+            Err(Error::CantReturnNegative)
+        ));
+        assert!(matches!(float_int(xfl(0, -1), 0, true), Ok(1)));
+        assert!(matches!(
+            float_int(xfl(20, 1), 0, false),
+            Err(Error::TooBig)
+        ));
+        assert!(matches!(
+            float_int(ONE, 16, false),
+            Err(Error::InvalidArgument)
+        ));
+    }
+
+    #[test]
+    fn host_vectors_test() {
+        // outputs of the xahaud host functions in its hook API tests
+        let raw = |res: Result<XFL>| match res {
+            Ok(float) => float.to_raw(),
+            Err(e) => panic!("{}", e.code()),
+        };
+        let float = XFL::from_raw;
+
+        assert_eq!(raw(float_set(-5, 6541432897943971)), 6275552114197674403);
+        assert_eq!(raw(float_set(37, -8019384286534438)), 2421948784557120294);
+        assert_eq!(raw(float_set(-54, -3477931844992923)), 778097067752718235);
+
+        // 1 / 10
+        assert_eq!(
+            raw(float_divide(ONE, float(6107881094714392576))),
+            6071852297695428608
+        );
+        // 9.134546514878452e-81 / 1
+        assert_eq!(
+            raw(float_divide(float(4638834963451748340), ONE)),
+            4638834963451748340
+        );
+        // 9.134546514878452e-81 / 1.41649684651e+75
+        assert_eq!(
+            raw(float_divide(
+                float(4638834963451748340),
+                float(7441363081262569392)
+            )),
+            0
+        );
+        // 1.3546984132111e+42 / 9.134546514878452e-81
+        assert!(matches!(
+            float_divide(float(6846826132016365020), float(4638834963451748340)),
+            Err(Error::XflOverflow)
+        ));
+
+        // 1 + 1
+        assert_eq!(raw(float_sum(ONE, ONE)), 6090866696204910592);
+        // 45678 + 0.345678
+        assert_eq!(
+            raw(float_sum(
+                float(6165492090242838528),
+                float(6074309077695428608)
+            )),
+            6165492124810638528
+        );
+        // -5.713362295774553e-77 + 5.248821377668419e84
+        assert_eq!(
+            raw(float_sum(
+                float(95785354843184473),
+                float(7607324992379065667)
+            )),
+            7607324992379065667
+        );
+    }
+
+    #[test]
+    fn div_rem_test() {
+        let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        for i in 0..10_000 {
+            let n = (next() as u128) << (i % 64) | next() as u128;
+            let d = next() >> (i % 64) | 1;
+            let (quotient, remainder) = div_rem(n, d);
+            assert_eq!(quotient, n / d as u128, "{n} / {d}");
+            assert_eq!(remainder as u128, n % d as u128, "{n} % {d}");
+        }
+        assert_eq!(div_rem(u128::MAX, 1), (u128::MAX, 0));
+        assert_eq!(
+            div_rem(u128::MAX, u64::MAX),
+            (u128::MAX / u64::MAX as u128, 0)
+        );
+    }
+
+    #[test]
+    fn float_mulratio_test() {
+        match float_mulratio(xfl(0, 10), false, 1, 3) {
+            Ok(float) => assert_eq!(parts(float), (false, 3_333_333_333_333_333, -15)),
+            Err(_) => panic!(),
+        }
+        match float_mulratio(xfl(0, 10), true, 1, 3) {
+            Ok(float) => assert_eq!(parts(float), (false, 3_333_333_333_333_334, -15)),
+            Err(_) => panic!(),
+        }
+        match float_mulratio(xfl(0, -3), false, 2, 1) {
+            Ok(float) => assert_eq!(float.to_raw(), xfl(0, -6).to_raw()),
+            Err(_) => panic!(),
+        }
+        assert!(matches!(
+            float_mulratio(ONE, false, 1, 0),
+            Err(Error::DivisionByZero)
+        ));
+    }
+}
@@ -0,0 +1,64 @@
+//! Entry points generated by the `hook` and `cbak` attributes, run on the testing host
+
+use rshooks_api::testing::MockLedger;
+use rshooks_api::*;
+
+#[hook]
+fn main(ctx: ExecutionContext) {
+    if ctx.is_weak() {
+        rollback(b"weak", 1);
+    }
+}
+
+#[cbak]
+fn callback(ctx: CallbackContext) {
+    if ctx == CallbackContext::EmitFailure {
+        rollback(b"emit failed", 2);
+    }
+}
+
+const INVALID: &[u8] = b"invalid entry point argument";
+
+#[test]
+fn hook_test() {
+    let mut ledger = MockLedger::new();
+
+    let outcome = ledger.run(|| hook(0));
+    assert!(outcome.is_accept());
+    assert_eq!(outcome.message(), b"");
+
+    for reserved in [1, 2] {
+        let outcome = ledger.run(|| hook(reserved));
+        assert!(outcome.is_rollback());
+        assert_eq!(outcome.message(), b"weak");
+        assert_eq!(outcome.code(), 1);
+    }
+}
+
+#[test]
+fn cbak_test() {
+    let mut ledger = MockLedger::new();
+
+    let outcome = ledger.run(|| cbak(0));
+    assert!(outcome.is_accept());
+
+    let outcome = ledger.run(|| cbak(1));
+    assert!(outcome.is_rollback());
+    assert_eq!(outcome.message(), b"emit failed");
+    assert_eq!(outcome.code(), 2);
+}
+
+#[test]
+fn invalid_reserved_test() {
+    let mut ledger = MockLedger::new();
+
+    for outcome in [
+        ledger.run(|| hook(3)),
+        ledger.run(|| hook(u32::MAX)),
+        ledger.run(|| cbak(2)),
+    ] {
+        assert!(outcome.is_rollback());
+        assert_eq!(outcome.message(), INVALID);
+        assert_eq!(outcome.code(), Error::InvalidArgument.code() as i64);
+    }
+}